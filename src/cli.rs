@@ -1,7 +1,15 @@
 use crate::Result;
-use crate::commands::{Mob, Setup, TeamMember};
-use crate::repositories::{MobSessionRepo, TeamMemberRepo};
-use clap::{Parser, Subcommand};
+use crate::commands::mob::CoauthorResolver;
+use crate::commands::{
+    History, Hook, Mob, Pingpong, Preset, Schedule, Setup, Stats, SuggestPair, Team, TeamMember,
+    Timer,
+};
+use crate::helpers::CommandRunner;
+use crate::repositories::{
+    CommitLogRepo, HistoryRepo, MobSessionRepo, PresetRepo, TeamGroupRepo, TeamMemberRepo,
+};
+use clap::{CommandFactory, Parser, Subcommand};
+use std::ffi::OsString;
 use std::io::Write;
 use std::str;
 
@@ -47,28 +55,186 @@ enum Commands {
     /// User must store team member(s) to team member repository by using keys
     /// before starting pair/mob programming session(s).
     #[clap(alias = "coauthor")] // alias for backward compatibility
-    TeamMember(TeamMember),
+    TeamMember(Box<TeamMember>),
+    /// Add/delete/list team group(s) of team members
+    ///
+    /// A team group can be used as @name with --with to start a session with all of its team members
+    Team(Team),
+    /// Count down driver rotations of the mob/pair programming session
+    ///
+    /// On expiry, rings the terminal bell and shows who drives next.
+    /// If the session has a driver, the next driver is switched automatically.
+    Timer(Timer),
+    /// Start ping-pong mode which swaps the driver and co-author after every commit
+    ///
+    /// Needs the post-commit githook which setup creates in the githooks directory
+    Pingpong(Pingpong),
+    /// Save/load/delete/list presets of mob/pair programming session co-author(s)
+    ///
+    /// A preset can be the default of a repository, which is used when committing in it with no session
+    Preset(Preset),
+    /// List history of the mob/pair programming sessions
+    ///
+    /// Entries are recorded when sessions start, co-authors are added and sessions end
+    History(History),
+    /// Show who paired with whom from the Co-authored-by trailers of commits in the current repository
+    ///
+    /// Authors and co-authors are resolved to team members by email
+    Stats(Stats),
+    /// Suggest team members to pair with, starting with the least recently paired with
+    ///
+    /// Pairing is based on the Co-authored-by trailers of commits in the current repository.
+    /// A suggestion can be selected to start the mob/pair programming session.
+    SuggestPair(SuggestPair),
+    /// Generate a round-robin pair rotation schedule for team members
+    ///
+    /// Pairs are balanced over the days. With an odd headcount, one pair becomes a trio each day.
+    Schedule(Schedule),
+    /// Run githook logic (invoked by the githooks created by setup)
+    #[command(hide = true)]
+    Hook(Hook),
 }
 
+#[allow(clippy::too_many_arguments)] // one repository per kind of stored data
 pub fn run(
     team_member_repo: &impl TeamMemberRepo,
+    team_group_repo: &impl TeamGroupRepo,
     mob_repo: &impl MobSessionRepo,
+    commit_log_repo: &impl CommitLogRepo,
+    history_repo: &impl HistoryRepo,
+    preset_repo: &impl PresetRepo,
+    command_runner: &impl CommandRunner,
     out: &mut impl Write,
 ) -> Result<()> {
-    let cli = Cli::parse();
-    run_inner(&cli, team_member_repo, mob_repo, out)
+    let cli = Cli::parse_from(rewrite_with_exclusions(
+        std::env::args_os(),
+        team_member_repo,
+    )?);
+    run_inner(
+        &cli,
+        team_member_repo,
+        team_group_repo,
+        mob_repo,
+        commit_log_repo,
+        history_repo,
+        preset_repo,
+        command_runner,
+        out,
+    )
+}
+
+/// Rewrites team member exclusions of --with (e.g. -lm or -@frontend) as --with=-lm
+/// so that they are parsed as values instead of unknown flags
+///
+/// An argument made up of known short flags (e.g. -l) is still a flag unless it is the key or
+/// alias of a team member. An argument which starts with -- is always a flag.
+fn rewrite_with_exclusions(
+    args: impl IntoIterator<Item = OsString>,
+    team_member_repo: &impl TeamMemberRepo,
+) -> Result<Vec<OsString>> {
+    let mut cmd = Cli::command();
+    cmd.build();
+    let shorts: Vec<char> = cmd.get_arguments().filter_map(|x| x.get_short()).collect();
+    // Listed on first use as most arguments of --with are not exclusions
+    let mut resolver = None;
+    let mut is_exclusion = |arg: &str| -> Result<bool> {
+        let Some(token) = arg.strip_prefix('-') else {
+            return Ok(false);
+        };
+        if token.is_empty() || token.starts_with('-') {
+            return Ok(false);
+        }
+        if !token.chars().all(|x| shorts.contains(&x)) {
+            return Ok(true);
+        }
+
+        let resolver = match &mut resolver {
+            Some(resolver) => resolver,
+            None => resolver.insert(CoauthorResolver::new(team_member_repo, false)?),
+        };
+        Ok(resolver.get(token)?.is_some())
+    };
+
+    let mut in_with = false;
+    let mut rewritten = Vec::new();
+    for arg in args {
+        let Some(arg_str) = arg.to_str() else {
+            rewritten.push(arg);
+            continue;
+        };
+        if in_with && is_exclusion(arg_str)? {
+            rewritten.push(format!("--with={arg_str}").into());
+            continue;
+        }
+        if arg_str.starts_with('-') {
+            in_with = arg_str == "--with" || arg_str == "-w";
+        }
+        rewritten.push(arg);
+    }
+
+    Ok(rewritten)
 }
 
+#[allow(clippy::too_many_arguments)] // one repository per kind of stored data
 fn run_inner(
     cli: &Cli,
     team_member_repo: &impl TeamMemberRepo,
+    team_group_repo: &impl TeamGroupRepo,
     mob_repo: &impl MobSessionRepo,
+    commit_log_repo: &impl CommitLogRepo,
+    history_repo: &impl HistoryRepo,
+    preset_repo: &impl PresetRepo,
+    command_runner: &impl CommandRunner,
     out: &mut impl Write,
 ) -> Result<()> {
     match &cli.command {
-        None => cli.mob.handle(team_member_repo, mob_repo, out)?,
+        None => cli.mob.handle(
+            team_member_repo,
+            team_group_repo,
+            mob_repo,
+            preset_repo,
+            history_repo,
+            out,
+        )?,
         Some(Commands::Setup(setup)) => setup.handle(out)?,
-        Some(Commands::TeamMember(team_member)) => team_member.handle(team_member_repo, out)?,
+        Some(Commands::TeamMember(team_member)) => team_member.handle(
+            team_member_repo,
+            team_group_repo,
+            preset_repo,
+            commit_log_repo,
+            command_runner,
+            out,
+        )?,
+        Some(Commands::Team(team)) => team.handle(team_member_repo, team_group_repo, out)?,
+        Some(Commands::Timer(timer)) => timer.handle(team_member_repo, mob_repo, out)?,
+        Some(Commands::Pingpong(pingpong)) => {
+            pingpong.handle(team_member_repo, mob_repo, history_repo, out)?
+        }
+        Some(Commands::Preset(preset)) => {
+            preset.handle(team_member_repo, mob_repo, preset_repo, history_repo, out)?
+        }
+        Some(Commands::History(history)) => history.handle(team_member_repo, history_repo, out)?,
+        Some(Commands::Stats(stats)) => stats.handle(team_member_repo, commit_log_repo, out)?,
+        Some(Commands::SuggestPair(suggest_pair)) => suggest_pair.handle(
+            team_member_repo,
+            team_group_repo,
+            mob_repo,
+            preset_repo,
+            commit_log_repo,
+            history_repo,
+            out,
+        )?,
+        Some(Commands::Schedule(schedule)) => {
+            schedule.handle(team_member_repo, commit_log_repo, out)?
+        }
+        Some(Commands::Hook(hook)) => hook.handle(
+            team_member_repo,
+            mob_repo,
+            preset_repo,
+            history_repo,
+            command_runner,
+            out,
+        )?,
     }
     Ok(())
 }
@@ -76,14 +242,28 @@ fn run_inner(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::repositories::{MockMobSessionRepo, MockTeamMemberRepo};
+    use crate::commands::output_format::OutputFormat;
+    use crate::commands::team_member::OnConflict;
+    use crate::helpers::MockCommandRunner;
+    use crate::repositories::{
+        self, MobSessionScope, MockCommitLogRepo, MockHistoryRepo, MockMobSessionRepo,
+        MockPresetRepo, MockTeamGroupRepo, MockTeamMemberRepo,
+    };
     use mockall::predicate;
 
     #[test]
     fn test_clear_mob_session() -> Result<()> {
         let mock_team_member_repo = MockTeamMemberRepo::new();
         let mut mock_mob_repo = MockMobSessionRepo::new();
-        mock_mob_repo.expect_clear().once().returning(|| Ok(()));
+        mock_mob_repo
+            .expect_list_coauthors()
+            .returning(|_| Ok(vec![]));
+        mock_mob_repo.expect_get_driver().returning(|_| Ok(None));
+        mock_mob_repo
+            .expect_clear()
+            .with(predicate::eq(MobSessionScope::Global))
+            .once()
+            .returning(|_| Ok(()));
 
         let cli = Cli {
             command: None,
@@ -93,11 +273,83 @@ mod tests {
                 list: false,
                 trailers: false,
                 add: None,
+                local: false,
+                expire_after: None,
+                allow_unusual: false,
+                verbose: false,
+                format: OutputFormat::Text,
+                driver: None,
             },
         };
 
         let mut out = Vec::new();
-        run_inner(&cli, &mock_team_member_repo, &mock_mob_repo, &mut out)?;
+        run_inner(
+            &cli,
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &mock_mob_repo,
+            &MockCommitLogRepo::new(),
+            &MockHistoryRepo::new(),
+            &MockPresetRepo::new(),
+            &MockCommandRunner::new(),
+            &mut out,
+        )?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rewrite_with_exclusions() -> Result<()> {
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo
+            .expect_list_aliases()
+            .once()
+            .returning(|| Ok(vec![("la".to_owned(), "lm".to_owned())]));
+        mock_team_member_repo
+            .expect_get()
+            .returning(|key| match key {
+                "ct" | "lm" => Ok(Some(repositories::TeamMember::new(
+                    key,
+                    "Leo Messi",
+                    "leo.messi@example.com",
+                )?)),
+                _ => Ok(None),
+            });
+        let args = [
+            "git-mob",
+            "--with",
+            "@frontend",
+            "-lm",
+            "-@backend",
+            "-ct",
+            "-la",
+            "-l",
+            "--local",
+            "-c",
+            "-em",
+        ]
+        .map(OsString::from);
+
+        let result = rewrite_with_exclusions(args, &mock_team_member_repo)?;
+
+        // -ct and -la are made up of short flags but are a key and an alias of team members
+        assert_eq!(
+            result,
+            [
+                "git-mob",
+                "--with",
+                "@frontend",
+                "--with=-lm",
+                "--with=-@backend",
+                "--with=-ct",
+                "--with=-la",
+                "-l",
+                "--local",
+                "-c",
+                "-em",
+            ]
+            .map(OsString::from)
+        );
 
         Ok(())
     }
@@ -106,12 +358,30 @@ mod tests {
     fn test_delete_team_member() -> Result<()> {
         let key = "lm";
         let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo
+            .expect_list_aliases()
+            .returning(|| Ok(vec![]));
         let mock_mob_repo = MockMobSessionRepo::new();
+        let mut is_removed = false;
         mock_team_member_repo
             .expect_get()
             .with(predicate::eq(key))
+            .times(2)
+            .returning(move |key| {
+                if is_removed {
+                    return Ok(None);
+                }
+                is_removed = true;
+                Ok(Some(repositories::TeamMember::parse(
+                    key,
+                    "Leo Messi <leo.messi@example.com>",
+                )?))
+            });
+        let mut mock_team_group_repo = MockTeamGroupRepo::new();
+        mock_team_group_repo
+            .expect_list()
             .once()
-            .returning(|_| Ok(Some("Leo Messi <leo.messi@example.com>".to_owned())));
+            .returning(|| Ok(vec![]));
         mock_team_member_repo
             .expect_remove()
             .with(predicate::eq(key))
@@ -119,22 +389,58 @@ mod tests {
             .returning(|_| Ok(()));
 
         let cli = Cli {
-            command: Some(Commands::TeamMember(TeamMember {
+            command: Some(Commands::TeamMember(Box::new(TeamMember {
                 delete: Some(key.to_owned()),
+                hide: None,
+                repo: false,
+                system: false,
                 add: None,
                 list: false,
-            })),
+                import_from_log: false,
+                since: None,
+                revision_range: None,
+                export: false,
+                import: None,
+                from: None,
+                format: OutputFormat::Text,
+                roster_format: None,
+                on_conflict: OnConflict::Skip,
+                dry_run: false,
+                allow_unusual: false,
+                force: false,
+                alias: None,
+                edit: None,
+                new_key: None,
+                name: None,
+                email: None,
+            }))),
             mob: Mob {
                 with: None,
                 clear: false,
                 list: false,
                 trailers: false,
                 add: None,
+                local: false,
+                expire_after: None,
+                allow_unusual: false,
+                verbose: false,
+                format: OutputFormat::Text,
+                driver: None,
             },
         };
 
         let mut out = Vec::new();
-        run_inner(&cli, &mock_team_member_repo, &mock_mob_repo, &mut out)?;
+        run_inner(
+            &cli,
+            &mock_team_member_repo,
+            &mock_team_group_repo,
+            &mock_mob_repo,
+            &MockCommitLogRepo::new(),
+            &MockHistoryRepo::new(),
+            &MockPresetRepo::new(),
+            &MockCommandRunner::new(),
+            &mut out,
+        )?;
 
         Ok(())
     }
@@ -0,0 +1,326 @@
+use crate::Result;
+use crate::commands::mob::CoauthorResolver;
+use crate::commands::output_format::{OutputFormat, Record};
+use crate::helpers::parse_duration;
+use crate::repositories::{HistoryEntry, HistoryEvent, HistoryRepo, TeamMemberRepo};
+use chrono::{DateTime, Local, NaiveDate, NaiveTime, TimeDelta};
+use clap::Parser;
+use std::io::Write;
+
+#[derive(Parser)]
+pub(crate) struct History {
+    /// Lists entries since the given date (YYYY-MM-DD) or duration ago e.g. 7d
+    ///
+    /// Usage example: git mob history --since 7d
+    #[arg(long = "since", value_name = "DATE", value_parser = parse_since)]
+    pub(crate) since: Option<DateTime<Local>>,
+    /// Lists entries of mob/pair programming sessions with the team member
+    ///
+    /// Usage example: git mob history --member lm
+    #[arg(long = "member", value_name = "TEAM_MEMBER_KEY")]
+    pub(crate) member: Option<String>,
+    /// Format of the output, set by the global --format
+    #[arg(from_global)]
+    pub(crate) format: OutputFormat,
+}
+
+fn parse_since(value: &str) -> std::result::Result<DateTime<Local>, String> {
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return date
+            .and_time(NaiveTime::MIN)
+            .and_local_timezone(Local)
+            .earliest()
+            .ok_or_else(|| format!("Invalid local date: {value}"));
+    }
+
+    match parse_duration(value) {
+        Some(duration) => Local::now()
+            .checked_sub_signed(duration)
+            .ok_or_else(|| format!("Invalid duration: {value}. It goes back too far")),
+        None => Err(format!(
+            "Invalid date or duration: {value}. Use a date like 2024-05-01 or a duration like 30m, 8h or 7d"
+        )),
+    }
+}
+
+impl History {
+    pub(crate) fn handle(
+        &self,
+        team_member_repo: &impl TeamMemberRepo,
+        history_repo: &impl HistoryRepo,
+        out: &mut impl Write,
+    ) -> Result<()> {
+        let member = match self.member.as_deref() {
+            Some(token) => Some(
+                CoauthorResolver::new(team_member_repo, false)?
+                    .resolve_team_member(token)?
+                    .to_string(),
+            ),
+            None => None,
+        };
+
+        let entries: Vec<HistoryEntry> = history_repo
+            .list()?
+            .into_iter()
+            .filter(|x| self.since.is_none_or(|since| x.timestamp >= since))
+            .filter(|x| {
+                member
+                    .as_ref()
+                    .is_none_or(|member| x.members.contains(member))
+            })
+            .collect();
+
+        match self.format {
+            OutputFormat::Text => {
+                for entry in entries.iter() {
+                    writeln!(out, "{}", Self::format_entry(entry))?;
+                }
+            }
+            format => format.write_records(out, "history", &entries)?,
+        }
+
+        Ok(())
+    }
+
+    fn format_entry(entry: &HistoryEntry) -> String {
+        let event = match &entry.event {
+            HistoryEvent::Start => "start".to_owned(),
+            HistoryEvent::Add => "add".to_owned(),
+            HistoryEvent::End { ended, started_at } => match started_at {
+                Some(started_at) => format!(
+                    "{ended} after {}",
+                    Self::format_duration(entry.timestamp - *started_at)
+                ),
+                None => ended.to_string(),
+            },
+        };
+        let repo = entry
+            .repo
+            .as_ref()
+            .map(|x| format!(" {}", x.display()))
+            .unwrap_or_default();
+
+        format!(
+            "{} {event} ({}){repo}: {}",
+            entry.timestamp.format("%Y-%m-%d %H:%M"),
+            entry.scope,
+            entry.members.join(", ")
+        )
+    }
+
+    fn format_duration(duration: TimeDelta) -> String {
+        let minutes = duration.num_minutes().max(0);
+        match (minutes / 60, minutes % 60) {
+            (0, minutes) => format!("{minutes}m"),
+            (hours, 0) => format!("{hours}h"),
+            (hours, minutes) => format!("{hours}h {minutes}m"),
+        }
+    }
+}
+
+impl Record for HistoryEntry {
+    const COLUMNS: &'static [&'static str] = &[
+        "timestamp",
+        "repo",
+        "scope",
+        "event",
+        "ended",
+        "started_at",
+        "members",
+    ];
+
+    fn cells(&self) -> Vec<String> {
+        let (event, ended, started_at) = match &self.event {
+            HistoryEvent::Start => ("start", None, None),
+            HistoryEvent::Add => ("add", None, None),
+            HistoryEvent::End { ended, started_at } => ("end", Some(ended), started_at.as_ref()),
+        };
+        vec![
+            self.timestamp.to_rfc3339(),
+            self.repo
+                .as_ref()
+                .map(|x| x.display().to_string())
+                .unwrap_or_default(),
+            self.scope.to_string(),
+            event.to_owned(),
+            ended.map(|x| x.to_string()).unwrap_or_default(),
+            started_at.map(|x| x.to_rfc3339()).unwrap_or_default(),
+            self.members.join(", "),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::{
+        MobSessionScope, MockHistoryRepo, MockTeamMemberRepo, SessionEnd, TeamMember,
+    };
+    use chrono::TimeZone;
+    use std::path::PathBuf;
+
+    fn create_entries() -> Vec<HistoryEntry> {
+        let leo = "Leo Messi <leo.messi@example.com>".to_owned();
+        let emi = "Emi Martinez <emi.martinez@example.com>".to_owned();
+        let sergio = "Sergio Aguero <sergio.aguero@example.com>".to_owned();
+
+        vec![
+            HistoryEntry {
+                timestamp: Local.with_ymd_and_hms(2026, 10, 16, 9, 0, 0).unwrap(),
+                repo: Some(PathBuf::from("/path/to/repo")),
+                scope: MobSessionScope::Local,
+                event: HistoryEvent::Start,
+                members: vec![leo.clone(), emi.clone()],
+            },
+            HistoryEntry {
+                timestamp: Local.with_ymd_and_hms(2026, 10, 16, 11, 30, 0).unwrap(),
+                repo: Some(PathBuf::from("/path/to/repo")),
+                scope: MobSessionScope::Local,
+                event: HistoryEvent::End {
+                    ended: SessionEnd::Cleared,
+                    started_at: Some(Local.with_ymd_and_hms(2026, 10, 16, 9, 0, 0).unwrap()),
+                },
+                members: vec![leo, emi],
+            },
+            HistoryEntry {
+                timestamp: Local.with_ymd_and_hms(2026, 10, 17, 14, 0, 0).unwrap(),
+                repo: None,
+                scope: MobSessionScope::Global,
+                event: HistoryEvent::Add,
+                members: vec![sergio],
+            },
+        ]
+    }
+
+    #[test]
+    fn test_history() -> Result<()> {
+        let mock_team_member_repo = MockTeamMemberRepo::new();
+        let mut mock_history_repo = MockHistoryRepo::new();
+        mock_history_repo
+            .expect_list()
+            .once()
+            .returning(|| Ok(create_entries()));
+
+        let history_cmd = History {
+            since: None,
+            member: None,
+            format: OutputFormat::Text,
+        };
+
+        let mut out = Vec::new();
+        history_cmd.handle(&mock_team_member_repo, &mock_history_repo, &mut out)?;
+
+        assert_eq!(
+            String::from_utf8(out)?,
+            "2026-10-16 09:00 start (local) /path/to/repo: Leo Messi <leo.messi@example.com>, Emi Martinez <emi.martinez@example.com>\n\
+             2026-10-16 11:30 cleared after 2h 30m (local) /path/to/repo: Leo Messi <leo.messi@example.com>, Emi Martinez <emi.martinez@example.com>\n\
+             2026-10-17 14:00 add (global): Sergio Aguero <sergio.aguero@example.com>\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_history_given_since_and_member() -> Result<()> {
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo
+            .expect_list_aliases()
+            .returning(|| Ok(vec![]));
+        mock_team_member_repo
+            .expect_get()
+            .withf(|key| key == "lm")
+            .once()
+            .returning(|_| {
+                Ok(Some(TeamMember::new(
+                    "lm",
+                    "Leo Messi",
+                    "leo.messi@example.com",
+                )?))
+            });
+        let mut mock_history_repo = MockHistoryRepo::new();
+        mock_history_repo
+            .expect_list()
+            .once()
+            .returning(|| Ok(create_entries()));
+
+        let history_cmd = History {
+            since: Some(Local.with_ymd_and_hms(2026, 10, 16, 10, 0, 0).unwrap()),
+            member: Some("lm".to_owned()),
+            format: OutputFormat::Text,
+        };
+
+        let mut out = Vec::new();
+        history_cmd.handle(&mock_team_member_repo, &mock_history_repo, &mut out)?;
+
+        assert_eq!(
+            String::from_utf8(out)?,
+            "2026-10-16 11:30 cleared after 2h 30m (local) /path/to/repo: Leo Messi <leo.messi@example.com>, Emi Martinez <emi.martinez@example.com>\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_history_given_json_format() -> Result<()> {
+        let mock_team_member_repo = MockTeamMemberRepo::new();
+        let mut mock_history_repo = MockHistoryRepo::new();
+        mock_history_repo
+            .expect_list()
+            .once()
+            .returning(|| Ok(create_entries()));
+
+        let history_cmd = History {
+            since: None,
+            member: None,
+            format: OutputFormat::Json,
+        };
+
+        let mut out = Vec::new();
+        history_cmd.handle(&mock_team_member_repo, &mock_history_repo, &mut out)?;
+
+        let document: serde_json::Value = serde_json::from_slice(&out)?;
+        assert_eq!(document["version"], 1);
+        let entries: Vec<HistoryEntry> = serde_json::from_value(document["history"].clone())?;
+        assert_eq!(entries, create_entries());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_history_when_team_member_not_found() -> Result<()> {
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo
+            .expect_list_aliases()
+            .returning(|| Ok(vec![]));
+        mock_team_member_repo.expect_list().returning(|| Ok(vec![]));
+        mock_team_member_repo.expect_get().returning(|_| Ok(None));
+        let mock_history_repo = MockHistoryRepo::new();
+
+        let history_cmd = History {
+            since: None,
+            member: Some("lm".to_owned()),
+            format: OutputFormat::Text,
+        };
+
+        let mut out = Vec::new();
+        let result = history_cmd.handle(&mock_team_member_repo, &mock_history_repo, &mut out);
+
+        assert!(result.is_err_and(|x| x.to_string() == "No team member found with key: lm"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_since() {
+        assert_eq!(
+            parse_since("2026-10-16"),
+            Ok(Local.with_ymd_and_hms(2026, 10, 16, 0, 0, 0).unwrap())
+        );
+        assert!(parse_since("7d").is_ok_and(|x| x < Local::now() - TimeDelta::days(6)));
+        assert!(parse_since("last week").is_err());
+        assert!(
+            parse_since("999999999d")
+                .is_err_and(|x| x == "Invalid duration: 999999999d. It goes back too far")
+        );
+    }
+}
@@ -0,0 +1,1253 @@
+use crate::Result;
+use crate::commands::mob::{
+    exclude_author, list_effective_coauthors, record_history, switch_driver,
+};
+use crate::helpers::CommandRunner;
+use crate::repositories::{
+    ExpiredSession, HistoryEvent, HistoryRepo, MobSessionRepo, MobSessionScope, PresetRepo,
+    SessionEnd, TeamMemberRepo,
+};
+use clap::{Parser, Subcommand};
+use std::{fs, io::Write, path::PathBuf};
+
+#[derive(Parser)]
+pub(crate) struct Hook {
+    #[command(subcommand)]
+    pub(crate) command: HookCommands,
+}
+
+#[derive(Subcommand)]
+pub(crate) enum HookCommands {
+    /// Appends Co-authored-by trailers of the mob/pair programming session to the commit message
+    ///
+    /// Arguments are the same as the ones git passes to the prepare-commit-msg githook
+    PrepareCommitMsg(PrepareCommitMsg),
+    /// Swaps the driver and co-author of the ping-pong mob/pair programming session
+    ///
+    /// Invoked by the post-commit githook which is created by setup
+    PostCommit(PostCommit),
+}
+
+#[derive(Parser)]
+pub(crate) struct PrepareCommitMsg {
+    /// Path to the file which contains the commit message
+    #[arg(value_name = "COMMIT_MSG_FILE")]
+    pub(crate) file: PathBuf,
+    /// Source of the commit message e.g. message, template, merge, squash or commit
+    #[arg(value_name = "COMMIT_SOURCE")]
+    pub(crate) source: Option<String>,
+    /// Commit object name (only given when source is commit)
+    #[arg(value_name = "SHA1")]
+    pub(crate) sha: Option<String>,
+}
+
+#[derive(Parser)]
+pub(crate) struct PostCommit {}
+
+impl Hook {
+    pub(crate) fn handle(
+        &self,
+        team_member_repo: &impl TeamMemberRepo,
+        mob_repo: &impl MobSessionRepo,
+        preset_repo: &impl PresetRepo,
+        history_repo: &impl HistoryRepo,
+        command_runner: &impl CommandRunner,
+        out: &mut impl Write,
+    ) -> Result<()> {
+        // Do nothing during rebase
+        if !Self::is_on_branch(command_runner)? {
+            return Ok(());
+        }
+
+        match &self.command {
+            HookCommands::PrepareCommitMsg(prepare_commit_msg) => {
+                prepare_commit_msg.handle(mob_repo, preset_repo, history_repo, command_runner, out)
+            }
+            HookCommands::PostCommit(post_commit) => {
+                post_commit.handle(team_member_repo, mob_repo, out)
+            }
+        }
+    }
+
+    fn is_on_branch(command_runner: &impl CommandRunner) -> Result<bool> {
+        let output = command_runner.execute("git", &["branch", "--show-current"])?;
+
+        Ok(output.status_code == Some(0) && !String::from_utf8(output.stdout)?.trim().is_empty())
+    }
+}
+
+impl PrepareCommitMsg {
+    const TRAILER_KEY: &'static str = "Co-authored-by";
+
+    pub(crate) fn handle(
+        &self,
+        mob_repo: &impl MobSessionRepo,
+        preset_repo: &impl PresetRepo,
+        history_repo: &impl HistoryRepo,
+        command_runner: &impl CommandRunner,
+        out: &mut impl Write,
+    ) -> Result<()> {
+        // Do nothing during amend (without message) or reuse of a commit message
+        if self.source.as_deref() == Some("commit") {
+            return Ok(());
+        }
+
+        // An expired session is removed so that its driver stops being the commit author. This commit
+        // already has its author, so the user is told how to reset it.
+        for scope in [MobSessionScope::Local, MobSessionScope::Global] {
+            let Some(expired_session) = mob_repo.remove_expired(scope)? else {
+                continue;
+            };
+            record_expired(history_repo, scope, &expired_session);
+
+            if let ExpiredSession {
+                driver: Some(driver),
+                expired_at,
+                ..
+            } = expired_session
+                && exclude_author(mob_repo, vec![driver.to_owned()])?.is_empty()
+            {
+                writeln!(
+                    out,
+                    "Mob session ({scope}) expired at {}. Its driver {driver} is still the author of this commit, \
+                     which git commit --amend --reset-author changes to you\n",
+                    expired_at.format("%Y-%m-%d %H:%M")
+                )?;
+            }
+        }
+
+        let (_, coauthors, _) = list_effective_coauthors(mob_repo, preset_repo)?;
+        let coauthors = exclude_author(mob_repo, coauthors)?;
+        if coauthors.is_empty() {
+            return Ok(());
+        }
+
+        let trailers: Vec<(String, String)> = coauthors
+            .into_iter()
+            .map(|x| (Self::TRAILER_KEY.to_owned(), x))
+            .collect();
+
+        let message = fs::read_to_string(&self.file)?;
+        let comment = Self::get_comment_string(command_runner, &message)?;
+        fs::write(&self.file, add_trailers(&message, &trailers, &comment))?;
+
+        for (key, value) in trailers.iter() {
+            writeln!(out, "{key}: {value}")?;
+        }
+        writeln!(out)?;
+
+        Ok(())
+    }
+
+    /// Gets the string which starts comment lines of the commit message from core.commentString
+    /// or core.commentChar git config (whichever is set last), which is `#` by default
+    fn get_comment_string(command_runner: &impl CommandRunner, message: &str) -> Result<String> {
+        let output = command_runner.execute(
+            "git",
+            &["config", "--get-regexp", "^core\\.comment(char|string)$"],
+        )?;
+
+        let stdout = match output.status_code {
+            Some(0) => String::from_utf8(output.stdout)?,
+            Some(1) => return Ok(DEFAULT_COMMENT_STRING.to_owned()),
+            Some(code) => {
+                return Err(format!("Git config command exited with status code: {code}").into());
+            }
+            None => return Err("Git config command terminated by signal".into()),
+        };
+        let value = stdout
+            .lines()
+            .last()
+            .and_then(|x| x.split_once(' '))
+            .map_or("", |(_, value)| value);
+
+        let comment = match value {
+            // git picks a character which no line of the message starts with, and then starts its
+            // comments at the end of the message with it
+            "auto" => message
+                .lines()
+                .rfind(|x| !x.trim().is_empty())
+                .and_then(|x| x.chars().next())
+                .filter(|x| AUTO_COMMENT_CHARS.contains(*x))
+                .map_or(DEFAULT_COMMENT_STRING.to_owned(), String::from),
+            "" => DEFAULT_COMMENT_STRING.to_owned(),
+            value => value.to_owned(),
+        };
+
+        Ok(comment)
+    }
+}
+
+/// Records the end of the expired session in the history unless the session had no members
+fn record_expired(
+    history_repo: &impl HistoryRepo,
+    scope: MobSessionScope,
+    expired_session: &ExpiredSession,
+) {
+    let mut members = expired_session.coauthors.clone();
+    if let Some(driver) = &expired_session.driver {
+        members.insert(0, driver.to_owned());
+    }
+    if members.is_empty() {
+        return;
+    }
+
+    let event = HistoryEvent::End {
+        ended: SessionEnd::Expired,
+        started_at: expired_session.started_at,
+    };
+    record_history(history_repo, scope, event, &members)
+}
+
+impl PostCommit {
+    pub(crate) fn handle(
+        &self,
+        team_member_repo: &impl TeamMemberRepo,
+        mob_repo: &impl MobSessionRepo,
+        out: &mut impl Write,
+    ) -> Result<()> {
+        let (scope, _) = mob_repo.list_active_coauthors()?;
+        if !mob_repo.is_pingpong(scope)? {
+            return Ok(());
+        }
+
+        let mut rotation = Vec::new();
+        for key in mob_repo.list_rotation(scope)? {
+            match team_member_repo.get(&key)? {
+                Some(team_member) => rotation.push(team_member),
+                None => return Err(format!("No team member found with key: {key}").into()),
+            }
+        }
+        if rotation.is_empty() {
+            return Ok(());
+        }
+
+        let driver = mob_repo.get_driver(scope)?;
+        let next = driver
+            .and_then(|driver| rotation.iter().position(|x| x.to_string() == driver))
+            .map_or(0, |x| (x + 1) % rotation.len());
+
+        switch_driver(mob_repo, scope, &rotation[next])?;
+        writeln!(out, "Ping-pong! {} drives next", rotation[next])?;
+
+        Ok(())
+    }
+}
+
+const DEFAULT_COMMENT_STRING: &str = "#";
+/// Characters which git picks the comment character from when core.commentChar is auto
+const AUTO_COMMENT_CHARS: &str = "#;@!$%^&|:";
+const SCISSORS: &str = "------------------------ >8 ------------------------";
+const GIT_GENERATED_PREFIXES: [&str; 2] = ["Signed-off-by: ", "(cherry picked from commit "];
+
+/// Appends trailers to the trailer block of the commit message, skipping the ones which
+/// already exist with the same key and value (like `git interpret-trailers --if-exists addIfDifferent`)
+///
+/// Lines starting with the comment string are comments, and the rest of the message keeps its
+/// line endings as the trailers are only inserted into it
+fn add_trailers(message: &str, trailers: &[(String, String)], comment: &str) -> String {
+    let newline = if message.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let lines: Vec<&str> = message.split_inclusive('\n').collect();
+
+    // Trailers are added before the comments and the scissors line at the end of the message
+    let scissors_line = format!("{comment} {SCISSORS}");
+    let scissors_index = lines
+        .iter()
+        .position(|x| x.trim_end_matches(['\r', '\n']) == scissors_line)
+        .unwrap_or(lines.len());
+    let body_len = lines[..scissors_index]
+        .iter()
+        .rposition(|x| !x.trim().is_empty() && !x.starts_with(comment))
+        .map_or(0, |x| x + 1);
+    let (body, tail) = lines.split_at(body_len);
+
+    let existing_trailers: Vec<(&str, &str)> = find_trailer_block(body)
+        .map(|block| block.iter().filter_map(|x| parse_trailer(x)).collect())
+        .unwrap_or_default();
+
+    let mut new_trailers: Vec<String> = Vec::new();
+    for (key, value) in trailers.iter() {
+        let exists = existing_trailers
+            .iter()
+            .any(|(k, v)| k.eq_ignore_ascii_case(key) && v == value);
+        let trailer = format!("{key}: {value}");
+        if !exists && !new_trailers.contains(&trailer) {
+            new_trailers.push(trailer);
+        }
+    }
+
+    if new_trailers.is_empty() {
+        return message.to_owned();
+    }
+
+    let tail: Vec<&str> = if body.is_empty() {
+        tail.iter()
+            .skip_while(|x| x.trim().is_empty())
+            .copied()
+            .collect()
+    } else {
+        tail.to_vec()
+    };
+
+    let mut result = body.concat();
+    let has_final_newline = !tail.is_empty() || result.is_empty() || result.ends_with('\n');
+    if !result.is_empty() && !result.ends_with('\n') {
+        result.push_str(newline);
+    }
+    if body.is_empty() || existing_trailers.is_empty() {
+        result.push_str(newline);
+    }
+    result.push_str(&new_trailers.join(newline));
+    if has_final_newline {
+        result.push_str(newline);
+    }
+    result.push_str(&tail.concat());
+
+    result
+}
+
+/// Finds the trailer block i.e. the last paragraph of the message body (other than the title)
+/// which consists of trailers
+fn find_trailer_block<'a>(body: &'a [&'a str]) -> Option<&'a [&'a str]> {
+    let start = body.iter().rposition(|x| x.trim().is_empty())? + 1;
+    let block = &body[start..];
+
+    let lines: Vec<&str> = block
+        .iter()
+        .filter(|x| !x.starts_with([' ', '\t']))
+        .copied()
+        .collect();
+    let trailers_count = lines.iter().filter(|x| parse_trailer(x).is_some()).count();
+    let has_git_generated_trailer = lines
+        .iter()
+        .any(|x| GIT_GENERATED_PREFIXES.iter().any(|p| x.starts_with(p)));
+
+    let is_trailer_block = trailers_count > 0
+        && (trailers_count == lines.len()
+            || (has_git_generated_trailer && trailers_count * 4 >= lines.len()));
+
+    is_trailer_block.then_some(block)
+}
+
+fn parse_trailer(line: &str) -> Option<(&str, &str)> {
+    let (key, value) = line.split_once(':')?;
+    let key = key.trim_end();
+    let is_valid_key =
+        !key.is_empty() && key.chars().all(|x| x.is_ascii_alphanumeric() || x == '-');
+
+    is_valid_key.then(|| (key, value.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{CmdOutput, MockCommandRunner};
+    use crate::repositories::{
+        MobSessionScope, MockHistoryRepo, MockMobSessionRepo, MockPresetRepo, MockTeamMemberRepo,
+        Preset, TeamMember,
+    };
+    use chrono::{Local, TimeZone};
+    use mockall::predicate;
+    use tempfile::NamedTempFile;
+
+    fn create_mock_command_runner(current_branch: &'static str) -> MockCommandRunner {
+        let mut mock_command_runner = MockCommandRunner::new();
+        mock_command_runner
+            .expect_execute()
+            .withf(|program, args| program == "git" && args == ["branch", "--show-current"])
+            .once()
+            .returning(move |_, _| {
+                Ok(CmdOutput {
+                    stdout: current_branch.into(),
+                    stderr: vec![],
+                    status_code: Some(0),
+                })
+            });
+        mock_command_runner
+    }
+
+    fn create_mock_comment_command_runner(
+        comment_config: Option<&'static str>,
+    ) -> MockCommandRunner {
+        let mut mock_command_runner = MockCommandRunner::new();
+        mock_command_runner
+            .expect_execute()
+            .withf(|program, args| {
+                program == "git"
+                    && args == ["config", "--get-regexp", "^core\\.comment(char|string)$"]
+            })
+            .once()
+            .returning(move |_, _| {
+                Ok(CmdOutput {
+                    stdout: comment_config.unwrap_or_default().into(),
+                    stderr: vec![],
+                    status_code: Some(if comment_config.is_some() { 0 } else { 1 }),
+                })
+            });
+        mock_command_runner
+    }
+
+    fn coauthor_trailers(coauthors: &[&str]) -> Vec<(String, String)> {
+        coauthors
+            .iter()
+            .map(|x| ("Co-authored-by".to_owned(), x.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_add_trailers() {
+        let trailers = coauthor_trailers(&[
+            "Leo Messi <leo.messi@example.com>",
+            "Emi Martinez <emi.martinez@example.com>",
+        ]);
+
+        let result = add_trailers("test: hello world!\n", &trailers, "#");
+
+        assert_eq!(
+            result,
+            "test: hello world!\n\n\
+             Co-authored-by: Leo Messi <leo.messi@example.com>\n\
+             Co-authored-by: Emi Martinez <emi.martinez@example.com>\n"
+        );
+    }
+
+    #[test]
+    fn test_add_trailers_given_message_with_body() {
+        let trailers = coauthor_trailers(&["Leo Messi <leo.messi@example.com>"]);
+
+        let result = add_trailers("test: hello world!\n\nLorem ipsum\n", &trailers, "#");
+
+        assert_eq!(
+            result,
+            "test: hello world!\n\nLorem ipsum\n\n\
+             Co-authored-by: Leo Messi <leo.messi@example.com>\n"
+        );
+    }
+
+    #[test]
+    fn test_add_trailers_given_existing_trailer_block() {
+        let trailers = coauthor_trailers(&[
+            "Leo Messi <leo.messi@example.com>",
+            "Emi Martinez <emi.martinez@example.com>",
+        ]);
+
+        let result = add_trailers(
+            "test: hello world!\n\n\
+             Signed-off-by: Cata Diaz <cata.diaz@example.com>\n\
+             co-authored-by: Leo Messi <leo.messi@example.com>\n",
+            &trailers,
+            "#",
+        );
+
+        assert_eq!(
+            result,
+            "test: hello world!\n\n\
+             Signed-off-by: Cata Diaz <cata.diaz@example.com>\n\
+             co-authored-by: Leo Messi <leo.messi@example.com>\n\
+             Co-authored-by: Emi Martinez <emi.martinez@example.com>\n"
+        );
+    }
+
+    #[test]
+    fn test_add_trailers_given_all_trailers_exist() {
+        let trailers = coauthor_trailers(&["Leo Messi <leo.messi@example.com>"]);
+        let message = "test: hello world!\n\nCo-authored-by: Leo Messi <leo.messi@example.com>\n";
+
+        let result = add_trailers(message, &trailers, "#");
+
+        assert_eq!(result, message);
+    }
+
+    #[test]
+    fn test_add_trailers_given_title_resembling_trailer() {
+        let trailers = coauthor_trailers(&["Leo Messi <leo.messi@example.com>"]);
+
+        let result = add_trailers("fix: hello world!\n", &trailers, "#");
+
+        assert_eq!(
+            result,
+            "fix: hello world!\n\nCo-authored-by: Leo Messi <leo.messi@example.com>\n"
+        );
+    }
+
+    #[test]
+    fn test_add_trailers_given_last_paragraph_not_trailer_block() {
+        let trailers = coauthor_trailers(&["Leo Messi <leo.messi@example.com>"]);
+
+        let result = add_trailers(
+            "test: hello world!\n\nFixes #1\nSee-also: foo\n",
+            &trailers,
+            "#",
+        );
+
+        assert_eq!(
+            result,
+            "test: hello world!\n\nFixes #1\nSee-also: foo\n\n\
+             Co-authored-by: Leo Messi <leo.messi@example.com>\n"
+        );
+    }
+
+    #[test]
+    fn test_add_trailers_given_comments() {
+        let trailers = coauthor_trailers(&["Leo Messi <leo.messi@example.com>"]);
+
+        let result = add_trailers(
+            "test: hello world!\n\n# Please enter the commit message\n# Lorem ipsum\n",
+            &trailers,
+            "#",
+        );
+
+        assert_eq!(
+            result,
+            "test: hello world!\n\n\
+             Co-authored-by: Leo Messi <leo.messi@example.com>\n\n\
+             # Please enter the commit message\n# Lorem ipsum\n"
+        );
+    }
+
+    #[test]
+    fn test_add_trailers_given_empty_message_with_comments() {
+        let trailers = coauthor_trailers(&["Leo Messi <leo.messi@example.com>"]);
+
+        let result = add_trailers("\n# Please enter the commit message\n", &trailers, "#");
+
+        assert_eq!(
+            result,
+            "\nCo-authored-by: Leo Messi <leo.messi@example.com>\n\
+             # Please enter the commit message\n"
+        );
+    }
+
+    #[test]
+    fn test_add_trailers_given_scissors_line() {
+        let trailers = coauthor_trailers(&["Leo Messi <leo.messi@example.com>"]);
+
+        let result = add_trailers(
+            &format!("test: hello world!\n# {SCISSORS}\n# Co-authored-by: Lorem ipsum\n"),
+            &trailers,
+            "#",
+        );
+
+        assert_eq!(
+            result,
+            format!(
+                "test: hello world!\n\n\
+                 Co-authored-by: Leo Messi <leo.messi@example.com>\n\
+                 # {SCISSORS}\n# Co-authored-by: Lorem ipsum\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_add_trailers_given_comment_string() {
+        let trailers = coauthor_trailers(&["Leo Messi <leo.messi@example.com>"]);
+
+        let result = add_trailers(
+            "test: hello world!\n\n#1 is fixed\n\n// Please enter the commit message\n",
+            &trailers,
+            "//",
+        );
+
+        assert_eq!(
+            result,
+            "test: hello world!\n\n#1 is fixed\n\n\
+             Co-authored-by: Leo Messi <leo.messi@example.com>\n\n\
+             // Please enter the commit message\n"
+        );
+    }
+
+    #[test]
+    fn test_add_trailers_given_crlf_line_endings() {
+        let trailers = coauthor_trailers(&[
+            "Leo Messi <leo.messi@example.com>",
+            "Emi Martinez <emi.martinez@example.com>",
+        ]);
+
+        let result = add_trailers(
+            "test: hello world!\r\n\r\n# Please enter the commit message\r\n",
+            &trailers,
+            "#",
+        );
+
+        assert_eq!(
+            result,
+            "test: hello world!\r\n\r\n\
+             Co-authored-by: Leo Messi <leo.messi@example.com>\r\n\
+             Co-authored-by: Emi Martinez <emi.martinez@example.com>\r\n\r\n\
+             # Please enter the commit message\r\n"
+        );
+    }
+
+    #[test]
+    fn test_add_trailers_given_message_without_final_newline() {
+        let trailers = coauthor_trailers(&["Leo Messi <leo.messi@example.com>"]);
+
+        let result = add_trailers("test: hello world!", &trailers, "#");
+
+        assert_eq!(
+            result,
+            "test: hello world!\n\nCo-authored-by: Leo Messi <leo.messi@example.com>"
+        );
+    }
+
+    #[test]
+    fn test_get_comment_string_given_auto_comment_char() -> Result<()> {
+        let result = PrepareCommitMsg::get_comment_string(
+            &create_mock_comment_command_runner(Some("core.commentchar auto\n")),
+            "test: #1 is fixed\n\n; Please enter the commit message\n",
+        )?;
+
+        assert_eq!(result, ";");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_prepare_commit_msg() -> Result<()> {
+        let file = NamedTempFile::new()?;
+        fs::write(file.path(), "test: hello world!\n")?;
+
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo
+            .expect_remove_expired()
+            .times(2)
+            .returning(|_| Ok(None));
+        mock_mob_repo
+            .expect_list_active_coauthors()
+            .once()
+            .returning(|| {
+                Ok((
+                    MobSessionScope::Global,
+                    vec![
+                        "Leo Messi <leo.messi@example.com>".to_owned(),
+                        "Emi Martinez <emi.martinez@example.com>".to_owned(),
+                    ],
+                ))
+            });
+        mock_mob_repo
+            .expect_get_author_email()
+            .once()
+            .returning(|| Ok(Some("jane.doe@example.com".to_owned())));
+        let mock_preset_repo = MockPresetRepo::new();
+
+        let prepare_commit_msg = PrepareCommitMsg {
+            file: file.path().to_path_buf(),
+            source: Some("message".to_owned()),
+            sha: None,
+        };
+
+        let mut out = Vec::new();
+        prepare_commit_msg.handle(
+            &mock_mob_repo,
+            &mock_preset_repo,
+            &MockHistoryRepo::new(),
+            &create_mock_comment_command_runner(None),
+            &mut out,
+        )?;
+
+        assert_eq!(
+            fs::read_to_string(file.path())?,
+            "test: hello world!\n\n\
+             Co-authored-by: Leo Messi <leo.messi@example.com>\n\
+             Co-authored-by: Emi Martinez <emi.martinez@example.com>\n"
+        );
+        assert_eq!(
+            out,
+            b"Co-authored-by: Leo Messi <leo.messi@example.com>\n\
+              Co-authored-by: Emi Martinez <emi.martinez@example.com>\n\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_prepare_commit_msg_given_comment_char() -> Result<()> {
+        let file = NamedTempFile::new()?;
+        fs::write(
+            file.path(),
+            "test: hello world!\n\n; Please enter the commit message\n",
+        )?;
+
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo
+            .expect_remove_expired()
+            .times(2)
+            .returning(|_| Ok(None));
+        mock_mob_repo
+            .expect_list_active_coauthors()
+            .once()
+            .returning(|| {
+                Ok((
+                    MobSessionScope::Global,
+                    vec!["Leo Messi <leo.messi@example.com>".to_owned()],
+                ))
+            });
+        mock_mob_repo
+            .expect_get_author_email()
+            .once()
+            .returning(|| Ok(Some("jane.doe@example.com".to_owned())));
+
+        let prepare_commit_msg = PrepareCommitMsg {
+            file: file.path().to_path_buf(),
+            source: None,
+            sha: None,
+        };
+
+        // the last one set of core.commentChar and core.commentString is used
+        let mut out = Vec::new();
+        prepare_commit_msg.handle(
+            &mock_mob_repo,
+            &MockPresetRepo::new(),
+            &MockHistoryRepo::new(),
+            &create_mock_comment_command_runner(Some(
+                "core.commentstring //\ncore.commentchar ;\n",
+            )),
+            &mut out,
+        )?;
+
+        assert_eq!(
+            fs::read_to_string(file.path())?,
+            "test: hello world!\n\n\
+             Co-authored-by: Leo Messi <leo.messi@example.com>\n\n\
+             ; Please enter the commit message\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_prepare_commit_msg_given_author_in_mob_session() -> Result<()> {
+        let file = NamedTempFile::new()?;
+        fs::write(file.path(), "test: hello world!\n")?;
+
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo
+            .expect_remove_expired()
+            .times(2)
+            .returning(|_| Ok(None));
+        mock_mob_repo
+            .expect_list_active_coauthors()
+            .once()
+            .returning(|| {
+                Ok((
+                    MobSessionScope::Global,
+                    vec![
+                        "Leo Messi <Leo.Messi@example.com>".to_owned(),
+                        "Emi Martinez <emi.martinez@example.com>".to_owned(),
+                    ],
+                ))
+            });
+        mock_mob_repo
+            .expect_get_author_email()
+            .once()
+            .returning(|| Ok(Some("leo.messi@EXAMPLE.com".to_owned())));
+
+        let prepare_commit_msg = PrepareCommitMsg {
+            file: file.path().to_path_buf(),
+            source: Some("message".to_owned()),
+            sha: None,
+        };
+
+        let mut out = Vec::new();
+        prepare_commit_msg.handle(
+            &mock_mob_repo,
+            &MockPresetRepo::new(),
+            &MockHistoryRepo::new(),
+            &create_mock_comment_command_runner(None),
+            &mut out,
+        )?;
+
+        assert_eq!(
+            fs::read_to_string(file.path())?,
+            "test: hello world!\n\n\
+             Co-authored-by: Emi Martinez <emi.martinez@example.com>\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_prepare_commit_msg_when_mob_session_is_empty() -> Result<()> {
+        let file = NamedTempFile::new()?;
+        fs::write(file.path(), "test: hello world!\n")?;
+
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo
+            .expect_remove_expired()
+            .times(2)
+            .returning(|_| Ok(None));
+        mock_mob_repo
+            .expect_list_active_coauthors()
+            .once()
+            .returning(|| Ok((MobSessionScope::Global, vec![])));
+        let mut mock_preset_repo = MockPresetRepo::new();
+        mock_preset_repo
+            .expect_get_default()
+            .once()
+            .returning(|| Ok(None));
+
+        let prepare_commit_msg = PrepareCommitMsg {
+            file: file.path().to_path_buf(),
+            source: None,
+            sha: None,
+        };
+
+        let mut out = Vec::new();
+        prepare_commit_msg.handle(
+            &mock_mob_repo,
+            &mock_preset_repo,
+            &MockHistoryRepo::new(),
+            &MockCommandRunner::new(),
+            &mut out,
+        )?;
+
+        assert_eq!(fs::read_to_string(file.path())?, "test: hello world!\n");
+        assert!(out.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_prepare_commit_msg_when_mob_session_is_empty_given_default_preset() -> Result<()> {
+        let file = NamedTempFile::new()?;
+        fs::write(file.path(), "test: hello world!\n")?;
+
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo
+            .expect_remove_expired()
+            .times(2)
+            .returning(|_| Ok(None));
+        mock_mob_repo
+            .expect_list_active_coauthors()
+            .once()
+            .returning(|| Ok((MobSessionScope::Local, vec![])));
+        mock_mob_repo
+            .expect_get_author_email()
+            .once()
+            .returning(|| Ok(Some("jane.doe@example.com".to_owned())));
+        let mut mock_preset_repo = MockPresetRepo::new();
+        mock_preset_repo
+            .expect_get_default()
+            .once()
+            .returning(|| Ok(Some("payments-mob".to_owned())));
+        mock_preset_repo
+            .expect_get()
+            .with(predicate::eq("payments-mob"))
+            .once()
+            .returning(|name| {
+                Ok(Some(Preset::new(
+                    name,
+                    &["Diego Maradona <diego.maradona@example.com>".to_owned()],
+                )?))
+            });
+
+        let prepare_commit_msg = PrepareCommitMsg {
+            file: file.path().to_path_buf(),
+            source: Some("message".to_owned()),
+            sha: None,
+        };
+
+        let mut out = Vec::new();
+        prepare_commit_msg.handle(
+            &mock_mob_repo,
+            &mock_preset_repo,
+            &MockHistoryRepo::new(),
+            &create_mock_comment_command_runner(None),
+            &mut out,
+        )?;
+
+        assert_eq!(
+            fs::read_to_string(file.path())?,
+            "test: hello world!\n\n\
+             Co-authored-by: Diego Maradona <diego.maradona@example.com>\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_prepare_commit_msg_when_mob_session_is_empty_given_default_preset_with_driver()
+    -> Result<()> {
+        let file = NamedTempFile::new()?;
+        fs::write(file.path(), "test: hello world!\n")?;
+
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo
+            .expect_remove_expired()
+            .times(2)
+            .returning(|_| Ok(None));
+        mock_mob_repo
+            .expect_list_active_coauthors()
+            .once()
+            .returning(|| Ok((MobSessionScope::Local, vec![])));
+        // the commit author is one of the co-authors of the preset, so they get no trailer
+        mock_mob_repo
+            .expect_get_author_email()
+            .once()
+            .returning(|| Ok(Some("Emi.Martinez@example.com".to_owned())));
+        let mut mock_preset_repo = MockPresetRepo::new();
+        mock_preset_repo
+            .expect_get_default()
+            .once()
+            .returning(|| Ok(Some("payments-mob".to_owned())));
+        mock_preset_repo
+            .expect_get()
+            .with(predicate::eq("payments-mob"))
+            .once()
+            .returning(|name| {
+                let mut preset = Preset::new(
+                    name,
+                    &[
+                        "Diego Maradona <diego.maradona@example.com>".to_owned(),
+                        "Emi Martinez <emi.martinez@example.com>".to_owned(),
+                    ],
+                )?;
+                preset.driver = Some("Leo Messi <leo.messi@example.com>".to_owned());
+                Ok(Some(preset))
+            });
+
+        let prepare_commit_msg = PrepareCommitMsg {
+            file: file.path().to_path_buf(),
+            source: Some("message".to_owned()),
+            sha: None,
+        };
+
+        let mut out = Vec::new();
+        prepare_commit_msg.handle(
+            &mock_mob_repo,
+            &mock_preset_repo,
+            &MockHistoryRepo::new(),
+            &create_mock_comment_command_runner(None),
+            &mut out,
+        )?;
+
+        assert_eq!(
+            fs::read_to_string(file.path())?,
+            "test: hello world!\n\n\
+             Co-authored-by: Leo Messi <leo.messi@example.com>\n\
+             Co-authored-by: Diego Maradona <diego.maradona@example.com>\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_prepare_commit_msg_when_mob_session_with_driver_expired() -> Result<()> {
+        let file = NamedTempFile::new()?;
+        fs::write(file.path(), "test: hello world!\n")?;
+
+        let expired_at = Local.with_ymd_and_hms(2025, 6, 2, 0, 0, 0).unwrap();
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo
+            .expect_remove_expired()
+            .with(predicate::eq(MobSessionScope::Local))
+            .once()
+            .returning(|_| Ok(None));
+        mock_mob_repo
+            .expect_remove_expired()
+            .with(predicate::eq(MobSessionScope::Global))
+            .once()
+            .returning(move |_| {
+                Ok(Some(ExpiredSession {
+                    coauthors: vec!["Emi Martinez <emi.martinez@example.com>".to_owned()],
+                    driver: Some("Leo Messi <leo.messi@example.com>".to_owned()),
+                    started_at: None,
+                    expired_at,
+                }))
+            });
+        mock_mob_repo
+            .expect_get_author_email()
+            .once()
+            .returning(|| Ok(Some("leo.messi@example.com".to_owned())));
+        mock_mob_repo
+            .expect_list_active_coauthors()
+            .once()
+            .returning(|| Ok((MobSessionScope::Global, vec![])));
+        let mut mock_preset_repo = MockPresetRepo::new();
+        mock_preset_repo.expect_get_default().returning(|| Ok(None));
+        let mut mock_history_repo = MockHistoryRepo::new();
+        mock_history_repo
+            .expect_append()
+            .withf(|scope, event, members| {
+                *scope == MobSessionScope::Global
+                    && *event
+                        == HistoryEvent::End {
+                            ended: SessionEnd::Expired,
+                            started_at: None,
+                        }
+                    && members
+                        == [
+                            "Leo Messi <leo.messi@example.com>",
+                            "Emi Martinez <emi.martinez@example.com>",
+                        ]
+            })
+            .once()
+            .returning(|_, _, _| Ok(()));
+
+        let prepare_commit_msg = PrepareCommitMsg {
+            file: file.path().to_path_buf(),
+            source: Some("message".to_owned()),
+            sha: None,
+        };
+
+        let mut out = Vec::new();
+        prepare_commit_msg.handle(
+            &mock_mob_repo,
+            &mock_preset_repo,
+            &mock_history_repo,
+            &MockCommandRunner::new(),
+            &mut out,
+        )?;
+
+        assert_eq!(fs::read_to_string(file.path())?, "test: hello world!\n");
+        assert_eq!(
+            String::from_utf8(out)?,
+            "Mob session (global) expired at 2025-06-02 00:00. Its driver Leo Messi <leo.messi@example.com> \
+             is still the author of this commit, which git commit --amend --reset-author changes to you\n\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_prepare_commit_msg_when_history_append_fails() -> Result<()> {
+        let file = NamedTempFile::new()?;
+        fs::write(file.path(), "test: hello world!\n")?;
+
+        let expired_at = Local.with_ymd_and_hms(2025, 6, 2, 0, 0, 0).unwrap();
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo
+            .expect_remove_expired()
+            .with(predicate::eq(MobSessionScope::Local))
+            .once()
+            .returning(move |_| {
+                Ok(Some(ExpiredSession {
+                    coauthors: vec!["Emi Martinez <emi.martinez@example.com>".to_owned()],
+                    driver: None,
+                    started_at: None,
+                    expired_at,
+                }))
+            });
+        mock_mob_repo
+            .expect_remove_expired()
+            .with(predicate::eq(MobSessionScope::Global))
+            .once()
+            .returning(|_| Ok(None));
+        mock_mob_repo
+            .expect_list_active_coauthors()
+            .once()
+            .returning(|| {
+                Ok((
+                    MobSessionScope::Global,
+                    vec!["Angel Di Maria <angel.dimaria@example.com>".to_owned()],
+                ))
+            });
+        mock_mob_repo
+            .expect_get_author_email()
+            .once()
+            .returning(|| Ok(None));
+        let mut mock_history_repo = MockHistoryRepo::new();
+        mock_history_repo
+            .expect_append()
+            .once()
+            .returning(|_, _, _| Err("Permission denied".into()));
+
+        let prepare_commit_msg = PrepareCommitMsg {
+            file: file.path().to_path_buf(),
+            source: Some("message".to_owned()),
+            sha: None,
+        };
+
+        let mut out = Vec::new();
+        prepare_commit_msg.handle(
+            &mock_mob_repo,
+            &MockPresetRepo::new(),
+            &mock_history_repo,
+            &create_mock_comment_command_runner(None),
+            &mut out,
+        )?;
+
+        assert_eq!(
+            fs::read_to_string(file.path())?,
+            "test: hello world!\n\nCo-authored-by: Angel Di Maria <angel.dimaria@example.com>\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_prepare_commit_msg_given_commit_source() -> Result<()> {
+        let file = NamedTempFile::new()?;
+        fs::write(file.path(), "test: hello world!\n")?;
+
+        let mock_mob_repo = MockMobSessionRepo::new();
+        let mock_preset_repo = MockPresetRepo::new();
+
+        let prepare_commit_msg = PrepareCommitMsg {
+            file: file.path().to_path_buf(),
+            source: Some("commit".to_owned()),
+            sha: Some("HEAD".to_owned()),
+        };
+
+        let mut out = Vec::new();
+        prepare_commit_msg.handle(
+            &mock_mob_repo,
+            &mock_preset_repo,
+            &MockHistoryRepo::new(),
+            &MockCommandRunner::new(),
+            &mut out,
+        )?;
+
+        assert_eq!(fs::read_to_string(file.path())?, "test: hello world!\n");
+        assert!(out.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_post_commit_given_pingpong() -> Result<()> {
+        let team_members = [
+            TeamMember::new("lm", "Leo Messi", "leo.messi@example.com")?,
+            TeamMember::new("em", "Emi Martinez", "emi.martinez@example.com")?,
+        ];
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo
+            .expect_get()
+            .returning(move |key| Ok(team_members.iter().find(|x| x.key == key).cloned()));
+
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo.expect_list_active_coauthors().returning(|| {
+            Ok((
+                MobSessionScope::Local,
+                vec!["Emi Martinez <emi.martinez@example.com>".to_owned()],
+            ))
+        });
+        mock_mob_repo
+            .expect_list_coauthors()
+            .returning(|_| Ok(vec!["Emi Martinez <emi.martinez@example.com>".to_owned()]));
+        mock_mob_repo
+            .expect_is_pingpong()
+            .with(predicate::eq(MobSessionScope::Local))
+            .returning(|_| Ok(true));
+        mock_mob_repo
+            .expect_list_rotation()
+            .returning(|_| Ok(vec!["lm".to_owned(), "em".to_owned()]));
+        mock_mob_repo
+            .expect_get_driver()
+            .returning(|_| Ok(Some("Leo Messi <leo.messi@example.com>".to_owned())));
+        mock_mob_repo
+            .expect_set_driver()
+            .withf(|scope, driver| *scope == MobSessionScope::Local && driver.key == "em")
+            .once()
+            .returning(|_, _| Ok(()));
+        mock_mob_repo
+            .expect_remove_coauthor()
+            .with(
+                predicate::eq(MobSessionScope::Local),
+                predicate::eq("Emi Martinez <emi.martinez@example.com>"),
+            )
+            .once()
+            .returning(|_, _| Ok(()));
+        mock_mob_repo
+            .expect_add_coauthor()
+            .with(
+                predicate::eq(MobSessionScope::Local),
+                predicate::eq("Leo Messi <leo.messi@example.com>"),
+            )
+            .once()
+            .returning(|_, _| Ok(()));
+
+        let mut out = Vec::new();
+        PostCommit {}.handle(&mock_team_member_repo, &mock_mob_repo, &mut out)?;
+
+        assert_eq!(
+            out,
+            b"Ping-pong! Emi Martinez <emi.martinez@example.com> drives next\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_post_commit_given_no_pingpong() -> Result<()> {
+        let mock_team_member_repo = MockTeamMemberRepo::new();
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo
+            .expect_list_active_coauthors()
+            .returning(|| Ok((MobSessionScope::Global, vec![])));
+        mock_mob_repo.expect_is_pingpong().returning(|_| Ok(false));
+        mock_mob_repo.expect_set_driver().never();
+
+        let mut out = Vec::new();
+        PostCommit {}.handle(&mock_team_member_repo, &mock_mob_repo, &mut out)?;
+
+        assert!(out.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_hook_given_branch() -> Result<()> {
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo
+            .expect_list_active_coauthors()
+            .once()
+            .returning(|| Ok((MobSessionScope::Global, vec![])));
+        mock_mob_repo
+            .expect_is_pingpong()
+            .once()
+            .returning(|_| Ok(false));
+
+        let hook = Hook {
+            command: HookCommands::PostCommit(PostCommit {}),
+        };
+
+        let mut out = Vec::new();
+        hook.handle(
+            &MockTeamMemberRepo::new(),
+            &mock_mob_repo,
+            &MockPresetRepo::new(),
+            &MockHistoryRepo::new(),
+            &create_mock_command_runner("main\n"),
+            &mut out,
+        )?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_hook_when_not_on_branch() -> Result<()> {
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo.expect_list_active_coauthors().never();
+
+        let file = NamedTempFile::new()?;
+        fs::write(file.path(), "test: hello world!\n")?;
+        let hook = Hook {
+            command: HookCommands::PrepareCommitMsg(PrepareCommitMsg {
+                file: file.path().to_path_buf(),
+                source: None,
+                sha: None,
+            }),
+        };
+
+        // no branch is checked out during a rebase
+        let mut out = Vec::new();
+        hook.handle(
+            &MockTeamMemberRepo::new(),
+            &mock_mob_repo,
+            &MockPresetRepo::new(),
+            &MockHistoryRepo::new(),
+            &create_mock_command_runner(""),
+            &mut out,
+        )?;
+
+        assert_eq!(fs::read_to_string(file.path())?, "test: hello world!\n");
+        assert!(out.is_empty());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,393 @@
+use crate::Result;
+use crate::helpers::CommandRunner;
+use crate::repositories::TeamMember;
+use clap::ValueEnum;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+/// Other pair/mob programming tool which team members can be imported from
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub(crate) enum ImportSource {
+    /// `~/.git-authors` YAML file (or the path in GIT_DUET_AUTHORS_FILE environment variable)
+    #[value(name = "git-duet")]
+    Duet,
+    /// `git-together.authors.*` git config
+    #[value(name = "git-together")]
+    Together,
+    /// `pair.*` git config
+    #[value(name = "git-pair")]
+    Pair,
+}
+
+/// Result of converting the entries of another tool into team members
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct Conversion {
+    pub(crate) team_members: Vec<TeamMember>,
+    /// Keys of the entries which cannot be converted along with the reason
+    pub(crate) failures: Vec<(String, String)>,
+}
+
+impl Conversion {
+    fn convert(&mut self, key: &str, name: &str, email: Option<String>) {
+        let result = match email {
+            Some(email) => TeamMember::new(key, name, &email),
+            None => Err("no email address".into()),
+        };
+
+        match result {
+            Ok(team_member) => self.team_members.push(team_member),
+            Err(err) => self.failures.push((key.to_owned(), err.to_string())),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct GitDuetAuthors {
+    #[serde(default)]
+    pairs: HashMap<String, String>,
+    #[serde(default)]
+    email: GitDuetEmail,
+    #[serde(default)]
+    email_addresses: HashMap<String, String>,
+}
+
+#[derive(Deserialize, Default)]
+struct GitDuetEmail {
+    domain: Option<String>,
+}
+
+impl ImportSource {
+    /// Reads the entries of the tool from the given path or the default location of the tool
+    pub(crate) fn load(
+        &self,
+        command_runner: &impl CommandRunner,
+        path: Option<&Path>,
+    ) -> Result<Conversion> {
+        match self {
+            ImportSource::Duet => {
+                let path = match path {
+                    Some(path) => path.to_owned(),
+                    None => match env::var_os("GIT_DUET_AUTHORS_FILE") {
+                        Some(path) => PathBuf::from(path),
+                        None => env::home_dir()
+                            .ok_or("Failed to get home directory")?
+                            .join(".git-authors"),
+                    },
+                };
+                let content = fs::read_to_string(&path)
+                    .map_err(|err| format!("Failed to read {}: {err}", path.display()))?;
+                Self::parse_git_duet(&content)
+                    .map_err(|err| format!("Failed to parse {}: {err}", path.display()).into())
+            }
+            ImportSource::Together => Ok(Self::parse_git_together(&Self::read_git_config(
+                command_runner,
+                path,
+                "^git-together\\.",
+            )?)),
+            ImportSource::Pair => Ok(Self::parse_git_pair(&Self::read_git_config(
+                command_runner,
+                path,
+                "^pair\\.",
+            )?)),
+        }
+    }
+
+    /// Reads git config entries matching the regex from the given config file or all the scopes
+    fn read_git_config(
+        command_runner: &impl CommandRunner,
+        path: Option<&Path>,
+        regex: &str,
+    ) -> Result<String> {
+        let mut args = vec!["config"];
+        if let Some(path) = path {
+            let path = path
+                .to_str()
+                .ok_or_else(|| format!("Invalid path: {}", path.display()))?;
+            args.extend(["--file", path]);
+        }
+        args.extend(["--get-regexp", regex]);
+        let output = command_runner.execute("git", &args)?;
+
+        match output.status_code {
+            Some(0) => Ok(String::from_utf8(output.stdout)?),
+            Some(1) => Ok(String::new()),
+            _ => Err(format!(
+                "Failed to read git config: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )
+            .into()),
+        }
+    }
+
+    /// Splits "Name; username" into name and username
+    fn split_name(value: &str) -> (&str, Option<&str>) {
+        match value.split_once(';') {
+            Some((name, username)) => (name.trim(), Some(username.trim())),
+            None => (value.trim(), None),
+        }
+    }
+
+    fn parse_git_duet(content: &str) -> Result<Conversion> {
+        let authors: GitDuetAuthors = serde_norway::from_str(content)?;
+
+        let mut keys: Vec<&String> = authors.pairs.keys().collect();
+        keys.sort();
+
+        let mut conversion = Conversion::default();
+        for key in keys {
+            let (name, username) = Self::split_name(&authors.pairs[key]);
+            let email = authors.email_addresses.get(key).cloned().or_else(|| {
+                Some(format!(
+                    "{}@{}",
+                    username?,
+                    authors.email.domain.as_deref()?
+                ))
+            });
+            conversion.convert(key, name, email);
+        }
+
+        Ok(conversion)
+    }
+
+    fn parse_git_together(config: &str) -> Conversion {
+        let entries = Self::parse_git_config(config);
+        let domain = entries.get("git-together.domain");
+
+        let mut conversion = Conversion::default();
+        for (config_key, value) in &entries {
+            let Some(key) = config_key.strip_prefix("git-together.authors.") else {
+                continue;
+            };
+            let (name, username) = Self::split_name(value);
+            let email = entries
+                .get(&format!("git-together.emails.{key}"))
+                .cloned()
+                .or_else(|| Some(format!("{}@{}", username?, domain?)));
+            conversion.convert(key, name, email);
+        }
+
+        conversion
+    }
+
+    fn parse_git_pair(config: &str) -> Conversion {
+        let entries = Self::parse_git_config(config);
+        let domain = entries.get("pair.domain");
+
+        let mut conversion = Conversion::default();
+        for (config_key, value) in &entries {
+            let Some(key) = config_key.strip_prefix("pair.") else {
+                continue;
+            };
+            if key == "domain" {
+                continue;
+            }
+
+            if value.contains('<') {
+                match TeamMember::parse(key, value) {
+                    Ok(team_member) => conversion.team_members.push(team_member),
+                    Err(err) => conversion.failures.push((key.to_owned(), err.to_string())),
+                }
+            } else {
+                let (name, username) = Self::split_name(value);
+                let email = username.zip(domain).map(|(x, y)| format!("{x}@{y}"));
+                conversion.convert(key, name, email);
+            }
+        }
+
+        conversion
+    }
+
+    /// Parses output of `git config --get-regexp` into ordered key value pairs
+    fn parse_git_config(config: &str) -> GitConfigEntries {
+        GitConfigEntries(
+            config
+                .lines()
+                .map(|x| x.split_once(' ').unwrap_or((x, "")))
+                .map(|(key, value)| (key.to_owned(), value.to_owned()))
+                .collect(),
+        )
+    }
+}
+
+struct GitConfigEntries(Vec<(String, String)>);
+
+impl GitConfigEntries {
+    /// Gets the last value of the key as git does
+    fn get(&self, key: &str) -> Option<&String> {
+        self.0.iter().rev().find(|(x, _)| x == key).map(|(_, x)| x)
+    }
+}
+
+impl<'a> IntoIterator for &'a GitConfigEntries {
+    type Item = &'a (String, String);
+    type IntoIter = std::slice::Iter<'a, (String, String)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{CmdOutput, MockCommandRunner};
+
+    fn failure(key: &str, reason: &str) -> (String, String) {
+        (key.to_owned(), reason.to_owned())
+    }
+
+    #[test]
+    fn test_parse_git_duet() -> Result<()> {
+        let content = r#"
+pairs:
+  lm: Leo Messi; leo.messi
+  em: Emi Martinez
+  ad: Angel Di Maria
+email:
+  domain: example.com
+email_addresses:
+  em: emi.martinez@example.com
+"#;
+
+        let result = ImportSource::parse_git_duet(content)?;
+
+        assert_eq!(
+            result,
+            Conversion {
+                team_members: vec![
+                    TeamMember::new("em", "Emi Martinez", "emi.martinez@example.com")?,
+                    TeamMember::new("lm", "Leo Messi", "leo.messi@example.com")?,
+                ],
+                failures: vec![failure("ad", "no email address")],
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_git_duet_when_invalid() -> Result<()> {
+        let result = ImportSource::parse_git_duet("pairs: [lm]");
+
+        assert!(result.is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_git_together() -> Result<()> {
+        let config = "git-together.domain example.com
+git-together.authors.lm Leo Messi; leo.messi
+git-together.authors.em Emi Martinez; emi
+git-together.emails.em emi.martinez@example.com
+git-together.authors.ad Angel Di Maria
+git-together.active lm+em
+";
+
+        let result = ImportSource::parse_git_together(config);
+
+        assert_eq!(
+            result,
+            Conversion {
+                team_members: vec![
+                    TeamMember::new("lm", "Leo Messi", "leo.messi@example.com")?,
+                    TeamMember::new("em", "Emi Martinez", "emi.martinez@example.com")?,
+                ],
+                failures: vec![failure("ad", "no email address")],
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_git_pair() -> Result<()> {
+        let config = "pair.lm Leo Messi <leo.messi@example.com>
+pair.domain example.com
+pair.em Emi Martinez; emi.martinez
+pair.ad Angel Di Maria
+pair.rdp <rodrigo.depaul@example.com>
+";
+
+        let result = ImportSource::parse_git_pair(config);
+
+        assert_eq!(
+            result,
+            Conversion {
+                team_members: vec![
+                    TeamMember::new("lm", "Leo Messi", "leo.messi@example.com")?,
+                    TeamMember::new("em", "Emi Martinez", "emi.martinez@example.com")?,
+                ],
+                failures: vec![
+                    failure("ad", "no email address"),
+                    failure(
+                        "rdp",
+                        "Invalid team member with key rdp: Invalid name: it must not be empty",
+                    ),
+                ],
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_git_pair_from_config_file() -> Result<()> {
+        let mut mock_command_runner = MockCommandRunner::new();
+        mock_command_runner
+            .expect_execute()
+            .withf(|program, args| {
+                program == "git"
+                    && args
+                        == [
+                            "config",
+                            "--file",
+                            "pair.config",
+                            "--get-regexp",
+                            "^pair\\.",
+                        ]
+            })
+            .once()
+            .returning(|_, _| {
+                Ok(CmdOutput {
+                    stdout: b"pair.lm Leo Messi <leo.messi@example.com>\n".into(),
+                    stderr: vec![],
+                    status_code: Some(0),
+                })
+            });
+
+        let result =
+            ImportSource::Pair.load(&mock_command_runner, Some(Path::new("pair.config")))?;
+
+        assert_eq!(
+            result.team_members,
+            vec![TeamMember::new("lm", "Leo Messi", "leo.messi@example.com")?]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_git_together_when_not_configured() -> Result<()> {
+        let mut mock_command_runner = MockCommandRunner::new();
+        mock_command_runner
+            .expect_execute()
+            .withf(|_, args| args == ["config", "--get-regexp", "^git-together\\."])
+            .once()
+            .returning(|_, _| {
+                Ok(CmdOutput {
+                    stdout: vec![],
+                    stderr: vec![],
+                    status_code: Some(1),
+                })
+            });
+
+        let result = ImportSource::Together.load(&mock_command_runner, None)?;
+
+        assert_eq!(result, Conversion::default());
+
+        Ok(())
+    }
+}
@@ -1,17 +1,32 @@
 use crate::Result;
-use crate::repositories::{MobSessionRepo, TeamMemberRepo};
-use clap::{Parser, arg};
+use crate::commands::output_format::{OutputFormat, Record};
+use crate::repositories::{
+    HistoryEvent, HistoryRepo, MobSessionRepo, MobSessionScope, PresetRepo, SessionEnd,
+    SessionExpiry, TeamGroup, TeamGroupRepo, TeamMember, TeamMemberRepo,
+};
+use crate::validation;
+use clap::{ArgGroup, Parser};
 use inquire::MultiSelect;
+use serde::Serialize;
+use std::fmt;
 use std::io::Write;
 
 #[derive(Parser)]
 #[command(arg_required_else_help = true)]
+#[command(group(ArgGroup::new("new_coauthors").args(["with", "add"]).multiple(true)))]
 pub(crate) struct Mob {
     /// Sets co-author(s) from team member(s) in the mob/pair programming session
     ///
-    /// This will clear any existing co-author(s) in current session
+    /// This will clear any existing co-author(s) in current session.
+    /// A team group can be given as @name and team member(s) can be excluded with -key or -@name.
+    /// A team member can also be given by a unique prefix of their key or by their name or email,
+    /// and a co-author who is not a team member as "Name <email>"
     ///
     /// Usage example: git mob pair --with lm mj
+    ///
+    /// Usage example: git mob --with @frontend -lm
+    ///
+    /// Usage example: git mob --with lm "John Doe <john.doe@example.com>"
     #[arg(short='w', long="with", num_args=0.., value_name="COAUTHOR_KEY")]
     pub(crate) with: Option<Vec<String>>,
     /// Adds co-author to the mob/pair programming session (usually non-team member)
@@ -24,93 +39,310 @@ pub(crate) struct Mob {
     /// Usage example: git mob --clear
     #[arg(short = 'c', long = "clear")]
     pub(crate) clear: bool,
+    /// Sets driver of the mob/pair programming session from team member
+    ///
+    /// The driver becomes the commit author (via author.name and author.email git config in the same scope as the session)
+    /// and is removed from the co-author(s). The previous driver becomes a co-author.
+    ///
+    /// Usage example: git mob --driver lm
+    #[arg(long = "driver", value_name = "TEAM_MEMBER_KEY")]
+    pub(crate) driver: Option<String>,
     /// Lists co-author(s) in the mob/pair programming session
     ///
     /// Usage example: git mob --list
     #[arg(short = 'l', long = "list")]
     pub(crate) list: bool,
+    /// Shows the scope, driver and expiry of the mob/pair programming session with --list
+    ///
+    /// Without it, --list only prints the co-author(s) one per line, which githooks set up by older versions rely on
+    ///
+    /// Usage example: git mob --list --verbose
+    #[arg(long = "verbose", requires = "list")]
+    pub(crate) verbose: bool,
     /// Lists Co-authored-by trailers in the mob/pair programming session
     ///
     /// Usage example: git mob --trailers
     #[arg(short = 't', long = "trailers")]
     pub(crate) trailers: bool,
+    /// Sets format of the output of the listing commands
+    ///
+    /// Applies to --list, --trailers, team-member --list, history, stats and schedule.
+    /// json prints a versioned document described in docs/output_schema.json.
+    /// tsv, table, csv and markdown print one row per entry with a header row.
+    /// Formats other than text can't be used with both --list and --trailers.
+    ///
+    /// Usage example: git mob --list --format json
+    #[arg(
+        long = "format",
+        value_enum,
+        value_name = "FORMAT",
+        default_value_t,
+        global = true
+    )]
+    pub(crate) format: OutputFormat,
+    /// Uses the mob/pair programming session of the current repository instead of the global one
+    ///
+    /// The local session takes precedence over the global one when committing in the repository
+    ///
+    /// Usage example: git mob --with lm --local
+    #[arg(long = "local")]
+    pub(crate) local: bool,
+    /// Sets how long mob/pair programming sessions last after they start e.g. 30m, 8h, 1d, eod (end of day) or never
+    ///
+    /// An expired session is ignored when committing. Local sessions expire after the global setting unless it is set with --local
+    ///
+    /// Usage example: git mob --expire-after eod
+    #[arg(long = "expire-after", value_name = "DURATION")]
+    pub(crate) expire_after: Option<SessionExpiry>,
+    /// Allows unusual but valid name and email of the co-author added with --add or given as "Name <email>" to --with
+    ///
+    /// e.g. emails without a domain name or names wrapped in quotes
+    ///
+    /// Usage example: git mob --add "John Doe" john@localhost --allow-unusual
+    #[arg(long = "allow-unusual", requires = "new_coauthors")]
+    pub(crate) allow_unusual: bool,
 }
 
 impl Mob {
     pub(crate) fn handle(
         &self,
         team_member_repo: &impl TeamMemberRepo,
+        team_group_repo: &impl TeamGroupRepo,
         mob_repo: &impl MobSessionRepo,
+        preset_repo: &impl PresetRepo,
+        history_repo: &impl HistoryRepo,
         out: &mut impl Write,
     ) -> Result<()> {
+        let scope = if self.local {
+            MobSessionScope::Local
+        } else {
+            MobSessionScope::Global
+        };
+
+        if self.format != OutputFormat::Text && !self.list && !self.trailers {
+            return Err(
+                "--format only applies to --list, --trailers and the listing commands".into(),
+            );
+        }
+        // a JSON document or table per listing would not make one valid output
+        if self.list && self.trailers && self.format != OutputFormat::Text {
+            return Err(
+                "--list and --trailers can only be used together with --format text".into(),
+            );
+        }
+
+        // the driver is resolved first so that a typo leaves the session untouched
+        let driver = match self.driver.as_deref() {
+            Some(token) => Some(
+                CoauthorResolver::new(team_member_repo, self.allow_unusual)?
+                    .resolve_team_member(token)?,
+            ),
+            None => None,
+        };
+        let driver_key = driver.as_ref().map(|x| x.key.as_str());
+
         if self.clear {
-            mob_repo.clear()?;
+            record_end(mob_repo, history_repo, scope, SessionEnd::Cleared)?;
+            mob_repo.clear(scope)?;
+        }
+
+        if let Some(expiry) = self.expire_after {
+            mob_repo.set_expire_after(scope, expiry)?;
         }
 
         if self.list {
-            let coauthors = mob_repo.list_coauthors()?;
-            if !coauthors.is_empty() {
-                writeln!(out, "{}", coauthors.join("\n"))?
+            match self.format {
+                OutputFormat::Text if self.verbose => {
+                    for expired_scope in [MobSessionScope::Local, MobSessionScope::Global] {
+                        if let Some(expired_at) = mob_repo.expired_at(expired_scope)? {
+                            writeln!(
+                                out,
+                                "Mob session ({expired_scope}) expired at {}",
+                                expired_at.format("%Y-%m-%d %H:%M")
+                            )?;
+                        }
+                    }
+
+                    let (active_scope, coauthors, preset) =
+                        list_effective_coauthors(mob_repo, preset_repo)?;
+                    let driver = mob_repo.get_driver(active_scope)?;
+                    if driver.is_some() || (preset.is_none() && !coauthors.is_empty()) {
+                        writeln!(out, "Mob session ({active_scope}):")?;
+                    }
+                    if let Some(driver) = &driver {
+                        writeln!(out, "Driver: {driver}")?;
+                    }
+                    match preset {
+                        Some(preset) => {
+                            writeln!(out, "Default preset ({preset}):")?;
+                            writeln!(out, "{}", coauthors.join("\n"))?
+                        }
+                        None if driver.is_some() => {
+                            for coauthor in coauthors {
+                                writeln!(out, "Navigator: {coauthor}")?;
+                            }
+                        }
+                        None if !coauthors.is_empty() => writeln!(out, "{}", coauthors.join("\n"))?,
+                        None => {}
+                    }
+                }
+                // one co-author per line, as githooks of older versions prefix each line with Co-authored-by
+                OutputFormat::Text => {
+                    let (_, coauthors, _) = list_effective_coauthors(mob_repo, preset_repo)?;
+                    if !coauthors.is_empty() {
+                        writeln!(out, "{}", coauthors.join("\n"))?
+                    }
+                }
+                format => {
+                    let (active_scope, coauthors, _) =
+                        list_effective_coauthors(mob_repo, preset_repo)?;
+                    let driver = mob_repo.get_driver(active_scope)?;
+                    let team_members = team_member_repo.list()?;
+                    let records: Vec<MemberRecord> = driver
+                        .iter()
+                        .map(|x| ("driver", x))
+                        .chain(coauthors.iter().map(|x| ("coauthor", x)))
+                        .map(|(role, member)| MemberRecord {
+                            role,
+                            coauthor: CoauthorRecord::new(member, active_scope, &team_members),
+                        })
+                        .collect();
+                    format.write_records(out, "members", &records)?;
+                }
             }
         }
 
         if self.trailers {
-            let coauthors = mob_repo.list_coauthors()?;
-            let trailers = coauthors
-                .iter()
-                .map(|x| format!("Co-authored-by: {x}"))
-                .collect::<Vec<String>>()
-                .join("\n");
-
-            if !coauthors.is_empty() {
-                writeln!(out, "{trailers}")?
+            let (active_scope, coauthors, _) = list_effective_coauthors(mob_repo, preset_repo)?;
+            let coauthors = exclude_author(mob_repo, coauthors)?;
+            match self.format {
+                OutputFormat::Text => {
+                    let trailers = coauthors
+                        .iter()
+                        .map(|x| format!("Co-authored-by: {x}"))
+                        .collect::<Vec<String>>()
+                        .join("\n");
+
+                    if !coauthors.is_empty() {
+                        writeln!(out, "{trailers}")?
+                    }
+                }
+                format => {
+                    let team_members = team_member_repo.list()?;
+                    let records: Vec<TrailerRecord> = coauthors
+                        .iter()
+                        .map(|x| TrailerRecord {
+                            coauthor: CoauthorRecord::new(x, active_scope, &team_members),
+                            trailer: format!("Co-authored-by: {x}"),
+                        })
+                        .collect();
+                    format.write_records(out, "trailers", &records)?;
+                }
             }
         }
 
         match self.with.as_deref() {
             None => {}
             Some([]) => {
-                let team_members = team_member_repo.list(false)?;
+                let team_members: Vec<_> = team_member_repo
+                    .list()?
+                    .into_iter()
+                    .filter(|x| Some(x.key.as_str()) != driver_key)
+                    .collect();
                 if team_members.is_empty() {
                     return Err(
                         "No team member(s) found. At least one team member must be added".into(),
                     );
                 }
 
-                let result = MultiSelect::new("Select active co-author(s):", team_members)
-                    .prompt_skippable()?;
+                // groups are offered first as they select several team members at once
+                let options: Vec<_> = team_group_repo
+                    .list()?
+                    .into_iter()
+                    .map(CoauthorOption::Group)
+                    .chain(team_members.iter().cloned().map(CoauthorOption::TeamMember))
+                    .collect();
+
+                let result =
+                    MultiSelect::new("Select active co-author(s):", options).prompt_skippable()?;
                 if let Some(selected) = result {
-                    mob_repo.clear()?;
-                    for team_member in selected.iter() {
-                        mob_repo.add_coauthor(team_member)?;
+                    let ended = if selected.is_empty() {
+                        SessionEnd::Cleared
+                    } else {
+                        SessionEnd::Replaced
+                    };
+                    record_end(mob_repo, history_repo, scope, ended)?;
+                    mob_repo.clear(scope)?;
+
+                    let aliases = team_member_repo.list_aliases()?;
+                    let mut coauthors: Vec<String> = Vec::new();
+                    for option in selected {
+                        let keys = match option {
+                            CoauthorOption::Group(team_group) => team_group.keys,
+                            CoauthorOption::TeamMember(team_member) => vec![team_member.key],
+                        };
+                        // group members who are the driver or no longer team members are skipped
+                        for team_member in keys.iter().filter_map(|key| {
+                            let key = resolve_alias(&aliases, key);
+                            team_members.iter().find(|x| x.key == key)
+                        }) {
+                            let coauthor = team_member.to_string();
+                            if !coauthors.contains(&coauthor) {
+                                mob_repo.add_coauthor(scope, &coauthor)?;
+                                coauthors.push(coauthor);
+                            }
+                        }
                     }
 
-                    if selected.is_empty() {
+                    if coauthors.is_empty() {
                         writeln!(out, "Going solo!")?
+                    } else {
+                        record_history(history_repo, scope, HistoryEvent::Start, &coauthors);
                     }
                 }
             }
-            Some(team_member_keys) => {
-                let mut coauthors: Vec<String> = Vec::new();
-                mob_repo.clear()?;
-
-                for key in team_member_keys {
-                    match team_member_repo.get(key)? {
-                        Some(team_member) => {
-                            mob_repo.add_coauthor(&team_member)?;
-                            coauthors.push(team_member);
-                        }
-                        None => return Err(format!("No team member found with key: {key}").into()),
-                    }
+            Some(tokens) => {
+                // every argument is resolved first so that a typo leaves the session untouched
+                let coauthors = resolve_coauthors(
+                    tokens,
+                    driver_key,
+                    self.allow_unusual,
+                    team_member_repo,
+                    team_group_repo,
+                )?;
+                record_end(mob_repo, history_repo, scope, SessionEnd::Replaced)?;
+                mob_repo.clear(scope)?;
+                for coauthor in &coauthors {
+                    mob_repo.add_coauthor(scope, coauthor)?;
                 }
 
+                if !coauthors.is_empty() {
+                    record_history(history_repo, scope, HistoryEvent::Start, &coauthors);
+                }
                 writeln!(out, "{}", coauthors.join("\n"))?
             }
         }
 
+        if let Some(driver) = &driver {
+            switch_driver(mob_repo, scope, driver)?;
+            writeln!(out, "Driver: {driver}")?
+        }
+
         if let Some([name, email]) = self.add.as_deref() {
+            let name = validation::normalize_name(name)?;
+            let email = validation::normalize_email(email)?;
+            if !self.allow_unusual {
+                validation::check_usual_name(&name)?;
+                validation::check_usual_email(&email)?;
+            }
             let coauthor = format!("{name} <{email}>");
-            mob_repo.add_coauthor(&coauthor)?;
+            mob_repo.add_coauthor(scope, &coauthor)?;
+            record_history(
+                history_repo,
+                scope,
+                HistoryEvent::Add,
+                std::slice::from_ref(&coauthor),
+            );
             writeln!(out, "{coauthor}")?
         }
 
@@ -118,17 +350,496 @@ impl Mob {
     }
 }
 
+/// Option of the co-author(s) multi-select prompt
+enum CoauthorOption {
+    Group(TeamGroup),
+    TeamMember(TeamMember),
+}
+
+impl fmt::Display for CoauthorOption {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CoauthorOption::Group(team_group) => write!(f, "{team_group}"),
+            CoauthorOption::TeamMember(team_member) => write!(f, "{team_member}"),
+        }
+    }
+}
+
+/// Member of the mob session in the structured output formats, resolved to a team member by email
+#[derive(Serialize)]
+struct CoauthorRecord {
+    key: Option<String>,
+    name: String,
+    email: String,
+    scope: String,
+    source: Option<String>,
+}
+
+impl CoauthorRecord {
+    fn new(coauthor: &str, scope: MobSessionScope, team_members: &[TeamMember]) -> Self {
+        let (name, email) = split_coauthor(coauthor).unwrap_or((coauthor.trim(), ""));
+        let team_member = team_members
+            .iter()
+            .find(|x| !email.is_empty() && x.email.to_lowercase() == email.to_lowercase());
+
+        CoauthorRecord {
+            key: team_member.map(|x| x.key.to_owned()),
+            name: name.to_owned(),
+            email: email.to_owned(),
+            scope: scope.to_string(),
+            source: team_member.and_then(|x| x.source).map(|x| x.to_string()),
+        }
+    }
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.key.clone().unwrap_or_default(),
+            self.name.to_owned(),
+            self.email.to_owned(),
+            self.scope.to_owned(),
+            self.source.clone().unwrap_or_default(),
+        ]
+    }
+}
+
+/// Entry of --list in the structured output formats
+#[derive(Serialize)]
+struct MemberRecord {
+    /// Either driver or coauthor
+    role: &'static str,
+    #[serde(flatten)]
+    coauthor: CoauthorRecord,
+}
+
+impl Record for MemberRecord {
+    const COLUMNS: &'static [&'static str] = &["role", "key", "name", "email", "scope", "source"];
+
+    fn cells(&self) -> Vec<String> {
+        [self.role.to_owned()]
+            .into_iter()
+            .chain(self.coauthor.cells())
+            .collect()
+    }
+}
+
+/// Entry of --trailers in the structured output formats
+#[derive(Serialize)]
+struct TrailerRecord {
+    #[serde(flatten)]
+    coauthor: CoauthorRecord,
+    trailer: String,
+}
+
+impl Record for TrailerRecord {
+    const COLUMNS: &'static [&'static str] =
+        &["key", "name", "email", "scope", "source", "trailer"];
+
+    fn cells(&self) -> Vec<String> {
+        let mut cells = self.coauthor.cells();
+        cells.push(self.trailer.to_owned());
+        cells
+    }
+}
+
+/// Splits a co-author formatted as "Name <email>" into the trimmed name and email
+fn split_coauthor(coauthor: &str) -> Option<(&str, &str)> {
+    coauthor
+        .trim()
+        .strip_suffix('>')
+        .and_then(|x| x.rsplit_once('<'))
+        .map(|(name, email)| (name.trim(), email.trim()))
+}
+
+/// Co-author resolved from an argument of --with
+enum ResolvedCoauthor {
+    TeamMember(TeamMember),
+    /// Co-author given as "Name <email>" who is not a team member
+    AdHoc(String),
+}
+
+impl fmt::Display for ResolvedCoauthor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ResolvedCoauthor::TeamMember(team_member) => write!(f, "{team_member}"),
+            ResolvedCoauthor::AdHoc(coauthor) => write!(f, "{coauthor}"),
+        }
+    }
+}
+
+/// Resolves the arguments of --with to co-author(s), expanding @name to the team group's
+/// members and dropping the ones excluded with -key or -@name, keeping the order of first
+/// appearance. The driver is skipped as they are the commit author.
+fn resolve_coauthors(
+    tokens: &[String],
+    driver: Option<&str>,
+    allow_unusual: bool,
+    team_member_repo: &impl TeamMemberRepo,
+    team_group_repo: &impl TeamGroupRepo,
+) -> Result<Vec<String>> {
+    let mut resolver = CoauthorResolver::new(team_member_repo, allow_unusual)?;
+    let mut included: Vec<ResolvedCoauthor> = Vec::new();
+    let mut excluded: Vec<ResolvedCoauthor> = Vec::new();
+
+    for token in tokens {
+        let (coauthors, token) = match token.strip_prefix('-') {
+            Some(token) => (&mut excluded, token),
+            None => (&mut included, token.as_str()),
+        };
+        match token.strip_prefix('@') {
+            Some(name) => match team_group_repo.get(name)? {
+                Some(team_group) => {
+                    for key in &team_group.keys {
+                        // group members are stored by key so they are never guessed, and the
+                        // ones who are no longer team members are skipped as in the picker
+                        match resolver.get(key)? {
+                            Some(team_member) => {
+                                coauthors.push(ResolvedCoauthor::TeamMember(team_member))
+                            }
+                            None => eprintln!(
+                                "Warning: Skipped missing team member with key {key} of team group @{name}"
+                            ),
+                        }
+                    }
+                }
+                None => return Err(format!("No team group found with name: {name}").into()),
+            },
+            None => coauthors.push(resolver.resolve(token)?),
+        }
+    }
+
+    let excluded: Vec<String> = excluded.iter().map(|x| x.to_string()).collect();
+    let mut resolved: Vec<String> = Vec::new();
+    for coauthor in included {
+        if let ResolvedCoauthor::TeamMember(team_member) = &coauthor
+            && Some(team_member.key.as_str()) == driver
+        {
+            continue;
+        }
+        let coauthor = coauthor.to_string();
+        if !excluded.contains(&coauthor) && !resolved.contains(&coauthor) {
+            resolved.push(coauthor);
+        }
+    }
+    Ok(resolved)
+}
+
+/// Resolves an argument of --with (or another argument taking a team member key) by exact key or alias, then unique key (or alias) prefix,
+/// then name or email ignoring case. An argument formatted as "Name <email>" is an ad-hoc
+/// co-author instead.
+pub(crate) struct CoauthorResolver<'a, R> {
+    team_member_repo: &'a R,
+    aliases: Vec<(String, String)>,
+    /// Listed on first use as arguments are usually exact keys
+    team_members: Option<Vec<TeamMember>>,
+    allow_unusual: bool,
+}
+
+impl<'a, R: TeamMemberRepo> CoauthorResolver<'a, R> {
+    /// Maximum number of suggestions in the error of an ambiguous or unknown argument
+    const MAX_SUGGESTIONS: usize = 3;
+
+    pub(crate) fn new(team_member_repo: &'a R, allow_unusual: bool) -> Result<Self> {
+        Ok(Self {
+            team_member_repo,
+            aliases: team_member_repo.list_aliases()?,
+            team_members: None,
+            allow_unusual,
+        })
+    }
+
+    /// Gets the team member by exact key or alias
+    pub(crate) fn get(&self, key: &str) -> Result<Option<TeamMember>> {
+        self.team_member_repo
+            .get(&resolve_alias(&self.aliases, key))
+    }
+
+    fn resolve(&mut self, token: &str) -> Result<ResolvedCoauthor> {
+        if let Some((name, email)) = split_coauthor(token) {
+            let name = validation::normalize_name(name)?;
+            let email = validation::normalize_email(email)?;
+            if !self.allow_unusual {
+                validation::check_usual_name(&name)?;
+                validation::check_usual_email(&email)?;
+            }
+            return Ok(ResolvedCoauthor::AdHoc(format!("{name} <{email}>")));
+        }
+
+        Ok(ResolvedCoauthor::TeamMember(
+            self.resolve_team_member(token)?,
+        ))
+    }
+
+    /// Resolves the argument to a team member the same way, without accepting an ad-hoc co-author
+    pub(crate) fn resolve_team_member(&mut self, token: &str) -> Result<TeamMember> {
+        if let Some(team_member) = self.get(token)? {
+            return Ok(team_member);
+        }
+
+        if self.team_members.is_none() {
+            self.team_members = Some(self.team_member_repo.list()?);
+        }
+        let team_members = self.team_members.as_deref().unwrap_or_default();
+
+        let by_prefix: Vec<&TeamMember> = team_members
+            .iter()
+            .filter(|x| self.keys_of(x).any(|key| key.starts_with(token)))
+            .collect();
+        if let Some(team_member) = self.pick_unique(token, by_prefix)? {
+            return Ok(team_member);
+        }
+
+        let token_lowercase = token.to_lowercase();
+        let by_name_or_email: Vec<&TeamMember> = team_members
+            .iter()
+            .filter(|x| {
+                x.name.to_lowercase() == token_lowercase
+                    || x.email.to_lowercase() == token_lowercase
+            })
+            .collect();
+        if let Some(team_member) = self.pick_unique(token, by_name_or_email)? {
+            return Ok(team_member);
+        }
+
+        // near misses such as typos and swapped letters are suggested
+        let max_distance = (token.chars().count() / 3).max(1);
+        let similar: Vec<&TeamMember> = team_members
+            .iter()
+            .filter(|x| self.distance(token, x) <= max_distance)
+            .collect();
+        match self.suggestions(token, similar) {
+            Some(suggestions) => Err(format!(
+                "No team member found with key: {token}. Did you mean {suggestions}?"
+            )
+            .into()),
+            None => Err(format!("No team member found with key: {token}").into()),
+        }
+    }
+
+    /// Returns the only matching team member, or an error with suggestions if there are several
+    fn pick_unique(&self, token: &str, matches: Vec<&TeamMember>) -> Result<Option<TeamMember>> {
+        match matches.as_slice() {
+            [] => Ok(None),
+            [team_member] => Ok(Some((*team_member).clone())),
+            _ => Err(format!(
+                "Ambiguous team member: {token}. Did you mean {}?",
+                self.suggestions(token, matches).unwrap_or_default()
+            )
+            .into()),
+        }
+    }
+
+    /// Formats the team members closest to the argument first, e.g. "lm (Leo Messi <leo.messi@example.com>)"
+    fn suggestions(&self, token: &str, mut team_members: Vec<&TeamMember>) -> Option<String> {
+        if team_members.is_empty() {
+            return None;
+        }
+        team_members.sort_by_cached_key(|x| (self.distance(token, x), x.key.clone()));
+        Some(
+            team_members
+                .iter()
+                .take(Self::MAX_SUGGESTIONS)
+                .map(|x| format!("{} ({x})", x.key))
+                .collect::<Vec<String>>()
+                .join(", "),
+        )
+    }
+
+    /// Edit distance between the argument and the closest of the team member's key, aliases,
+    /// name, words of the name, email and local part of the email, ignoring case
+    fn distance(&self, token: &str, team_member: &TeamMember) -> usize {
+        let token = token.to_lowercase();
+        let local_part = team_member
+            .email
+            .split_once('@')
+            .map_or(team_member.email.as_str(), |(x, _)| x);
+
+        self.keys_of(team_member)
+            .chain([
+                team_member.name.as_str(),
+                team_member.email.as_str(),
+                local_part,
+            ])
+            .chain(team_member.name.split_whitespace())
+            .map(|x| strsim::damerau_levenshtein(&token, &x.to_lowercase()))
+            .min()
+            .unwrap_or(usize::MAX)
+    }
+
+    /// Key of the team member followed by its aliases
+    fn keys_of<'b>(&'b self, team_member: &'b TeamMember) -> impl Iterator<Item = &'b str> {
+        std::iter::once(team_member.key.as_str()).chain(
+            self.aliases
+                .iter()
+                .filter(|(_, key)| *key == team_member.key)
+                .map(|(alias, _)| alias.as_str()),
+        )
+    }
+}
+
+/// Returns the key which the alias resolves to, or the key itself if it is not an alias
+fn resolve_alias(aliases: &[(String, String)], key: &str) -> String {
+    aliases
+        .iter()
+        .find(|(alias, _)| alias == key)
+        .map_or(key, |(_, key)| key)
+        .to_owned()
+}
+
+/// Lists co-author(s) of the active session along with its scope. Without co-author(s) in the
+/// session, the ones of the default preset of the repository are listed instead (if it still
+/// exists) along with its name, as they are the ones added to commits. The driver of the preset
+/// is listed first as a co-author, since the preset doesn't set the commit author.
+pub(crate) fn list_effective_coauthors(
+    mob_repo: &impl MobSessionRepo,
+    preset_repo: &impl PresetRepo,
+) -> Result<(MobSessionScope, Vec<String>, Option<String>)> {
+    let (scope, coauthors) = mob_repo.list_active_coauthors()?;
+    if coauthors.is_empty()
+        && let Some(name) = preset_repo.get_default()?
+        && let Some(preset) = preset_repo.get(&name)?
+    {
+        let mut coauthors: Vec<String> = preset.driver.into_iter().collect();
+        for coauthor in preset.coauthors {
+            if !coauthors.contains(&coauthor) {
+                coauthors.push(coauthor);
+            }
+        }
+        return Ok((scope, coauthors, Some(name)));
+    }
+
+    Ok((scope, coauthors, None))
+}
+
+/// Records the end of the session in the history unless the session has no members
+pub(crate) fn record_end(
+    mob_repo: &impl MobSessionRepo,
+    history_repo: &impl HistoryRepo,
+    scope: MobSessionScope,
+    ended: SessionEnd,
+) -> Result<()> {
+    let mut members = mob_repo.list_coauthors(scope)?;
+    if let Some(driver) = mob_repo.get_driver(scope)? {
+        members.insert(0, driver);
+    }
+    if members.is_empty() {
+        return Ok(());
+    }
+
+    let started_at = mob_repo.started_at(scope)?;
+    record_history(
+        history_repo,
+        scope,
+        HistoryEvent::End { ended, started_at },
+        &members,
+    );
+    Ok(())
+}
+
+/// Appends the change to the session to the history, only warning if it fails as the history
+/// must never stop the session from changing or a commit from being made
+pub(crate) fn record_history(
+    history_repo: &impl HistoryRepo,
+    scope: MobSessionScope,
+    event: HistoryEvent,
+    members: &[String],
+) {
+    if let Err(err) = history_repo.append(scope, event, members) {
+        eprintln!("Warning: Failed to record the mob session history: {err}");
+    }
+}
+
+/// Drops the co-author(s) with the same email as the commit author (ignoring case), e.g. when
+/// you are in the session yourself or commit with --author, as the author needs no trailer
+pub(crate) fn exclude_author(
+    mob_repo: &impl MobSessionRepo,
+    coauthors: Vec<String>,
+) -> Result<Vec<String>> {
+    if coauthors.is_empty() {
+        return Ok(coauthors);
+    }
+    let Some(author_email) = mob_repo.get_author_email()? else {
+        return Ok(coauthors);
+    };
+
+    let author_email = author_email.to_lowercase();
+    Ok(coauthors
+        .into_iter()
+        .filter(|coauthor| {
+            split_coauthor(coauthor).is_none_or(|(_, email)| email.to_lowercase() != author_email)
+        })
+        .collect())
+}
+
+/// Sets the driver and recomputes co-author(s) so that the new driver is not a co-author
+/// and the previous driver becomes one
+pub(crate) fn switch_driver(
+    mob_repo: &impl MobSessionRepo,
+    scope: MobSessionScope,
+    driver: &TeamMember,
+) -> Result<()> {
+    let previous_driver = mob_repo.get_driver(scope)?;
+    let coauthors = mob_repo.list_coauthors(scope)?;
+
+    mob_repo.set_driver(scope, driver)?;
+
+    let driver = driver.to_string();
+    if coauthors.contains(&driver) {
+        mob_repo.remove_coauthor(scope, &driver)?;
+    }
+    if let Some(previous_driver) = previous_driver
+        && previous_driver != driver
+        && !coauthors.contains(&previous_driver)
+    {
+        mob_repo.add_coauthor(scope, &previous_driver)?;
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::repositories::{MockMobSessionRepo, MockTeamMemberRepo};
+    use crate::repositories::{
+        MockHistoryRepo, MockMobSessionRepo, MockPresetRepo, MockTeamGroupRepo, MockTeamMemberRepo,
+        Preset, TeamMemberSource,
+    };
+    use chrono::{Local, TimeZone};
     use mockall::predicate;
 
+    fn create_team_members() -> Result<Vec<TeamMember>> {
+        Ok(vec![
+            TeamMember::new("leo", "Leo Messi", "leo.messi@example.com")?,
+            TeamMember::new("em", "Emi Martinez", "emi.martinez@example.com")?,
+            TeamMember::new(
+                "lisandro",
+                "Lisandro Martinez",
+                "lisandro.martinez@example.com",
+            )?,
+        ])
+    }
+
     #[test]
     fn test_clear_mob() -> Result<()> {
         let mock_team_member_repo = MockTeamMemberRepo::new();
         let mut mock_mob_repo = MockMobSessionRepo::new();
-        mock_mob_repo.expect_clear().once().returning(|| Ok(()));
+        let started_at = Local.with_ymd_and_hms(2026, 10, 18, 9, 0, 0).unwrap();
+        mock_mob_repo
+            .expect_list_coauthors()
+            .with(predicate::eq(MobSessionScope::Global))
+            .returning(|_| Ok(vec!["Emi Martinez <emi.martinez@example.com>".to_owned()]));
+        mock_mob_repo
+            .expect_get_driver()
+            .with(predicate::eq(MobSessionScope::Global))
+            .returning(|_| Ok(Some("Leo Messi <leo.messi@example.com>".to_owned())));
+        mock_mob_repo
+            .expect_started_at()
+            .with(predicate::eq(MobSessionScope::Global))
+            .returning(move |_| Ok(Some(started_at)));
+        mock_mob_repo
+            .expect_clear()
+            .with(predicate::eq(MobSessionScope::Global))
+            .once()
+            .returning(|_| Ok(()));
 
         let mob_cmd = Mob {
             clear: true,
@@ -136,10 +847,41 @@ mod tests {
             list: false,
             trailers: false,
             add: None,
+            local: false,
+            expire_after: None,
+            allow_unusual: false,
+            verbose: false,
+            format: OutputFormat::Text,
+            driver: None,
         };
 
+        let mut mock_history_repo = MockHistoryRepo::new();
+        mock_history_repo
+            .expect_append()
+            .withf(move |scope, event, members| {
+                *scope == MobSessionScope::Global
+                    && *event
+                        == HistoryEvent::End {
+                            ended: SessionEnd::Cleared,
+                            started_at: Some(started_at),
+                        }
+                    && members
+                        == [
+                            "Leo Messi <leo.messi@example.com>",
+                            "Emi Martinez <emi.martinez@example.com>",
+                        ]
+            })
+            .once()
+            .returning(|_, _, _| Ok(()));
         let mut out = Vec::new();
-        mob_cmd.handle(&mock_team_member_repo, &mock_mob_repo, &mut out)?;
+        mob_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &mock_mob_repo,
+            &MockPresetRepo::new(),
+            &mock_history_repo,
+            &mut out,
+        )?;
 
         Ok(())
     }
@@ -155,10 +897,12 @@ mod tests {
 
         let mock_team_member_repo = MockTeamMemberRepo::new();
         let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo.expect_expired_at().never();
         mock_mob_repo
-            .expect_list_coauthors()
+            .expect_list_active_coauthors()
             .once()
-            .returning(move || Ok(coauthors.to_owned()));
+            .returning(move || Ok((MobSessionScope::Global, coauthors.to_owned())));
+        mock_mob_repo.expect_get_driver().never();
 
         let mob_cmd = Mob {
             list: true,
@@ -166,10 +910,24 @@ mod tests {
             with: None,
             trailers: false,
             add: None,
+            local: false,
+            expire_after: None,
+            allow_unusual: false,
+            verbose: false,
+            format: OutputFormat::Text,
+            driver: None,
         };
 
+        let mock_history_repo = MockHistoryRepo::new();
         let mut out = Vec::new();
-        mob_cmd.handle(&mock_team_member_repo, &mock_mob_repo, &mut out)?;
+        mob_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &mock_mob_repo,
+            &MockPresetRepo::new(),
+            &mock_history_repo,
+            &mut out,
+        )?;
 
         assert_eq!(out, expected_output.as_bytes());
 
@@ -180,10 +938,18 @@ mod tests {
     fn test_list_mob_when_mob_session_is_empty() -> Result<()> {
         let mock_team_member_repo = MockTeamMemberRepo::new();
         let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo.expect_expired_at().never();
         mock_mob_repo
-            .expect_list_coauthors()
+            .expect_list_active_coauthors()
+            .once()
+            .returning(move || Ok((MobSessionScope::Global, vec![])));
+        mock_mob_repo.expect_get_driver().never();
+
+        let mut mock_preset_repo = MockPresetRepo::new();
+        mock_preset_repo
+            .expect_get_default()
             .once()
-            .returning(move || Ok(vec![]));
+            .returning(|| Ok(None));
 
         let mob_cmd = Mob {
             list: true,
@@ -191,10 +957,24 @@ mod tests {
             with: None,
             trailers: false,
             add: None,
+            local: false,
+            expire_after: None,
+            allow_unusual: false,
+            verbose: false,
+            format: OutputFormat::Text,
+            driver: None,
         };
 
+        let mock_history_repo = MockHistoryRepo::new();
         let mut out = Vec::new();
-        mob_cmd.handle(&mock_team_member_repo, &mock_mob_repo, &mut out)?;
+        mob_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &mock_mob_repo,
+            &mock_preset_repo,
+            &mock_history_repo,
+            &mut out,
+        )?;
 
         assert_eq!(out, b"");
 
@@ -211,9 +991,13 @@ mod tests {
         let mock_team_member_repo = MockTeamMemberRepo::new();
         let mut mock_mob_repo = MockMobSessionRepo::new();
         mock_mob_repo
-            .expect_list_coauthors()
+            .expect_list_active_coauthors()
             .once()
-            .returning(move || Ok(coauthors.to_owned()));
+            .returning(move || Ok((MobSessionScope::Global, coauthors.to_owned())));
+        mock_mob_repo
+            .expect_get_author_email()
+            .once()
+            .returning(|| Ok(Some("jane.doe@example.com".to_owned())));
 
         let mob_cmd = Mob {
             list: false,
@@ -221,10 +1005,24 @@ mod tests {
             with: None,
             trailers: true,
             add: None,
+            local: false,
+            expire_after: None,
+            allow_unusual: false,
+            verbose: false,
+            format: OutputFormat::Text,
+            driver: None,
         };
 
+        let mock_history_repo = MockHistoryRepo::new();
         let mut out = Vec::new();
-        mob_cmd.handle(&mock_team_member_repo, &mock_mob_repo, &mut out)?;
+        mob_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &mock_mob_repo,
+            &MockPresetRepo::new(),
+            &mock_history_repo,
+            &mut out,
+        )?;
 
         assert_eq!(
             out,
@@ -236,13 +1034,24 @@ mod tests {
     }
 
     #[test]
-    fn test_mob_coauthor_trailers_when_mob_session_is_empty() -> Result<()> {
-        let mock_team_member_repo = MockTeamMemberRepo::new();
+    fn test_mob_coauthor_trailers_given_author_in_mob_session() -> Result<()> {
         let mut mock_mob_repo = MockMobSessionRepo::new();
         mock_mob_repo
-            .expect_list_coauthors()
+            .expect_list_active_coauthors()
             .once()
-            .returning(move || Ok(vec![]));
+            .returning(|| {
+                Ok((
+                    MobSessionScope::Global,
+                    vec![
+                        "Leo Messi <leo.messi@example.com>".to_owned(),
+                        "Emi Martinez <emi.martinez@example.com>".to_owned(),
+                    ],
+                ))
+            });
+        mock_mob_repo
+            .expect_get_author_email()
+            .once()
+            .returning(|| Ok(Some("Emi.Martinez@example.com".to_owned())));
 
         let mob_cmd = Mob {
             list: false,
@@ -250,151 +1059,1710 @@ mod tests {
             with: None,
             trailers: true,
             add: None,
+            local: false,
+            expire_after: None,
+            allow_unusual: false,
+            verbose: false,
+            format: OutputFormat::Text,
+            driver: None,
         };
 
         let mut out = Vec::new();
-        mob_cmd.handle(&mock_team_member_repo, &mock_mob_repo, &mut out)?;
+        mob_cmd.handle(
+            &MockTeamMemberRepo::new(),
+            &MockTeamGroupRepo::new(),
+            &mock_mob_repo,
+            &MockPresetRepo::new(),
+            &MockHistoryRepo::new(),
+            &mut out,
+        )?;
 
-        assert_eq!(out, b"");
+        assert_eq!(out, b"Co-authored-by: Leo Messi <leo.messi@example.com>\n");
 
         Ok(())
     }
 
     #[test]
-    fn test_mob_with_given_no_team_members_added() -> Result<()> {
-        let coauthors = vec![];
+    fn test_mob_coauthor_trailers_when_mob_session_is_empty() -> Result<()> {
+        let mock_team_member_repo = MockTeamMemberRepo::new();
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo
+            .expect_list_active_coauthors()
+            .once()
+            .returning(move || Ok((MobSessionScope::Global, vec![])));
 
-        let mut mock_team_member_repo = MockTeamMemberRepo::new();
-        let mock_mob_repo = MockMobSessionRepo::new();
-        mock_team_member_repo
-            .expect_list()
-            .with(predicate::eq(false))
+        let mut mock_preset_repo = MockPresetRepo::new();
+        mock_preset_repo
+            .expect_get_default()
             .once()
-            .returning(move |_| Ok(coauthors.to_owned()));
+            .returning(|| Ok(None));
 
         let mob_cmd = Mob {
-            with: Some(vec![]),
-            clear: false,
             list: false,
-            trailers: false,
+            clear: false,
+            with: None,
+            trailers: true,
             add: None,
+            local: false,
+            expire_after: None,
+            allow_unusual: false,
+            verbose: false,
+            format: OutputFormat::Text,
+            driver: None,
         };
 
+        let mock_history_repo = MockHistoryRepo::new();
         let mut out = Vec::new();
-        let result = mob_cmd.handle(&mock_team_member_repo, &mock_mob_repo, &mut out);
+        mob_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &mock_mob_repo,
+            &mock_preset_repo,
+            &mock_history_repo,
+            &mut out,
+        )?;
 
-        assert!(result.is_err_and(|err| err.to_string()
-            == *"No team member(s) found. At least one team member must be added"));
+        assert_eq!(out, b"");
 
         Ok(())
     }
 
     #[test]
-    fn test_mob_with_by_keys() -> Result<()> {
-        let keys = vec!["lm".to_owned(), "em".to_owned()];
-        let coauthors = [
-            "Leo Messi <leo.messi@example.com>",
-            "Emi Martinez <emi.martinez@example.com>",
-        ];
-
-        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+    fn test_list_mob_given_default_preset() -> Result<()> {
         let mut mock_mob_repo = MockMobSessionRepo::new();
-        mock_mob_repo.expect_clear().once().returning(|| Ok(()));
-        mock_team_member_repo
-            .expect_get()
-            .with(predicate::eq(keys[0].to_owned()))
+        mock_mob_repo.expect_expired_at().returning(|_| Ok(None));
+        mock_mob_repo
+            .expect_list_active_coauthors()
             .once()
-            .returning(move |_| Ok(Some(coauthors[0].to_owned())));
+            .returning(|| Ok((MobSessionScope::Global, vec![])));
         mock_mob_repo
-            .expect_add_coauthor()
-            .with(predicate::eq(coauthors[0].to_owned()))
+            .expect_get_driver()
             .once()
-            .returning(|_| Ok(()));
-        mock_team_member_repo
-            .expect_get()
-            .with(predicate::eq(keys[1].to_owned()))
+            .returning(|_| Ok(None));
+        let mut mock_preset_repo = MockPresetRepo::new();
+        mock_preset_repo
+            .expect_get_default()
             .once()
-            .returning(move |_| Ok(Some(coauthors[1].to_owned())));
-        mock_mob_repo
-            .expect_add_coauthor()
-            .with(predicate::eq(coauthors[1].to_owned()))
+            .returning(|| Ok(Some("payments-mob".to_owned())));
+        mock_preset_repo
+            .expect_get()
+            .with(predicate::eq("payments-mob"))
             .once()
-            .returning(|_| Ok(()));
+            .returning(|name| {
+                Ok(Some(Preset::new(
+                    name,
+                    &["Leo Messi <leo.messi@example.com>".to_owned()],
+                )?))
+            });
 
         let mob_cmd = Mob {
-            with: Some(keys),
+            list: true,
             clear: false,
-            list: false,
+            with: None,
             trailers: false,
             add: None,
+            local: false,
+            expire_after: None,
+            allow_unusual: false,
+            verbose: true,
+            format: OutputFormat::Text,
+            driver: None,
         };
 
         let mut out = Vec::new();
-        mob_cmd.handle(&mock_team_member_repo, &mock_mob_repo, &mut out)?;
+        mob_cmd.handle(
+            &MockTeamMemberRepo::new(),
+            &MockTeamGroupRepo::new(),
+            &mock_mob_repo,
+            &mock_preset_repo,
+            &MockHistoryRepo::new(),
+            &mut out,
+        )?;
 
-        assert_eq!(out, format!("{}\n", coauthors.join("\n")).as_bytes());
+        assert_eq!(
+            out,
+            b"Default preset (payments-mob):\nLeo Messi <leo.messi@example.com>\n"
+        );
 
         Ok(())
     }
 
     #[test]
-    fn test_mob_with_by_key_when_team_member_not_found() -> Result<()> {
-        let key = "lm";
-
-        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+    fn test_mob_coauthor_trailers_given_default_preset() -> Result<()> {
         let mut mock_mob_repo = MockMobSessionRepo::new();
-        mock_mob_repo.expect_clear().once().returning(|| Ok(()));
-        mock_team_member_repo
-            .expect_get()
-            .with(predicate::eq(key))
+        mock_mob_repo
+            .expect_list_active_coauthors()
             .once()
-            .returning(|_| Ok(None));
+            .returning(|| Ok((MobSessionScope::Global, vec![])));
+        mock_mob_repo
+            .expect_get_author_email()
+            .once()
+            .returning(|| Ok(Some("jane.doe@example.com".to_owned())));
+        let mut mock_preset_repo = MockPresetRepo::new();
+        mock_preset_repo
+            .expect_get_default()
+            .once()
+            .returning(|| Ok(Some("payments-mob".to_owned())));
+        mock_preset_repo.expect_get().once().returning(|name| {
+            Ok(Some(Preset::new(
+                name,
+                &["Leo Messi <leo.messi@example.com>".to_owned()],
+            )?))
+        });
 
         let mob_cmd = Mob {
-            with: Some(vec![key.to_owned()]),
-            clear: false,
             list: false,
-            trailers: false,
+            clear: false,
+            with: None,
+            trailers: true,
             add: None,
+            local: false,
+            expire_after: None,
+            allow_unusual: false,
+            verbose: false,
+            format: OutputFormat::Text,
+            driver: None,
         };
 
         let mut out = Vec::new();
-        let result = mob_cmd.handle(&mock_team_member_repo, &mock_mob_repo, &mut out);
+        mob_cmd.handle(
+            &MockTeamMemberRepo::new(),
+            &MockTeamGroupRepo::new(),
+            &mock_mob_repo,
+            &mock_preset_repo,
+            &MockHistoryRepo::new(),
+            &mut out,
+        )?;
 
-        assert!(
-            result.is_err_and(
-                |err| err.to_string() == format!("No team member found with key: {key}")
-            )
-        );
+        assert_eq!(out, b"Co-authored-by: Leo Messi <leo.messi@example.com>\n");
 
         Ok(())
     }
 
     #[test]
-    fn test_add_coauthor() -> Result<()> {
-        let name = "Leo Messi";
+    fn test_mob_with_given_no_team_members_added() -> Result<()> {
+        let coauthors = vec![];
+
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        let mock_mob_repo = MockMobSessionRepo::new();
+        mock_team_member_repo
+            .expect_list()
+            .once()
+            .returning(move || Ok(coauthors.to_owned()));
+
+        let mob_cmd = Mob {
+            with: Some(vec![]),
+            clear: false,
+            list: false,
+            trailers: false,
+            add: None,
+            local: false,
+            expire_after: None,
+            allow_unusual: false,
+            verbose: false,
+            format: OutputFormat::Text,
+            driver: None,
+        };
+
+        let mock_history_repo = MockHistoryRepo::new();
+        let mut out = Vec::new();
+        let result = mob_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &mock_mob_repo,
+            &MockPresetRepo::new(),
+            &mock_history_repo,
+            &mut out,
+        );
+
+        assert!(result.is_err_and(|err| err.to_string()
+            == *"No team member(s) found. At least one team member must be added"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mob_with_by_keys() -> Result<()> {
+        let keys = vec!["lm".to_owned(), "em".to_owned()];
+        let coauthors = [
+            "Leo Messi <leo.messi@example.com>",
+            "Emi Martinez <emi.martinez@example.com>",
+        ];
+
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo
+            .expect_list_aliases()
+            .returning(|| Ok(vec![]));
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo
+            .expect_list_coauthors()
+            .returning(|_| Ok(vec![]));
+        mock_mob_repo.expect_get_driver().returning(|_| Ok(None));
+        mock_mob_repo
+            .expect_clear()
+            .with(predicate::eq(MobSessionScope::Global))
+            .once()
+            .returning(|_| Ok(()));
+        mock_team_member_repo
+            .expect_get()
+            .with(predicate::eq(keys[0].to_owned()))
+            .once()
+            .returning(move |key| Ok(Some(TeamMember::parse(key, coauthors[0])?)));
+        mock_mob_repo
+            .expect_add_coauthor()
+            .with(
+                predicate::eq(MobSessionScope::Global),
+                predicate::eq(coauthors[0].to_owned()),
+            )
+            .once()
+            .returning(|_, _| Ok(()));
+        mock_team_member_repo
+            .expect_get()
+            .with(predicate::eq(keys[1].to_owned()))
+            .once()
+            .returning(move |key| Ok(Some(TeamMember::parse(key, coauthors[1])?)));
+        mock_mob_repo
+            .expect_add_coauthor()
+            .with(
+                predicate::eq(MobSessionScope::Global),
+                predicate::eq(coauthors[1].to_owned()),
+            )
+            .once()
+            .returning(|_, _| Ok(()));
+
+        let mob_cmd = Mob {
+            with: Some(keys),
+            clear: false,
+            list: false,
+            trailers: false,
+            add: None,
+            local: false,
+            expire_after: None,
+            allow_unusual: false,
+            verbose: false,
+            format: OutputFormat::Text,
+            driver: None,
+        };
+
+        let mut mock_history_repo = MockHistoryRepo::new();
+        mock_history_repo
+            .expect_append()
+            .withf(move |scope, event, members| {
+                *scope == MobSessionScope::Global
+                    && *event == HistoryEvent::Start
+                    && members == coauthors
+            })
+            .once()
+            .returning(|_, _, _| Ok(()));
+        let mut out = Vec::new();
+        mob_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &mock_mob_repo,
+            &MockPresetRepo::new(),
+            &mock_history_repo,
+            &mut out,
+        )?;
+
+        assert_eq!(out, format!("{}\n", coauthors.join("\n")).as_bytes());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mob_with_by_key_when_team_member_not_found() -> Result<()> {
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo
+            .expect_list_aliases()
+            .returning(|| Ok(vec![]));
+        mock_team_member_repo
+            .expect_get()
+            .with(predicate::eq("lep"))
+            .once()
+            .returning(|_| Ok(None));
+        mock_team_member_repo
+            .expect_list()
+            .once()
+            .returning(create_team_members);
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo.expect_clear().never();
+
+        let mob_cmd = Mob {
+            with: Some(vec!["lep".to_owned()]),
+            clear: false,
+            list: false,
+            trailers: false,
+            add: None,
+            local: false,
+            expire_after: None,
+            allow_unusual: false,
+            verbose: false,
+            format: OutputFormat::Text,
+            driver: None,
+        };
+
+        let mut out = Vec::new();
+        let result = mob_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &mock_mob_repo,
+            &MockPresetRepo::new(),
+            &MockHistoryRepo::new(),
+            &mut out,
+        );
+
+        assert!(result.is_err_and(|err| err.to_string()
+            == "No team member found with key: lep. Did you mean leo (Leo Messi <leo.messi@example.com>)?"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mob_with_by_key_prefix_name_and_email() -> Result<()> {
+        let coauthors = [
+            "Leo Messi <leo.messi@example.com>",
+            "Emi Martinez <emi.martinez@example.com>",
+            "Lisandro Martinez <lisandro.martinez@example.com>",
+        ];
+
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo
+            .expect_list_aliases()
+            .returning(|| Ok(vec![]));
+        mock_team_member_repo.expect_get().returning(|_| Ok(None));
+        mock_team_member_repo
+            .expect_list()
+            .once()
+            .returning(create_team_members);
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo
+            .expect_list_coauthors()
+            .returning(|_| Ok(vec![]));
+        mock_mob_repo.expect_get_driver().returning(|_| Ok(None));
+        mock_mob_repo.expect_clear().once().returning(|_| Ok(()));
+        mock_mob_repo
+            .expect_add_coauthor()
+            .withf(move |_, coauthor| coauthors.contains(&coauthor))
+            .times(3)
+            .returning(|_, _| Ok(()));
+        let mut mock_history_repo = MockHistoryRepo::new();
+        mock_history_repo
+            .expect_append()
+            .withf(move |_, _, members| members == coauthors)
+            .once()
+            .returning(|_, _, _| Ok(()));
+
+        let mob_cmd = Mob {
+            with: Some(vec![
+                "le".into(),
+                "emi martinez".into(),
+                "Lisandro.Martinez@example.com".into(),
+            ]),
+            clear: false,
+            list: false,
+            trailers: false,
+            add: None,
+            local: false,
+            expire_after: None,
+            allow_unusual: false,
+            verbose: false,
+            format: OutputFormat::Text,
+            driver: None,
+        };
+
+        let mut out = Vec::new();
+        mob_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &mock_mob_repo,
+            &MockPresetRepo::new(),
+            &mock_history_repo,
+            &mut out,
+        )?;
+
+        assert_eq!(out, format!("{}\n", coauthors.join("\n")).as_bytes());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mob_with_when_key_prefix_ambiguous() -> Result<()> {
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo
+            .expect_list_aliases()
+            .returning(|| Ok(vec![]));
+        mock_team_member_repo.expect_get().returning(|_| Ok(None));
+        mock_team_member_repo
+            .expect_list()
+            .once()
+            .returning(create_team_members);
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo.expect_clear().never();
+
+        let mob_cmd = Mob {
+            with: Some(vec!["l".into()]),
+            clear: false,
+            list: false,
+            trailers: false,
+            add: None,
+            local: false,
+            expire_after: None,
+            allow_unusual: false,
+            verbose: false,
+            format: OutputFormat::Text,
+            driver: None,
+        };
+
+        let mut out = Vec::new();
+        let result = mob_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &mock_mob_repo,
+            &MockPresetRepo::new(),
+            &MockHistoryRepo::new(),
+            &mut out,
+        );
+
+        assert!(result.is_err_and(|err| err.to_string()
+            == "Ambiguous team member: l. Did you mean leo (Leo Messi <leo.messi@example.com>), lisandro (Lisandro Martinez <lisandro.martinez@example.com>)?"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mob_with_given_adhoc_coauthor() -> Result<()> {
+        let coauthor = "John Doe <john.doe@example.com>";
+
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo
+            .expect_list_aliases()
+            .returning(|| Ok(vec![]));
+        mock_team_member_repo.expect_get().never();
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo
+            .expect_list_coauthors()
+            .returning(|_| Ok(vec![]));
+        mock_mob_repo.expect_get_driver().returning(|_| Ok(None));
+        mock_mob_repo.expect_clear().once().returning(|_| Ok(()));
+        mock_mob_repo
+            .expect_add_coauthor()
+            .with(
+                predicate::eq(MobSessionScope::Global),
+                predicate::eq(coauthor),
+            )
+            .once()
+            .returning(|_, _| Ok(()));
+        let mut mock_history_repo = MockHistoryRepo::new();
+        mock_history_repo
+            .expect_append()
+            .once()
+            .returning(|_, _, _| Ok(()));
+
+        let mob_cmd = Mob {
+            with: Some(vec![" John Doe <john.doe@example.com> ".into()]),
+            clear: false,
+            list: false,
+            trailers: false,
+            add: None,
+            local: false,
+            expire_after: None,
+            allow_unusual: false,
+            verbose: false,
+            format: OutputFormat::Text,
+            driver: None,
+        };
+
+        let mut out = Vec::new();
+        mob_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &mock_mob_repo,
+            &MockPresetRepo::new(),
+            &mock_history_repo,
+            &mut out,
+        )?;
+
+        assert_eq!(out, format!("{coauthor}\n").as_bytes());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mob_with_given_team_group_and_exclusion() -> Result<()> {
+        let coauthors = [
+            "Emi Martinez <emi.martinez@example.com>",
+            "Sam Alves <sam.alves@example.com>",
+        ];
+
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo
+            .expect_list_aliases()
+            .returning(|| Ok(vec![]));
+        mock_team_member_repo
+            .expect_get()
+            .returning(move |key| match key {
+                "lm" => Ok(Some(TeamMember::parse(
+                    key,
+                    "Leo Messi <leo.messi@example.com>",
+                )?)),
+                "em" => Ok(Some(TeamMember::parse(key, coauthors[0])?)),
+                "sa" => Ok(Some(TeamMember::parse(key, coauthors[1])?)),
+                _ => Ok(None),
+            });
+        let mut mock_team_group_repo = MockTeamGroupRepo::new();
+        mock_team_group_repo
+            .expect_get()
+            .with(predicate::eq("frontend"))
+            .once()
+            .returning(|name| {
+                Ok(Some(TeamGroup::new(
+                    name,
+                    &["lm".into(), "em".into(), "sa".into()],
+                )?))
+            });
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo
+            .expect_list_coauthors()
+            .returning(|_| Ok(vec![]));
+        mock_mob_repo.expect_get_driver().returning(|_| Ok(None));
+        mock_mob_repo.expect_clear().once().returning(|_| Ok(()));
+        mock_mob_repo
+            .expect_add_coauthor()
+            .withf(move |scope, coauthor| {
+                *scope == MobSessionScope::Global && coauthors.contains(&coauthor)
+            })
+            .times(2)
+            .returning(|_, _| Ok(()));
+        let mut mock_history_repo = MockHistoryRepo::new();
+        mock_history_repo
+            .expect_append()
+            .withf(move |_, event, members| *event == HistoryEvent::Start && members == coauthors)
+            .once()
+            .returning(|_, _, _| Ok(()));
+
+        let mob_cmd = Mob {
+            with: Some(vec!["@frontend".into(), "sa".into(), "-lm".into()]),
+            clear: false,
+            list: false,
+            trailers: false,
+            add: None,
+            local: false,
+            expire_after: None,
+            allow_unusual: false,
+            verbose: false,
+            format: OutputFormat::Text,
+            driver: None,
+        };
+
+        let mut out = Vec::new();
+        mob_cmd.handle(
+            &mock_team_member_repo,
+            &mock_team_group_repo,
+            &mock_mob_repo,
+            &MockPresetRepo::new(),
+            &mock_history_repo,
+            &mut out,
+        )?;
+
+        assert_eq!(out, format!("{}\n", coauthors.join("\n")).as_bytes());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mob_with_given_team_group_with_missing_team_member() -> Result<()> {
+        let coauthors = [
+            "Leo Messi <leo.messi@example.com>",
+            "Emi Martinez <emi.martinez@example.com>",
+        ];
+
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo
+            .expect_list_aliases()
+            .returning(|| Ok(vec![]));
+        mock_team_member_repo
+            .expect_get()
+            .returning(move |key| match key {
+                "lm" => Ok(Some(TeamMember::parse(key, coauthors[0])?)),
+                "em" => Ok(Some(TeamMember::parse(key, coauthors[1])?)),
+                _ => Ok(None),
+            });
+        let mut mock_team_group_repo = MockTeamGroupRepo::new();
+        mock_team_group_repo
+            .expect_get()
+            .with(predicate::eq("frontend"))
+            .once()
+            .returning(|name| {
+                Ok(Some(TeamGroup::new(
+                    name,
+                    &["lm".into(), "sa".into(), "em".into()],
+                )?))
+            });
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo
+            .expect_list_coauthors()
+            .returning(|_| Ok(vec![]));
+        mock_mob_repo.expect_get_driver().returning(|_| Ok(None));
+        mock_mob_repo.expect_clear().once().returning(|_| Ok(()));
+        mock_mob_repo
+            .expect_add_coauthor()
+            .withf(move |scope, coauthor| {
+                *scope == MobSessionScope::Global && coauthors.contains(&coauthor)
+            })
+            .times(2)
+            .returning(|_, _| Ok(()));
+        let mut mock_history_repo = MockHistoryRepo::new();
+        mock_history_repo
+            .expect_append()
+            .once()
+            .returning(|_, _, _| Ok(()));
+
+        let mob_cmd = Mob {
+            with: Some(vec!["@frontend".into()]),
+            clear: false,
+            list: false,
+            trailers: false,
+            add: None,
+            local: false,
+            expire_after: None,
+            allow_unusual: false,
+            verbose: false,
+            format: OutputFormat::Text,
+            driver: None,
+        };
+
+        let mut out = Vec::new();
+        mob_cmd.handle(
+            &mock_team_member_repo,
+            &mock_team_group_repo,
+            &mock_mob_repo,
+            &MockPresetRepo::new(),
+            &mock_history_repo,
+            &mut out,
+        )?;
+
+        assert_eq!(out, format!("{}\n", coauthors.join("\n")).as_bytes());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mob_with_given_aliases() -> Result<()> {
+        let coauthor = "Leo Messi <leo.messi@example.com>";
+
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo.expect_list_aliases().returning(|| {
+            Ok(vec![
+                ("leo".to_owned(), "lm".to_owned()),
+                ("dibu".to_owned(), "em".to_owned()),
+            ])
+        });
+        mock_team_member_repo
+            .expect_get()
+            .returning(move |key| match key {
+                "lm" => Ok(Some(TeamMember::parse(key, coauthor)?)),
+                "em" => Ok(Some(TeamMember::parse(
+                    key,
+                    "Emi Martinez <emi.martinez@example.com>",
+                )?)),
+                _ => Ok(None),
+            });
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo
+            .expect_list_coauthors()
+            .returning(|_| Ok(vec![]));
+        mock_mob_repo.expect_get_driver().returning(|_| Ok(None));
+        mock_mob_repo.expect_clear().once().returning(|_| Ok(()));
+        mock_mob_repo
+            .expect_add_coauthor()
+            .with(
+                predicate::eq(MobSessionScope::Global),
+                predicate::eq(coauthor),
+            )
+            .once()
+            .returning(|_, _| Ok(()));
+        let mut mock_history_repo = MockHistoryRepo::new();
+        mock_history_repo
+            .expect_append()
+            .once()
+            .returning(|_, _, _| Ok(()));
+
+        // leo and lm are the same team member and dibu excludes em
+        let mob_cmd = Mob {
+            with: Some(vec!["leo".into(), "lm".into(), "em".into(), "-dibu".into()]),
+            clear: false,
+            list: false,
+            trailers: false,
+            add: None,
+            local: false,
+            expire_after: None,
+            allow_unusual: false,
+            verbose: false,
+            format: OutputFormat::Text,
+            driver: None,
+        };
+
+        let mut out = Vec::new();
+        mob_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &mock_mob_repo,
+            &MockPresetRepo::new(),
+            &mock_history_repo,
+            &mut out,
+        )?;
+
+        assert_eq!(out, format!("{coauthor}\n").as_bytes());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mob_with_when_team_group_not_found() -> Result<()> {
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo
+            .expect_list_aliases()
+            .returning(|| Ok(vec![]));
+        let mut mock_team_group_repo = MockTeamGroupRepo::new();
+        mock_team_group_repo
+            .expect_get()
+            .with(predicate::eq("frontend"))
+            .once()
+            .returning(|_| Ok(None));
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo.expect_clear().never();
+
+        let mob_cmd = Mob {
+            with: Some(vec!["@frontend".into()]),
+            clear: false,
+            list: false,
+            trailers: false,
+            add: None,
+            local: false,
+            expire_after: None,
+            allow_unusual: false,
+            verbose: false,
+            format: OutputFormat::Text,
+            driver: None,
+        };
+
+        let mut out = Vec::new();
+        let result = mob_cmd.handle(
+            &mock_team_member_repo,
+            &mock_team_group_repo,
+            &mock_mob_repo,
+            &MockPresetRepo::new(),
+            &MockHistoryRepo::new(),
+            &mut out,
+        );
+
+        assert!(
+            result.is_err_and(|err| err.to_string() == "No team group found with name: frontend")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mob_with_when_excluded_team_member_not_found() -> Result<()> {
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo
+            .expect_list_aliases()
+            .returning(|| Ok(vec![]));
+        mock_team_member_repo
+            .expect_get()
+            .returning(|key| match key {
+                "em" => Ok(Some(TeamMember::parse(
+                    key,
+                    "Emi Martinez <emi.martinez@example.com>",
+                )?)),
+                _ => Ok(None),
+            });
+        mock_team_member_repo
+            .expect_list()
+            .once()
+            .returning(create_team_members);
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo.expect_clear().never();
+
+        let mob_cmd = Mob {
+            with: Some(vec!["em".into(), "-me".into()]),
+            clear: false,
+            list: false,
+            trailers: false,
+            add: None,
+            local: false,
+            expire_after: None,
+            allow_unusual: false,
+            verbose: false,
+            format: OutputFormat::Text,
+            driver: None,
+        };
+
+        let mut out = Vec::new();
+        let result = mob_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &mock_mob_repo,
+            &MockPresetRepo::new(),
+            &MockHistoryRepo::new(),
+            &mut out,
+        );
+
+        assert!(result.is_err_and(|err| err.to_string()
+            == "No team member found with key: me. Did you mean em (Emi Martinez <emi.martinez@example.com>)?"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_coauthor() -> Result<()> {
+        let name = "Leo Messi";
         let email = "leo.messi@example.com";
 
         let mock_team_member_repo = MockTeamMemberRepo::new();
         let mut mock_mob_repo = MockMobSessionRepo::new();
         mock_mob_repo
             .expect_add_coauthor()
-            .with(predicate::eq(format!("{name} <{email}>")))
+            .with(
+                predicate::eq(MobSessionScope::Global),
+                predicate::eq(format!("{name} <{email}>")),
+            )
+            .once()
+            .returning(|_, _| Ok(()));
+
+        let mob_cmd = Mob {
+            add: Some(vec![name.to_owned(), email.to_owned()]),
+            with: None,
+            clear: false,
+            list: false,
+            trailers: false,
+            local: false,
+            expire_after: None,
+            allow_unusual: false,
+            verbose: false,
+            format: OutputFormat::Text,
+            driver: None,
+        };
+
+        let mut mock_history_repo = MockHistoryRepo::new();
+        mock_history_repo
+            .expect_append()
+            .withf(move |scope, event, members| {
+                *scope == MobSessionScope::Global
+                    && *event == HistoryEvent::Add
+                    && members == [format!("{name} <{email}>")]
+            })
+            .once()
+            .returning(|_, _, _| Ok(()));
+        let mut out = Vec::new();
+        mob_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &mock_mob_repo,
+            &MockPresetRepo::new(),
+            &mock_history_repo,
+            &mut out,
+        )?;
+
+        assert_eq!(out, format!("{name} <{email}>\n").as_bytes());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_coauthor_when_email_unusual() -> Result<()> {
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo.expect_add_coauthor().never();
+
+        let mob_cmd = Mob {
+            add: Some(vec!["Leo Messi".to_owned(), "leo@localhost".to_owned()]),
+            with: None,
+            clear: false,
+            list: false,
+            trailers: false,
+            local: false,
+            expire_after: None,
+            allow_unusual: false,
+            verbose: false,
+            format: OutputFormat::Text,
+            driver: None,
+        };
+
+        let mut out = Vec::new();
+        let result = mob_cmd.handle(
+            &MockTeamMemberRepo::new(),
+            &MockTeamGroupRepo::new(),
+            &mock_mob_repo,
+            &MockPresetRepo::new(),
+            &MockHistoryRepo::new(),
+            &mut out,
+        );
+
+        assert!(result.is_err_and(|err| {
+            err.to_string()
+                .ends_with("Use --allow-unusual if it is intended")
+        }));
+        assert!(out.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_coauthor_given_allow_unusual() -> Result<()> {
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo
+            .expect_add_coauthor()
+            .with(
+                predicate::eq(MobSessionScope::Global),
+                predicate::eq("Leo Messi <leo@localhost>"),
+            )
+            .once()
+            .returning(|_, _| Ok(()));
+        let mut mock_history_repo = MockHistoryRepo::new();
+        mock_history_repo
+            .expect_append()
+            .once()
+            .returning(|_, _, _| Ok(()));
+
+        let mob_cmd = Mob {
+            add: Some(vec![" Leo Messi ".to_owned(), "leo@localhost".to_owned()]),
+            with: None,
+            clear: false,
+            list: false,
+            trailers: false,
+            local: false,
+            expire_after: None,
+            allow_unusual: true,
+            verbose: false,
+            format: OutputFormat::Text,
+            driver: None,
+        };
+
+        let mut out = Vec::new();
+        mob_cmd.handle(
+            &MockTeamMemberRepo::new(),
+            &MockTeamGroupRepo::new(),
+            &mock_mob_repo,
+            &MockPresetRepo::new(),
+            &mock_history_repo,
+            &mut out,
+        )?;
+
+        assert_eq!(out, b"Leo Messi <leo@localhost>\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_mob_given_local_session() -> Result<()> {
+        let mock_team_member_repo = MockTeamMemberRepo::new();
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo
+            .expect_expired_at()
+            .times(2)
+            .returning(|_| Ok(None));
+        mock_mob_repo
+            .expect_list_active_coauthors()
+            .once()
+            .returning(move || {
+                Ok((
+                    MobSessionScope::Local,
+                    vec!["Leo Messi <leo.messi@example.com>".to_owned()],
+                ))
+            });
+        mock_mob_repo.expect_get_driver().returning(|_| Ok(None));
+
+        let mob_cmd = Mob {
+            list: true,
+            clear: false,
+            with: None,
+            trailers: false,
+            add: None,
+            local: false,
+            expire_after: None,
+            allow_unusual: false,
+            verbose: true,
+            format: OutputFormat::Text,
+            driver: None,
+        };
+
+        let mock_history_repo = MockHistoryRepo::new();
+        let mut out = Vec::new();
+        mob_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &mock_mob_repo,
+            &MockPresetRepo::new(),
+            &mock_history_repo,
+            &mut out,
+        )?;
+
+        assert_eq!(
+            out,
+            b"Mob session (local):\nLeo Messi <leo.messi@example.com>\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mob_with_by_keys_given_local_scope() -> Result<()> {
+        let key = "lm";
+        let coauthor = "Leo Messi <leo.messi@example.com>";
+
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo
+            .expect_list_aliases()
+            .returning(|| Ok(vec![]));
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo
+            .expect_list_coauthors()
+            .returning(|_| Ok(vec![]));
+        mock_mob_repo.expect_get_driver().returning(|_| Ok(None));
+        mock_mob_repo
+            .expect_clear()
+            .with(predicate::eq(MobSessionScope::Local))
+            .once()
+            .returning(|_| Ok(()));
+        mock_team_member_repo
+            .expect_get()
+            .with(predicate::eq(key))
+            .once()
+            .returning(move |key| Ok(Some(TeamMember::parse(key, coauthor)?)));
+        mock_mob_repo
+            .expect_add_coauthor()
+            .with(
+                predicate::eq(MobSessionScope::Local),
+                predicate::eq(coauthor),
+            )
+            .once()
+            .returning(|_, _| Ok(()));
+
+        let mob_cmd = Mob {
+            with: Some(vec![key.to_owned()]),
+            clear: false,
+            list: false,
+            trailers: false,
+            add: None,
+            local: true,
+            expire_after: None,
+            allow_unusual: false,
+            verbose: false,
+            format: OutputFormat::Text,
+            driver: None,
+        };
+
+        let mut mock_history_repo = MockHistoryRepo::new();
+        mock_history_repo
+            .expect_append()
+            .withf(move |scope, event, members| {
+                *scope == MobSessionScope::Local
+                    && *event == HistoryEvent::Start
+                    && members == [coauthor]
+            })
+            .once()
+            .returning(|_, _, _| Ok(()));
+        let mut out = Vec::new();
+        mob_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &mock_mob_repo,
+            &MockPresetRepo::new(),
+            &mock_history_repo,
+            &mut out,
+        )?;
+
+        assert_eq!(out, format!("{coauthor}\n").as_bytes());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_mob_when_mob_session_expired() -> Result<()> {
+        let expired_at = Local.with_ymd_and_hms(2025, 6, 2, 0, 0, 0).unwrap();
+
+        let mock_team_member_repo = MockTeamMemberRepo::new();
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo
+            .expect_expired_at()
+            .with(predicate::eq(MobSessionScope::Local))
+            .once()
+            .returning(|_| Ok(None));
+        mock_mob_repo
+            .expect_expired_at()
+            .with(predicate::eq(MobSessionScope::Global))
+            .once()
+            .returning(move |_| Ok(Some(expired_at)));
+        mock_mob_repo
+            .expect_list_active_coauthors()
+            .once()
+            .returning(move || Ok((MobSessionScope::Global, vec![])));
+        mock_mob_repo.expect_get_driver().returning(|_| Ok(None));
+
+        let mut mock_preset_repo = MockPresetRepo::new();
+        mock_preset_repo
+            .expect_get_default()
+            .once()
+            .returning(|| Ok(None));
+
+        let mob_cmd = Mob {
+            list: true,
+            clear: false,
+            with: None,
+            trailers: false,
+            add: None,
+            local: false,
+            expire_after: None,
+            allow_unusual: false,
+            verbose: true,
+            format: OutputFormat::Text,
+            driver: None,
+        };
+
+        let mock_history_repo = MockHistoryRepo::new();
+        let mut out = Vec::new();
+        mob_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &mock_mob_repo,
+            &mock_preset_repo,
+            &mock_history_repo,
+            &mut out,
+        )?;
+
+        assert_eq!(out, b"Mob session (global) expired at 2025-06-02 00:00\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mob_expire_after() -> Result<()> {
+        let mock_team_member_repo = MockTeamMemberRepo::new();
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo
+            .expect_set_expire_after()
+            .with(
+                predicate::eq(MobSessionScope::Local),
+                predicate::eq(SessionExpiry::EndOfDay),
+            )
+            .once()
+            .returning(|_, _| Ok(()));
+
+        let mob_cmd = Mob {
+            list: false,
+            clear: false,
+            with: None,
+            trailers: false,
+            add: None,
+            local: true,
+            expire_after: Some(SessionExpiry::EndOfDay),
+            allow_unusual: false,
+            verbose: false,
+            format: OutputFormat::Text,
+            driver: None,
+        };
+
+        let mock_history_repo = MockHistoryRepo::new();
+        let mut out = Vec::new();
+        mob_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &mock_mob_repo,
+            &MockPresetRepo::new(),
+            &mock_history_repo,
+            &mut out,
+        )?;
+
+        assert!(out.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_mob_given_json_format() -> Result<()> {
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo.expect_list().once().returning(|| {
+            Ok(vec![
+                TeamMember::new("lm", "Leo Messi", "leo.messi@example.com")?
+                    .with_source(TeamMemberSource::User),
+            ])
+        });
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo.expect_expired_at().never();
+        mock_mob_repo
+            .expect_list_active_coauthors()
+            .once()
+            .returning(|| {
+                Ok((
+                    MobSessionScope::Local,
+                    vec!["John Doe <john.doe@example.com>".to_owned()],
+                ))
+            });
+        mock_mob_repo
+            .expect_get_driver()
+            .with(predicate::eq(MobSessionScope::Local))
+            .once()
+            .returning(|_| Ok(Some("Leo Messi <Leo.Messi@example.com>".to_owned())));
+
+        let mob_cmd = Mob {
+            list: true,
+            clear: false,
+            with: None,
+            trailers: false,
+            add: None,
+            local: false,
+            expire_after: None,
+            allow_unusual: false,
+            verbose: false,
+            format: OutputFormat::Json,
+            driver: None,
+        };
+
+        let mut out = Vec::new();
+        mob_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &mock_mob_repo,
+            &MockPresetRepo::new(),
+            &MockHistoryRepo::new(),
+            &mut out,
+        )?;
+
+        let document: serde_json::Value = serde_json::from_slice(&out)?;
+        assert_eq!(
+            document,
+            serde_json::json!({
+                "version": 1,
+                "members": [
+                    {
+                        "role": "driver",
+                        "key": "lm",
+                        "name": "Leo Messi",
+                        "email": "Leo.Messi@example.com",
+                        "scope": "local",
+                        "source": "user"
+                    },
+                    {
+                        "role": "coauthor",
+                        "key": null,
+                        "name": "John Doe",
+                        "email": "john.doe@example.com",
+                        "scope": "local",
+                        "source": null
+                    }
+                ]
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mob_coauthor_trailers_given_tsv_format() -> Result<()> {
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo
+            .expect_list()
+            .once()
+            .returning(create_team_members);
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo
+            .expect_list_active_coauthors()
+            .once()
+            .returning(|| {
+                Ok((
+                    MobSessionScope::Global,
+                    vec!["Emi Martinez <emi.martinez@example.com>".to_owned()],
+                ))
+            });
+        mock_mob_repo
+            .expect_get_author_email()
+            .once()
+            .returning(|| Ok(None));
+
+        let mob_cmd = Mob {
+            list: false,
+            clear: false,
+            with: None,
+            trailers: true,
+            add: None,
+            local: false,
+            expire_after: None,
+            allow_unusual: false,
+            verbose: false,
+            format: OutputFormat::Tsv,
+            driver: None,
+        };
+
+        let mut out = Vec::new();
+        mob_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &mock_mob_repo,
+            &MockPresetRepo::new(),
+            &MockHistoryRepo::new(),
+            &mut out,
+        )?;
+
+        assert_eq!(
+            String::from_utf8(out)?,
+            "key\tname\temail\tscope\tsource\ttrailer\n\
+             em\tEmi Martinez\temi.martinez@example.com\tglobal\t\tCo-authored-by: Emi Martinez <emi.martinez@example.com>\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mob_list_and_trailers_given_json_format() -> Result<()> {
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo.expect_set_expire_after().never();
+        mock_mob_repo.expect_list_active_coauthors().never();
+
+        let mob_cmd = Mob {
+            list: true,
+            clear: false,
+            with: None,
+            trailers: true,
+            add: None,
+            local: false,
+            expire_after: Some(SessionExpiry::EndOfDay),
+            allow_unusual: false,
+            verbose: false,
+            format: OutputFormat::Json,
+            driver: None,
+        };
+
+        let mut out = Vec::new();
+        let result = mob_cmd.handle(
+            &MockTeamMemberRepo::new(),
+            &MockTeamGroupRepo::new(),
+            &mock_mob_repo,
+            &MockPresetRepo::new(),
+            &MockHistoryRepo::new(),
+            &mut out,
+        );
+
+        assert!(result.is_err_and(|err| err.to_string()
+            == "--list and --trailers can only be used together with --format text"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_mob_given_driver() -> Result<()> {
+        let mock_team_member_repo = MockTeamMemberRepo::new();
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo
+            .expect_expired_at()
+            .times(2)
+            .returning(|_| Ok(None));
+        mock_mob_repo
+            .expect_list_active_coauthors()
+            .once()
+            .returning(|| {
+                Ok((
+                    MobSessionScope::Local,
+                    vec!["Emi Martinez <emi.martinez@example.com>".to_owned()],
+                ))
+            });
+        mock_mob_repo
+            .expect_get_driver()
+            .with(predicate::eq(MobSessionScope::Local))
+            .once()
+            .returning(|_| Ok(Some("Leo Messi <leo.messi@example.com>".to_owned())));
+
+        let mob_cmd = Mob {
+            list: true,
+            clear: false,
+            with: None,
+            trailers: false,
+            add: None,
+            local: false,
+            expire_after: None,
+            allow_unusual: false,
+            verbose: true,
+            format: OutputFormat::Text,
+            driver: None,
+        };
+
+        let mock_history_repo = MockHistoryRepo::new();
+        let mut out = Vec::new();
+        mob_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &mock_mob_repo,
+            &MockPresetRepo::new(),
+            &mock_history_repo,
+            &mut out,
+        )?;
+
+        assert_eq!(
+            out,
+            b"Mob session (local):\n\
+              Driver: Leo Messi <leo.messi@example.com>\n\
+              Navigator: Emi Martinez <emi.martinez@example.com>\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_mob_given_driver_without_verbose() -> Result<()> {
+        let mock_team_member_repo = MockTeamMemberRepo::new();
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo.expect_expired_at().never();
+        mock_mob_repo
+            .expect_list_active_coauthors()
+            .once()
+            .returning(|| {
+                Ok((
+                    MobSessionScope::Local,
+                    vec!["Emi Martinez <emi.martinez@example.com>".to_owned()],
+                ))
+            });
+        mock_mob_repo.expect_get_driver().never();
+
+        let mob_cmd = Mob {
+            list: true,
+            clear: false,
+            with: None,
+            trailers: false,
+            add: None,
+            local: false,
+            expire_after: None,
+            allow_unusual: false,
+            verbose: false,
+            format: OutputFormat::Text,
+            driver: None,
+        };
+
+        let mock_history_repo = MockHistoryRepo::new();
+        let mut out = Vec::new();
+        mob_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &mock_mob_repo,
+            &MockPresetRepo::new(),
+            &mock_history_repo,
+            &mut out,
+        )?;
+
+        assert_eq!(out, b"Emi Martinez <emi.martinez@example.com>\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mob_driver() -> Result<()> {
+        let driver = TeamMember::new("lm", "Leo Messi", "leo.messi@example.com")?;
+        let previous_driver = "Emi Martinez <emi.martinez@example.com>";
+
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo
+            .expect_list_aliases()
+            .returning(|| Ok(vec![]));
+        let cloned_driver = driver.clone();
+        mock_team_member_repo
+            .expect_get()
+            .with(predicate::eq("lm"))
+            .once()
+            .returning(move |_| Ok(Some(cloned_driver.clone())));
+
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo
+            .expect_get_driver()
+            .with(predicate::eq(MobSessionScope::Global))
+            .once()
+            .returning(|_| Ok(Some(previous_driver.to_owned())));
+        mock_mob_repo
+            .expect_list_coauthors()
+            .with(predicate::eq(MobSessionScope::Global))
+            .once()
+            .returning(|_| {
+                Ok(vec![
+                    "Leo Messi <leo.messi@example.com>".to_owned(),
+                    "Angel Di Maria <angel.dimaria@example.com>".to_owned(),
+                ])
+            });
+        mock_mob_repo
+            .expect_set_driver()
+            .with(
+                predicate::eq(MobSessionScope::Global),
+                predicate::eq(driver.clone()),
+            )
+            .once()
+            .returning(|_, _| Ok(()));
+        mock_mob_repo
+            .expect_remove_coauthor()
+            .with(
+                predicate::eq(MobSessionScope::Global),
+                predicate::eq("Leo Messi <leo.messi@example.com>"),
+            )
+            .once()
+            .returning(|_, _| Ok(()));
+        mock_mob_repo
+            .expect_add_coauthor()
+            .with(
+                predicate::eq(MobSessionScope::Global),
+                predicate::eq(previous_driver),
+            )
             .once()
-            .returning(|_| Ok(()));
+            .returning(|_, _| Ok(()));
 
         let mob_cmd = Mob {
-            add: Some(vec![name.to_owned(), email.to_owned()]),
+            list: false,
+            clear: false,
             with: None,
+            trailers: false,
+            add: None,
+            local: false,
+            expire_after: None,
+            allow_unusual: false,
+            verbose: false,
+            format: OutputFormat::Text,
+            driver: Some("lm".to_owned()),
+        };
+
+        let mock_history_repo = MockHistoryRepo::new();
+        let mut out = Vec::new();
+        mob_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &mock_mob_repo,
+            &MockPresetRepo::new(),
+            &mock_history_repo,
+            &mut out,
+        )?;
+
+        assert_eq!(out, b"Driver: Leo Messi <leo.messi@example.com>\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mob_driver_by_name_given_with() -> Result<()> {
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo
+            .expect_list_aliases()
+            .returning(|| Ok(vec![]));
+        mock_team_member_repo
+            .expect_get()
+            .returning(|key| Ok(create_team_members()?.into_iter().find(|x| x.key == key)));
+        mock_team_member_repo
+            .expect_list()
+            .returning(create_team_members);
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo
+            .expect_list_coauthors()
+            .with(predicate::eq(MobSessionScope::Global))
+            .returning(|_| Ok(vec![]));
+        mock_mob_repo.expect_get_driver().returning(|_| Ok(None));
+        mock_mob_repo
+            .expect_clear()
+            .with(predicate::eq(MobSessionScope::Global))
+            .once()
+            .returning(|_| Ok(()));
+        mock_mob_repo
+            .expect_add_coauthor()
+            .with(
+                predicate::eq(MobSessionScope::Global),
+                predicate::eq("Emi Martinez <emi.martinez@example.com>"),
+            )
+            .once()
+            .returning(|_, _| Ok(()));
+        mock_mob_repo
+            .expect_set_driver()
+            .withf(|scope, driver| *scope == MobSessionScope::Global && driver.key == "leo")
+            .once()
+            .returning(|_, _| Ok(()));
+        let mut mock_history_repo = MockHistoryRepo::new();
+        mock_history_repo
+            .expect_append()
+            .withf(|_, event, members| {
+                *event == HistoryEvent::Start
+                    && members == ["Emi Martinez <emi.martinez@example.com>"]
+            })
+            .once()
+            .returning(|_, _, _| Ok(()));
+
+        // the driver is skipped from the co-author(s) although they are given by name
+        let mob_cmd = Mob {
+            list: false,
             clear: false,
+            with: Some(vec!["leo".to_owned(), "em".to_owned()]),
+            trailers: false,
+            add: None,
+            local: false,
+            expire_after: None,
+            allow_unusual: false,
+            verbose: false,
+            format: OutputFormat::Text,
+            driver: Some("leo messi".to_owned()),
+        };
+
+        let mut out = Vec::new();
+        mob_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &mock_mob_repo,
+            &MockPresetRepo::new(),
+            &mock_history_repo,
+            &mut out,
+        )?;
+
+        assert_eq!(
+            String::from_utf8(out)?,
+            "Emi Martinez <emi.martinez@example.com>\nDriver: Leo Messi <leo.messi@example.com>\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mob_driver_when_team_member_not_found() -> Result<()> {
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo
+            .expect_list_aliases()
+            .returning(|| Ok(vec![]));
+        mock_team_member_repo.expect_get().returning(|_| Ok(None));
+        mock_team_member_repo
+            .expect_list()
+            .once()
+            .returning(create_team_members);
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo.expect_clear().never();
+        mock_mob_repo.expect_add_coauthor().never();
+        mock_mob_repo.expect_set_driver().never();
+        let mut mock_history_repo = MockHistoryRepo::new();
+        mock_history_repo.expect_append().never();
+
+        let mob_cmd = Mob {
             list: false,
+            clear: true,
+            with: Some(vec!["em".to_owned()]),
             trailers: false,
+            add: None,
+            local: false,
+            expire_after: None,
+            allow_unusual: false,
+            verbose: false,
+            format: OutputFormat::Text,
+            driver: Some("lep".to_owned()),
         };
 
         let mut out = Vec::new();
-        mob_cmd.handle(&mock_team_member_repo, &mock_mob_repo, &mut out)?;
+        let result = mob_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &mock_mob_repo,
+            &MockPresetRepo::new(),
+            &mock_history_repo,
+            &mut out,
+        );
 
-        assert_eq!(out, format!("{name} <{email}>\n").as_bytes());
+        assert!(result.is_err_and(|err| err.to_string()
+            == "No team member found with key: lep. Did you mean leo (Leo Messi <leo.messi@example.com>)?"));
 
         Ok(())
     }
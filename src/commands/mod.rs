@@ -1,7 +1,28 @@
+pub(crate) mod history;
+pub(crate) mod hook;
+pub(crate) mod importers;
 pub(crate) mod mob;
+pub(crate) mod output_format;
+pub(crate) mod pingpong;
+pub(crate) mod preset;
+pub(crate) mod roster_format;
+pub(crate) mod schedule;
 pub(crate) mod setup;
+pub(crate) mod stats;
+pub(crate) mod suggest_pair;
+pub(crate) mod team;
 pub(crate) mod team_member;
+pub(crate) mod timer;
 
+pub(crate) use history::History;
+pub(crate) use hook::Hook;
 pub(crate) use mob::Mob;
+pub(crate) use pingpong::Pingpong;
+pub(crate) use preset::Preset;
+pub(crate) use schedule::Schedule;
 pub(crate) use setup::Setup;
+pub(crate) use stats::Stats;
+pub(crate) use suggest_pair::SuggestPair;
+pub(crate) use team::Team;
 pub(crate) use team_member::TeamMember;
+pub(crate) use timer::Timer;
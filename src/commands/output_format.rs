@@ -0,0 +1,258 @@
+//! Output formats of the listing commands (git mob --list, --trailers, team-member --list,
+//! history, stats and schedule)
+//!
+//! The JSON documents are described by the schema in docs/output_schema.json. Their version is
+//! increased whenever a field is removed or changes meaning, while new fields can be added to
+//! the same version.
+
+use crate::Result;
+use clap::ValueEnum;
+use serde::Serialize;
+use std::io::Write;
+
+/// Version of the JSON documents of the listing commands
+pub(crate) const JSON_VERSION: u32 = 1;
+
+/// Output format of the listing commands
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub(crate) enum OutputFormat {
+    /// Human readable lines, which may change between versions
+    #[default]
+    Text,
+    /// Versioned JSON document described in docs/output_schema.json
+    Json,
+    /// Tab separated values with a header row
+    Tsv,
+    /// Aligned columns with a header row
+    Table,
+    /// Comma separated values with a header row, quoted where needed
+    Csv,
+    /// Markdown table
+    Markdown,
+}
+
+/// Entry of a listing which has the same fields in every structured format
+pub(crate) trait Record: Serialize {
+    /// Names of the columns of the tabular formats, which are also the JSON field names
+    const COLUMNS: &'static [&'static str];
+
+    /// Cells in the order of the columns, empty for missing values
+    fn cells(&self) -> Vec<String>;
+}
+
+impl OutputFormat {
+    /// Writes the records as a JSON document which has them under the given field, or as rows
+    /// of the tabular formats. The text format differs between commands so they write it
+    /// themselves, and it falls back to the table here.
+    pub(crate) fn write_records<R: Record>(
+        self,
+        out: &mut impl Write,
+        field: &str,
+        records: &[R],
+    ) -> Result<()> {
+        if self == OutputFormat::Json {
+            let mut fields = serde_json::Map::new();
+            fields.insert(field.to_owned(), serde_json::to_value(records)?);
+            return write_json(out, &fields);
+        }
+
+        let header = match self {
+            OutputFormat::Text | OutputFormat::Table => {
+                R::COLUMNS.iter().map(|x| x.to_uppercase()).collect()
+            }
+            _ => R::COLUMNS.iter().map(|x| x.to_string()).collect(),
+        };
+        let mut rows = vec![header];
+        rows.extend(records.iter().map(|x| x.cells()));
+        self.write_rows(out, &rows)
+    }
+
+    /// Writes the rows, the first of which is the header, in the tabular format. The text and
+    /// JSON formats fall back to the table as they differ between commands.
+    pub(crate) fn write_rows(self, out: &mut impl Write, rows: &[Vec<String>]) -> Result<()> {
+        match self {
+            OutputFormat::Tsv => {
+                for row in rows {
+                    writeln!(out, "{}", row.join("\t"))?;
+                }
+            }
+            OutputFormat::Csv => {
+                let mut writer = csv::WriterBuilder::new().flexible(true).from_writer(vec![]);
+                for row in rows {
+                    writer.write_record(row)?;
+                }
+                out.write_all(&writer.into_inner()?)?;
+            }
+            OutputFormat::Markdown => {
+                let escape = |row: &[String]| -> Vec<String> {
+                    row.iter().map(|x| x.replace('|', "\\|")).collect()
+                };
+                let mut rows = rows.iter();
+                if let Some(header) = rows.next() {
+                    writeln!(out, "| {} |", escape(header).join(" | "))?;
+                    writeln!(out, "|{}", " --- |".repeat(header.len()))?;
+                }
+                for row in rows {
+                    writeln!(out, "| {} |", escape(row).join(" | "))?;
+                }
+            }
+            OutputFormat::Text | OutputFormat::Json | OutputFormat::Table => {
+                write_table(out, rows)?
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes the fields as a JSON document with its version
+pub(crate) fn write_json(out: &mut impl Write, fields: &impl Serialize) -> Result<()> {
+    let mut document = serde_json::Map::new();
+    document.insert("version".to_owned(), JSON_VERSION.into());
+    match serde_json::to_value(fields)? {
+        serde_json::Value::Object(fields) => document.extend(fields),
+        _ => return Err("JSON document must be an object".into()),
+    }
+    writeln!(out, "{}", serde_json::to_string_pretty(&document)?)?;
+
+    Ok(())
+}
+
+/// Writes the rows as columns aligned to their widest cell
+pub(crate) fn write_table(out: &mut impl Write, rows: &[Vec<String>]) -> Result<()> {
+    let mut widths: Vec<usize> = Vec::new();
+    for row in rows {
+        widths.resize(widths.len().max(row.len()), 0);
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    for row in rows {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        writeln!(out, "{}", line.trim_end())?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct TestRecord {
+        key: &'static str,
+        aliases: Vec<&'static str>,
+    }
+
+    impl Record for TestRecord {
+        const COLUMNS: &'static [&'static str] = &["key", "aliases"];
+
+        fn cells(&self) -> Vec<String> {
+            vec![self.key.to_owned(), self.aliases.join(",")]
+        }
+    }
+
+    fn create_records() -> Vec<TestRecord> {
+        vec![
+            TestRecord {
+                key: "lm",
+                aliases: vec!["leo", "messi"],
+            },
+            TestRecord {
+                key: "em",
+                aliases: vec![],
+            },
+        ]
+    }
+
+    #[test]
+    fn test_write_records_given_json_format() -> Result<()> {
+        let mut out = Vec::new();
+        OutputFormat::Json.write_records(&mut out, "team_members", &create_records())?;
+
+        let document: serde_json::Value = serde_json::from_slice(&out)?;
+        assert_eq!(
+            document,
+            serde_json::json!({
+                "version": 1,
+                "team_members": [
+                    { "key": "lm", "aliases": ["leo", "messi"] },
+                    { "key": "em", "aliases": [] }
+                ]
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_records_given_tsv_format() -> Result<()> {
+        let mut out = Vec::new();
+        OutputFormat::Tsv.write_records(&mut out, "team_members", &create_records())?;
+
+        assert_eq!(
+            String::from_utf8(out)?,
+            "key\taliases\nlm\tleo,messi\nem\t\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_records_given_table_format() -> Result<()> {
+        let mut out = Vec::new();
+        OutputFormat::Table.write_records(&mut out, "team_members", &create_records())?;
+
+        assert_eq!(
+            String::from_utf8(out)?,
+            "KEY  ALIASES\nlm   leo,messi\nem\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_records_given_csv_format() -> Result<()> {
+        let mut out = Vec::new();
+        OutputFormat::Csv.write_records(&mut out, "team_members", &create_records())?;
+
+        assert_eq!(
+            String::from_utf8(out)?,
+            "key,aliases\nlm,\"leo,messi\"\nem,\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_records_given_markdown_format() -> Result<()> {
+        let mut out = Vec::new();
+        OutputFormat::Markdown.write_records(&mut out, "team_members", &create_records())?;
+
+        assert_eq!(
+            String::from_utf8(out)?,
+            "| key | aliases |\n| --- | --- |\n| lm | leo,messi |\n| em |  |\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_json_schema_version() -> Result<()> {
+        let schema: serde_json::Value =
+            serde_json::from_str(include_str!("../../docs/output_schema.json"))?;
+
+        assert_eq!(
+            schema["properties"]["version"]["const"],
+            serde_json::json!(JSON_VERSION)
+        );
+
+        Ok(())
+    }
+}
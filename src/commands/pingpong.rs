@@ -0,0 +1,173 @@
+use crate::Result;
+use crate::commands::mob::{CoauthorResolver, record_end, record_history};
+use crate::commands::setup::Setup;
+use crate::repositories::{
+    HistoryEvent, HistoryRepo, MobSessionRepo, MobSessionScope, SessionEnd, TeamMemberRepo,
+};
+use clap::Parser;
+use path_clean::PathClean;
+use std::io::Write;
+
+#[derive(Parser)]
+pub(crate) struct Pingpong {
+    /// Team members who take turns to commit, starting with the first one as the driver
+    ///
+    /// Usage example: git mob pingpong lm em
+    #[arg(num_args = 2, value_names = ["DRIVER_KEY", "NAVIGATOR_KEY"], required = true)]
+    pub(crate) keys: Vec<String>,
+    /// Uses the mob/pair programming session of the current repository instead of the global one
+    ///
+    /// Usage example: git mob pingpong lm em --local
+    #[arg(long = "local")]
+    pub(crate) local: bool,
+}
+
+impl Pingpong {
+    pub(crate) fn handle(
+        &self,
+        team_member_repo: &impl TeamMemberRepo,
+        mob_repo: &impl MobSessionRepo,
+        history_repo: &impl HistoryRepo,
+        out: &mut impl Write,
+    ) -> Result<()> {
+        let scope = if self.local {
+            MobSessionScope::Local
+        } else {
+            MobSessionScope::Global
+        };
+
+        let mut resolver = CoauthorResolver::new(team_member_repo, false)?;
+        let team_members = self
+            .keys
+            .iter()
+            .map(|x| resolver.resolve_team_member(x))
+            .collect::<Result<Vec<_>>>()?;
+        let [driver, navigator] = team_members.as_slice() else {
+            return Err("Ping-pong mode needs exactly two team members".into());
+        };
+        if driver.key == navigator.key {
+            return Err("Ping-pong mode needs two different team members".into());
+        }
+
+        Self::check_post_commit_hook()?;
+
+        record_end(mob_repo, history_repo, scope, SessionEnd::Replaced)?;
+        mob_repo.clear(scope)?;
+        mob_repo.add_coauthor(scope, &navigator.to_string())?;
+        mob_repo.set_driver(scope, driver)?;
+        mob_repo.set_rotation(scope, &[driver.key.to_owned(), navigator.key.to_owned()])?;
+        mob_repo.set_pingpong(scope, true)?;
+        record_history(
+            history_repo,
+            scope,
+            HistoryEvent::Start,
+            &[driver.to_string(), navigator.to_string()],
+        );
+
+        writeln!(out, "Ping-pong mode ({scope}):")?;
+        writeln!(out, "Driver: {driver}")?;
+        writeln!(out, "Navigator: {navigator}")?;
+
+        Ok(())
+    }
+
+    /// Checks that the hooks directory which setup uses has a post-commit githook invoking git
+    /// mob. The githook is never created or replaced here as the directory may be tracked by
+    /// the repository or the githook may belong to another tool.
+    fn check_post_commit_hook() -> Result<()> {
+        let (hooks_dir, setup_cmd) = match Setup::get_hooks_dir("--local")? {
+            Some(hooks_dir) => (hooks_dir, "git mob setup --local"),
+            None => match Setup::get_hooks_dir("--global")? {
+                Some(hooks_dir) => (hooks_dir, "git mob setup"),
+                None => return Err("Githooks directory is not set. Run git mob setup first".into()),
+            },
+        };
+
+        let path = hooks_dir.join(Setup::POST_COMMIT_HOOK).clean();
+        if Setup::has_post_commit_hook(&path) {
+            return Ok(());
+        }
+
+        let path_str = path.to_string_lossy();
+        if path.exists() {
+            Err(format!(
+                "Post-commit githook does not invoke git mob: {path_str}. Add `git mob hook post-commit` to it to use ping-pong mode"
+            )
+            .into())
+        } else {
+            Err(format!("Post-commit githook not found: {path_str}. Run {setup_cmd} first").into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::{
+        MockHistoryRepo, MockMobSessionRepo, MockTeamMemberRepo, TeamMember,
+    };
+
+    #[test]
+    fn test_pingpong_given_same_team_member() -> Result<()> {
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo
+            .expect_list_aliases()
+            .returning(|| Ok(vec![]));
+        mock_team_member_repo.expect_get().returning(|key| {
+            Ok(Some(TeamMember::new(
+                key,
+                "Leo Messi",
+                "leo.messi@example.com",
+            )?))
+        });
+        let mock_mob_repo = MockMobSessionRepo::new();
+
+        let pingpong_cmd = Pingpong {
+            keys: vec!["lm".to_owned(), "lm".to_owned()],
+            local: false,
+        };
+
+        let mut out = Vec::new();
+        let result = pingpong_cmd.handle(
+            &mock_team_member_repo,
+            &mock_mob_repo,
+            &MockHistoryRepo::new(),
+            &mut out,
+        );
+
+        assert!(
+            result
+                .is_err_and(|x| x.to_string() == "Ping-pong mode needs two different team members")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pingpong_when_team_member_not_found() -> Result<()> {
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo
+            .expect_list_aliases()
+            .returning(|| Ok(vec![]));
+        mock_team_member_repo.expect_get().returning(|_| Ok(None));
+        mock_team_member_repo.expect_list().returning(|| Ok(vec![]));
+        let mock_mob_repo = MockMobSessionRepo::new();
+
+        let pingpong_cmd = Pingpong {
+            keys: vec!["lm".to_owned(), "em".to_owned()],
+            local: false,
+        };
+
+        let mut out = Vec::new();
+        let result = pingpong_cmd.handle(
+            &mock_team_member_repo,
+            &mock_mob_repo,
+            &MockHistoryRepo::new(),
+            &mut out,
+        );
+
+        assert!(result.is_err_and(|x| x.to_string() == "No team member found with key: lm"));
+
+        Ok(())
+    }
+}
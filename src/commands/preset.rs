@@ -0,0 +1,577 @@
+use crate::Result;
+use crate::commands::mob::{record_end, record_history, switch_driver};
+use crate::repositories::{
+    self, HistoryEvent, HistoryRepo, MobSessionRepo, MobSessionScope, PresetRepo, SessionEnd,
+    TeamMemberRepo,
+};
+use clap::{Parser, Subcommand};
+use std::io::Write;
+
+#[derive(Parser)]
+pub(crate) struct Preset {
+    #[command(subcommand)]
+    pub(crate) command: PresetCommands,
+}
+
+#[derive(Subcommand)]
+pub(crate) enum PresetCommands {
+    /// Saves co-author(s) and driver of the active mob/pair programming session as a preset
+    ///
+    /// Co-author(s) added with --add are saved too. Any existing preset with the same name is replaced.
+    ///
+    /// Usage example: git mob preset save payments-mob
+    Save(PresetSave),
+    /// Starts a mob/pair programming session with the co-author(s) and driver of the preset
+    ///
+    /// This will clear any existing co-author(s) in the session
+    ///
+    /// Usage example: git mob preset load payments-mob
+    Load(PresetLoad),
+    /// Deletes preset
+    ///
+    /// Usage example: git mob preset delete payments-mob
+    Delete(PresetDelete),
+    /// Lists presets with their co-author(s)
+    ///
+    /// Usage example: git mob preset list
+    List,
+    /// Sets the preset used when committing in the current repository with no mob/pair programming session
+    ///
+    /// Usage example: git mob preset default payments-mob
+    Default(PresetDefault),
+}
+
+#[derive(Parser)]
+pub(crate) struct PresetSave {
+    #[arg(value_name = "PRESET_NAME")]
+    pub(crate) name: String,
+    /// Also makes the preset the default of the current repository
+    #[arg(long = "default")]
+    pub(crate) default: bool,
+}
+
+#[derive(Parser)]
+pub(crate) struct PresetLoad {
+    #[arg(value_name = "PRESET_NAME")]
+    pub(crate) name: String,
+    /// Starts the mob/pair programming session of the current repository instead of the global one
+    #[arg(long = "local")]
+    pub(crate) local: bool,
+}
+
+#[derive(Parser)]
+pub(crate) struct PresetDelete {
+    #[arg(value_name = "PRESET_NAME")]
+    pub(crate) name: String,
+}
+
+#[derive(Parser)]
+pub(crate) struct PresetDefault {
+    #[arg(value_name = "PRESET_NAME", required_unless_present = "unset")]
+    pub(crate) name: Option<String>,
+    /// Stops using a default preset in the current repository
+    #[arg(long = "unset", conflicts_with = "name")]
+    pub(crate) unset: bool,
+}
+
+impl Preset {
+    pub(crate) fn handle(
+        &self,
+        team_member_repo: &impl TeamMemberRepo,
+        mob_repo: &impl MobSessionRepo,
+        preset_repo: &impl PresetRepo,
+        history_repo: &impl HistoryRepo,
+        out: &mut impl Write,
+    ) -> Result<()> {
+        match &self.command {
+            PresetCommands::Save(save) => {
+                let (scope, coauthors) = mob_repo.list_active_coauthors()?;
+                let driver = mob_repo.get_driver(scope)?;
+                if coauthors.is_empty() && driver.is_none() {
+                    return Err(
+                        "No co-author(s) in the mob/pair programming session to save".into(),
+                    );
+                }
+
+                let preset = repositories::Preset {
+                    driver,
+                    ..repositories::Preset::new(&save.name, &coauthors)?
+                };
+                preset_repo.save(&preset)?;
+                if save.default {
+                    preset_repo.set_default(&preset.name)?;
+                }
+                write_preset(out, &preset)?
+            }
+            PresetCommands::Load(load) => {
+                let scope = if load.local {
+                    MobSessionScope::Local
+                } else {
+                    MobSessionScope::Global
+                };
+                let preset = get_preset(preset_repo, &load.name)?;
+                // the driver is resolved first so that a missing one leaves the session untouched
+                let driver = match preset.driver.as_deref() {
+                    Some(driver) => Some(
+                        team_member_repo
+                            .list()?
+                            .into_iter()
+                            .find(|x| x.to_string() == driver)
+                            .ok_or_else(|| {
+                                format!(
+                                    "Driver of preset {} is no longer a team member: {driver}",
+                                    preset.name
+                                )
+                            })?,
+                    ),
+                    None => None,
+                };
+
+                record_end(mob_repo, history_repo, scope, SessionEnd::Replaced)?;
+                mob_repo.clear(scope)?;
+                for coauthor in &preset.coauthors {
+                    mob_repo.add_coauthor(scope, coauthor)?;
+                }
+                if let Some(driver) = &driver {
+                    switch_driver(mob_repo, scope, driver)?;
+                }
+                let members: Vec<String> = preset
+                    .driver
+                    .iter()
+                    .chain(&preset.coauthors)
+                    .cloned()
+                    .collect();
+                record_history(history_repo, scope, HistoryEvent::Start, &members);
+                write_preset(out, &preset)?
+            }
+            PresetCommands::Delete(delete) => {
+                get_preset(preset_repo, &delete.name)?;
+                preset_repo.remove(&delete.name)?;
+            }
+            PresetCommands::List => {
+                let default = preset_repo.get_default()?;
+                for preset in preset_repo.list()? {
+                    let marker = if default.as_ref() == Some(&preset.name) {
+                        " (default)"
+                    } else {
+                        ""
+                    };
+                    let members: Vec<String> = preset
+                        .driver
+                        .iter()
+                        .map(|x| format!("{x} (driver)"))
+                        .chain(preset.coauthors.iter().cloned())
+                        .collect();
+                    writeln!(out, "{}{marker}: {}", preset.name, members.join(", "))?
+                }
+            }
+            PresetCommands::Default(default) => match default.name.as_deref() {
+                Some(name) => {
+                    get_preset(preset_repo, name)?;
+                    preset_repo.set_default(name)?;
+                }
+                None => preset_repo.unset_default()?,
+            },
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes the co-author(s) and driver of the preset in the same way as git mob --with and --driver
+fn write_preset(out: &mut impl Write, preset: &repositories::Preset) -> Result<()> {
+    if !preset.coauthors.is_empty() {
+        writeln!(out, "{}", preset.coauthors.join("\n"))?;
+    }
+    if let Some(driver) = &preset.driver {
+        writeln!(out, "Driver: {driver}")?;
+    }
+
+    Ok(())
+}
+
+fn get_preset(preset_repo: &impl PresetRepo, name: &str) -> Result<repositories::Preset> {
+    preset_repo
+        .get(name)?
+        .ok_or_else(|| format!("No preset found with name: {name}").into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::{
+        MockHistoryRepo, MockMobSessionRepo, MockPresetRepo, MockTeamMemberRepo, TeamMember,
+    };
+    use mockall::predicate;
+
+    fn create_preset() -> Result<repositories::Preset> {
+        repositories::Preset::new(
+            "payments-mob",
+            &[
+                "Leo Messi <leo.messi@example.com>".to_owned(),
+                "Diego Maradona <diego.maradona@example.com>".to_owned(),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_save_preset() -> Result<()> {
+        let preset = repositories::Preset {
+            driver: Some("Emi Martinez <emi.martinez@example.com>".to_owned()),
+            ..create_preset()?
+        };
+        let coauthors = preset.coauthors.clone();
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo
+            .expect_list_active_coauthors()
+            .once()
+            .returning(move || Ok((MobSessionScope::Local, coauthors.clone())));
+        mock_mob_repo
+            .expect_get_driver()
+            .with(predicate::eq(MobSessionScope::Local))
+            .once()
+            .returning(|_| Ok(Some("Emi Martinez <emi.martinez@example.com>".to_owned())));
+        let mut mock_preset_repo = MockPresetRepo::new();
+        mock_preset_repo
+            .expect_save()
+            .with(predicate::eq(preset))
+            .once()
+            .returning(|_| Ok(()));
+        mock_preset_repo
+            .expect_set_default()
+            .with(predicate::eq("payments-mob"))
+            .once()
+            .returning(|_| Ok(()));
+
+        let preset_cmd = Preset {
+            command: PresetCommands::Save(PresetSave {
+                name: "payments-mob".to_owned(),
+                default: true,
+            }),
+        };
+
+        let mut out = Vec::new();
+        preset_cmd.handle(
+            &MockTeamMemberRepo::new(),
+            &mock_mob_repo,
+            &mock_preset_repo,
+            &MockHistoryRepo::new(),
+            &mut out,
+        )?;
+
+        assert_eq!(
+            String::from_utf8(out)?,
+            "Leo Messi <leo.messi@example.com>\n\
+             Diego Maradona <diego.maradona@example.com>\n\
+             Driver: Emi Martinez <emi.martinez@example.com>\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_save_preset_when_mob_session_is_empty() -> Result<()> {
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo
+            .expect_list_active_coauthors()
+            .once()
+            .returning(|| Ok((MobSessionScope::Global, vec![])));
+        mock_mob_repo
+            .expect_get_driver()
+            .once()
+            .returning(|_| Ok(None));
+        let mut mock_preset_repo = MockPresetRepo::new();
+        mock_preset_repo.expect_save().never();
+
+        let preset_cmd = Preset {
+            command: PresetCommands::Save(PresetSave {
+                name: "payments-mob".to_owned(),
+                default: false,
+            }),
+        };
+
+        let mut out = Vec::new();
+        let result = preset_cmd.handle(
+            &MockTeamMemberRepo::new(),
+            &mock_mob_repo,
+            &mock_preset_repo,
+            &MockHistoryRepo::new(),
+            &mut out,
+        );
+
+        assert!(result.is_err_and(
+            |x| x.to_string() == "No co-author(s) in the mob/pair programming session to save"
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_preset() -> Result<()> {
+        let preset = create_preset()?;
+        let coauthors = preset.coauthors.clone();
+        let mut mock_preset_repo = MockPresetRepo::new();
+        mock_preset_repo
+            .expect_get()
+            .with(predicate::eq("payments-mob"))
+            .once()
+            .returning(move |_| Ok(Some(preset.clone())));
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo
+            .expect_list_coauthors()
+            .with(predicate::eq(MobSessionScope::Local))
+            .returning(|_| Ok(vec![]));
+        mock_mob_repo.expect_get_driver().returning(|_| Ok(None));
+        mock_mob_repo
+            .expect_clear()
+            .with(predicate::eq(MobSessionScope::Local))
+            .once()
+            .returning(|_| Ok(()));
+        let cloned_coauthors = coauthors.clone();
+        mock_mob_repo
+            .expect_add_coauthor()
+            .withf(move |scope, coauthor| {
+                *scope == MobSessionScope::Local && cloned_coauthors.iter().any(|x| x == coauthor)
+            })
+            .times(2)
+            .returning(|_, _| Ok(()));
+        let mut mock_history_repo = MockHistoryRepo::new();
+        mock_history_repo
+            .expect_append()
+            .withf(move |scope, event, members| {
+                *scope == MobSessionScope::Local
+                    && *event == HistoryEvent::Start
+                    && members == coauthors
+            })
+            .once()
+            .returning(|_, _, _| Ok(()));
+
+        let preset_cmd = Preset {
+            command: PresetCommands::Load(PresetLoad {
+                name: "payments-mob".to_owned(),
+                local: true,
+            }),
+        };
+
+        let mut out = Vec::new();
+        preset_cmd.handle(
+            &MockTeamMemberRepo::new(),
+            &mock_mob_repo,
+            &mock_preset_repo,
+            &mock_history_repo,
+            &mut out,
+        )?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_preset_given_driver() -> Result<()> {
+        let preset = repositories::Preset {
+            driver: Some("Emi Martinez <emi.martinez@example.com>".to_owned()),
+            ..create_preset()?
+        };
+        let mut mock_preset_repo = MockPresetRepo::new();
+        mock_preset_repo
+            .expect_get()
+            .once()
+            .returning(move |_| Ok(Some(preset.clone())));
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo.expect_list().once().returning(|| {
+            Ok(vec![TeamMember::new(
+                "em",
+                "Emi Martinez",
+                "emi.martinez@example.com",
+            )?])
+        });
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo
+            .expect_list_coauthors()
+            .returning(|_| Ok(vec![]));
+        mock_mob_repo.expect_get_driver().returning(|_| Ok(None));
+        mock_mob_repo.expect_clear().once().returning(|_| Ok(()));
+        mock_mob_repo
+            .expect_add_coauthor()
+            .times(2)
+            .returning(|_, _| Ok(()));
+        mock_mob_repo
+            .expect_set_driver()
+            .withf(|scope, driver| *scope == MobSessionScope::Global && driver.key == "em")
+            .once()
+            .returning(|_, _| Ok(()));
+        let mut mock_history_repo = MockHistoryRepo::new();
+        mock_history_repo
+            .expect_append()
+            .withf(|_, event, members| {
+                *event == HistoryEvent::Start
+                    && members
+                        == [
+                            "Emi Martinez <emi.martinez@example.com>",
+                            "Leo Messi <leo.messi@example.com>",
+                            "Diego Maradona <diego.maradona@example.com>",
+                        ]
+            })
+            .once()
+            .returning(|_, _, _| Ok(()));
+
+        let preset_cmd = Preset {
+            command: PresetCommands::Load(PresetLoad {
+                name: "payments-mob".to_owned(),
+                local: false,
+            }),
+        };
+
+        let mut out = Vec::new();
+        preset_cmd.handle(
+            &mock_team_member_repo,
+            &mock_mob_repo,
+            &mock_preset_repo,
+            &mock_history_repo,
+            &mut out,
+        )?;
+
+        assert_eq!(
+            String::from_utf8(out)?,
+            "Leo Messi <leo.messi@example.com>\n\
+             Diego Maradona <diego.maradona@example.com>\n\
+             Driver: Emi Martinez <emi.martinez@example.com>\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_preset_when_driver_not_found() -> Result<()> {
+        let preset = repositories::Preset {
+            driver: Some("Emi Martinez <emi.martinez@example.com>".to_owned()),
+            ..create_preset()?
+        };
+        let mut mock_preset_repo = MockPresetRepo::new();
+        mock_preset_repo
+            .expect_get()
+            .once()
+            .returning(move |_| Ok(Some(preset.clone())));
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo
+            .expect_list()
+            .once()
+            .returning(|| Ok(vec![]));
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo.expect_clear().never();
+
+        let preset_cmd = Preset {
+            command: PresetCommands::Load(PresetLoad {
+                name: "payments-mob".to_owned(),
+                local: false,
+            }),
+        };
+
+        let mut out = Vec::new();
+        let result = preset_cmd.handle(
+            &mock_team_member_repo,
+            &mock_mob_repo,
+            &mock_preset_repo,
+            &MockHistoryRepo::new(),
+            &mut out,
+        );
+
+        assert!(result.is_err_and(|x| x.to_string()
+            == "Driver of preset payments-mob is no longer a team member: Emi Martinez <emi.martinez@example.com>"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_preset_when_preset_not_found() -> Result<()> {
+        let mut mock_preset_repo = MockPresetRepo::new();
+        mock_preset_repo.expect_get().once().returning(|_| Ok(None));
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo.expect_clear().never();
+
+        let preset_cmd = Preset {
+            command: PresetCommands::Load(PresetLoad {
+                name: "payments-mob".to_owned(),
+                local: false,
+            }),
+        };
+
+        let mut out = Vec::new();
+        let result = preset_cmd.handle(
+            &MockTeamMemberRepo::new(),
+            &mock_mob_repo,
+            &mock_preset_repo,
+            &MockHistoryRepo::new(),
+            &mut out,
+        );
+
+        assert!(result.is_err_and(|x| x.to_string() == "No preset found with name: payments-mob"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_presets() -> Result<()> {
+        let mut mock_preset_repo = MockPresetRepo::new();
+        mock_preset_repo
+            .expect_get_default()
+            .once()
+            .returning(|| Ok(Some("payments-mob".to_owned())));
+        mock_preset_repo.expect_list().once().returning(|| {
+            Ok(vec![
+                create_preset()?,
+                repositories::Preset::new(
+                    "search",
+                    &["Emi Martinez <emi.martinez@example.com>".to_owned()],
+                )?,
+            ])
+        });
+
+        let preset_cmd = Preset {
+            command: PresetCommands::List,
+        };
+
+        let mut out = Vec::new();
+        preset_cmd.handle(
+            &MockTeamMemberRepo::new(),
+            &MockMobSessionRepo::new(),
+            &mock_preset_repo,
+            &MockHistoryRepo::new(),
+            &mut out,
+        )?;
+
+        assert_eq!(
+            String::from_utf8(out)?,
+            "payments-mob (default): Leo Messi <leo.messi@example.com>, Diego Maradona <diego.maradona@example.com>\n\
+             search: Emi Martinez <emi.martinez@example.com>\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unset_default_preset() -> Result<()> {
+        let mut mock_preset_repo = MockPresetRepo::new();
+        mock_preset_repo
+            .expect_unset_default()
+            .once()
+            .returning(|| Ok(()));
+
+        let preset_cmd = Preset {
+            command: PresetCommands::Default(PresetDefault {
+                name: None,
+                unset: true,
+            }),
+        };
+
+        let mut out = Vec::new();
+        preset_cmd.handle(
+            &MockTeamMemberRepo::new(),
+            &MockMobSessionRepo::new(),
+            &mock_preset_repo,
+            &MockHistoryRepo::new(),
+            &mut out,
+        )?;
+
+        Ok(())
+    }
+}
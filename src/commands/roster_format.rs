@@ -0,0 +1,158 @@
+use crate::Result;
+use crate::repositories::{CoauthorsFileTeamMemberRepo, TeamMember};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// File format which team members can be exported to and imported from
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub(crate) enum RosterFormat {
+    /// JSON array of team members
+    #[default]
+    Json,
+    /// Comma separated values with a header row
+    Csv,
+    /// `~/.git-coauthors` file of the JavaScript git-mob tool
+    GitCoauthors,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RosterEntry {
+    key: String,
+    name: String,
+    email: String,
+}
+
+impl RosterFormat {
+    /// Infers format from the extension of the file
+    pub(crate) fn from_path(path: &Path) -> Option<Self> {
+        if path.file_name()? == CoauthorsFileTeamMemberRepo::FILE_NAME {
+            return Some(RosterFormat::GitCoauthors);
+        }
+
+        let extension = path.extension()?.to_str()?;
+        RosterFormat::from_str(extension, true).ok()
+    }
+
+    pub(crate) fn serialize(&self, team_members: &[TeamMember]) -> Result<String> {
+        let entries = team_members.iter().map(|x| RosterEntry {
+            key: x.key.to_owned(),
+            name: x.name.to_owned(),
+            email: x.email.to_owned(),
+        });
+
+        match self {
+            RosterFormat::Json => {
+                let mut output = serde_json::to_string_pretty(&entries.collect::<Vec<_>>())?;
+                output.push('\n');
+                Ok(output)
+            }
+            RosterFormat::Csv => {
+                let mut writer = csv::Writer::from_writer(vec![]);
+                for entry in entries {
+                    writer.serialize(entry)?;
+                }
+                Ok(String::from_utf8(writer.into_inner()?)?)
+            }
+            RosterFormat::GitCoauthors => CoauthorsFileTeamMemberRepo::serialize(team_members),
+        }
+    }
+
+    pub(crate) fn deserialize(&self, input: &str) -> Result<Vec<TeamMember>> {
+        let entries: Vec<RosterEntry> = match self {
+            RosterFormat::GitCoauthors => return CoauthorsFileTeamMemberRepo::deserialize(input),
+            RosterFormat::Json => serde_json::from_str(input)?,
+            RosterFormat::Csv => csv::Reader::from_reader(input.as_bytes())
+                .deserialize()
+                .collect::<std::result::Result<_, _>>()?,
+        };
+
+        entries
+            .into_iter()
+            .map(|x| TeamMember::new(&x.key, &x.name, &x.email))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JSON: &str = r#"[
+  {
+    "key": "lm",
+    "name": "Leo Messi",
+    "email": "leo.messi@example.com"
+  },
+  {
+    "key": "em",
+    "name": "Emi Martinez",
+    "email": "emi.martinez@example.com"
+  }
+]
+"#;
+
+    const CSV: &str = "key,name,email
+lm,Leo Messi,leo.messi@example.com
+em,Emi Martinez,emi.martinez@example.com
+";
+
+    fn team_members() -> Result<Vec<TeamMember>> {
+        Ok(vec![
+            TeamMember::new("lm", "Leo Messi", "leo.messi@example.com")?,
+            TeamMember::new("em", "Emi Martinez", "emi.martinez@example.com")?,
+        ])
+    }
+
+    #[test]
+    fn test_from_path() {
+        assert_eq!(
+            RosterFormat::from_path(Path::new("roster.json")),
+            Some(RosterFormat::Json)
+        );
+        assert_eq!(
+            RosterFormat::from_path(Path::new("team/roster.CSV")),
+            Some(RosterFormat::Csv)
+        );
+        assert_eq!(
+            RosterFormat::from_path(Path::new("/home/lm/.git-coauthors")),
+            Some(RosterFormat::GitCoauthors)
+        );
+        assert_eq!(RosterFormat::from_path(Path::new("roster.txt")), None);
+        assert_eq!(RosterFormat::from_path(Path::new("roster")), None);
+    }
+
+    #[test]
+    fn test_serialize() -> Result<()> {
+        assert_eq!(RosterFormat::Json.serialize(&team_members()?)?, JSON);
+        assert_eq!(RosterFormat::Csv.serialize(&team_members()?)?, CSV);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_deserialize() -> Result<()> {
+        assert_eq!(RosterFormat::Json.deserialize(JSON)?, team_members()?);
+        assert_eq!(RosterFormat::Csv.deserialize(CSV)?, team_members()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_deserialize_when_invalid_team_member() -> Result<()> {
+        let result = RosterFormat::Csv.deserialize("key,name,email\nlm,Leo Messi,\n");
+
+        assert!(result.is_err_and(|x| x.to_string() == "Invalid email: it must not be empty"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_deserialize_when_invalid_format() -> Result<()> {
+        let result = RosterFormat::Json.deserialize(r#"[{ "key": "lm" }]"#);
+
+        assert!(result.is_err_and(|x| x.to_string().starts_with("missing field `name`")));
+
+        Ok(())
+    }
+}
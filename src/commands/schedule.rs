@@ -0,0 +1,511 @@
+use crate::Result;
+use crate::commands::mob::CoauthorResolver;
+use crate::commands::output_format::{OutputFormat, write_json, write_table};
+use crate::repositories::{CommitLogRepo, TeamMember, TeamMemberRepo};
+use chrono::Local;
+use clap::Parser;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io::Write;
+
+#[derive(Parser)]
+pub(crate) struct Schedule {
+    /// Team members to rotate, separated by commas. Defaults to all team members
+    ///
+    /// Usage example: git mob schedule --members lm,em,sa,dm
+    #[arg(
+        long = "members",
+        value_name = "TEAM_MEMBER_KEYS",
+        value_delimiter = ','
+    )]
+    pub(crate) members: Option<Vec<String>>,
+    /// Number of days to plan, up to a year
+    #[arg(
+        long = "days",
+        value_name = "DAYS",
+        default_value = "5",
+        value_parser = clap::value_parser!(u32).range(1..=366)
+    )]
+    pub(crate) days: u32,
+    /// Seed of the shuffled order of team members, so that the same schedule can be generated again
+    ///
+    /// Usage example: git mob schedule --days 10 --seed 42
+    #[arg(long = "seed", value_name = "SEED")]
+    pub(crate) seed: Option<u64>,
+    /// Plans pairs who have co-authored fewer commits in the current repository first
+    #[arg(long = "weight-history")]
+    pub(crate) weight_history: bool,
+    /// Format of the output, set by the global --format
+    #[arg(from_global)]
+    pub(crate) format: OutputFormat,
+}
+
+#[derive(Serialize)]
+struct ScheduleJson<'a> {
+    seed: u64,
+    days: Vec<DayJson<'a>>,
+}
+
+#[derive(Serialize)]
+struct DayJson<'a> {
+    day: usize,
+    pairs: &'a [Vec<String>],
+}
+
+/// SplitMix64 pseudo-random number generator which is good enough to shuffle team members
+struct SeededRng(u64);
+
+impl SeededRng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = (self.next() % (i as u64 + 1)) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+impl Schedule {
+    pub(crate) fn handle(
+        &self,
+        team_member_repo: &impl TeamMemberRepo,
+        commit_log_repo: &impl CommitLogRepo,
+        out: &mut impl Write,
+    ) -> Result<()> {
+        let mut team_members = match self.members.as_deref() {
+            Some(tokens) => {
+                let mut resolver = CoauthorResolver::new(team_member_repo, false)?;
+                let mut team_members = Vec::new();
+                for token in tokens {
+                    let team_member = resolver.resolve_team_member(token)?;
+                    if !team_members.contains(&team_member) {
+                        team_members.push(team_member)
+                    }
+                }
+                team_members
+            }
+            None => team_member_repo.list()?,
+        };
+        if team_members.len() < 2 {
+            return Err("At least two team members are needed to make a schedule".into());
+        }
+
+        let seed = self
+            .seed
+            .unwrap_or_else(|| Local::now().timestamp_micros().unsigned_abs());
+        SeededRng(seed).shuffle(&mut team_members);
+
+        let history = if self.weight_history {
+            Self::count_pairings(&team_members, commit_log_repo)?
+        } else {
+            HashMap::new()
+        };
+        let keys: Vec<String> = team_members.into_iter().map(|x| x.key).collect();
+        let days = Self::plan(&keys, self.days as usize, &history);
+
+        if self.format == OutputFormat::Json {
+            let schedule = ScheduleJson {
+                seed,
+                days: days
+                    .iter()
+                    .enumerate()
+                    .map(|(index, pairs)| DayJson {
+                        day: index + 1,
+                        pairs,
+                    })
+                    .collect(),
+            };
+            return write_json(out, &schedule);
+        }
+
+        let mut rows = vec![Self::header(&days)];
+        rows.extend(days.iter().enumerate().map(|(index, pairs)| {
+            [(index + 1).to_string()]
+                .into_iter()
+                .chain(pairs.iter().map(|x| x.join(", ")))
+                .collect()
+        }));
+        match self.format {
+            // the seed is only shown in the formats which are read by people
+            OutputFormat::Text => {
+                write_table(out, &rows)?;
+                writeln!(out)?;
+                writeln!(out, "Generated with --seed {seed}")?;
+            }
+            OutputFormat::Markdown => {
+                self.format.write_rows(out, &rows)?;
+                writeln!(out)?;
+                writeln!(out, "Generated with `--seed {seed}`")?;
+            }
+            format => format.write_rows(out, &rows)?,
+        }
+
+        Ok(())
+    }
+
+    fn header(days: &[Vec<Vec<String>>]) -> Vec<String> {
+        let pair_count = days.iter().map(|x| x.len()).max().unwrap_or_default();
+        ["Day".to_owned()]
+            .into_iter()
+            .chain((1..=pair_count).map(|x| format!("Pair {x}")))
+            .collect()
+    }
+
+    /// Counts commits co-authored by each pair of the team members, keyed by their keys in order
+    fn count_pairings(
+        team_members: &[TeamMember],
+        commit_log_repo: &impl CommitLogRepo,
+    ) -> Result<HashMap<(String, String), u32>> {
+        let keys: HashMap<String, &str> = team_members
+            .iter()
+            .map(|x| (x.email.to_lowercase(), x.key.as_str()))
+            .collect();
+        let mut counts = HashMap::new();
+
+        for commit in commit_log_repo.list_commits(None, None)? {
+            let mut participants: Vec<&str> = [&commit.author]
+                .into_iter()
+                .chain(&commit.coauthors)
+                .filter_map(|x| keys.get(&x.email.to_lowercase()).copied())
+                .collect();
+            participants.sort_unstable();
+            participants.dedup();
+
+            for (index, a) in participants.iter().enumerate() {
+                for b in participants[index + 1..].iter() {
+                    *counts.entry((a.to_string(), b.to_string())).or_default() += 1;
+                }
+            }
+        }
+
+        Ok(counts)
+    }
+
+    /// Generates all rounds of a round-robin (circle method) where everyone pairs with everyone else once.
+    /// With an odd headcount, whoever would sit out joins the first pair as a trio.
+    fn rounds(keys: &[String]) -> Vec<Vec<Vec<String>>> {
+        let mut slots: Vec<Option<&String>> = keys.iter().map(Some).collect();
+        // an empty first slot never rotates, so everyone takes a turn at sitting out (i.e. joining a trio)
+        if slots.len() % 2 == 1 {
+            slots.insert(0, None);
+        }
+        let half = slots.len() / 2;
+
+        let mut rounds = Vec::new();
+        for _ in 0..slots.len() - 1 {
+            let mut pairs: Vec<Vec<String>> = Vec::new();
+            let mut sitting_out = None;
+            for index in 0..half {
+                match (slots[index], slots[slots.len() - 1 - index]) {
+                    (Some(a), Some(b)) => pairs.push(vec![a.to_owned(), b.to_owned()]),
+                    (Some(x), None) | (None, Some(x)) => sitting_out = Some(x.to_owned()),
+                    (None, None) => {}
+                }
+            }
+            if let (Some(key), Some(pair)) = (sitting_out, pairs.first_mut()) {
+                pair.push(key);
+            }
+            rounds.push(pairs);
+
+            // first slot stays put while the rest rotate
+            slots[1..].rotate_right(1);
+        }
+        rounds
+    }
+
+    /// Plans the days by picking the round used the fewest times so far, and among those the one whose
+    /// pairs have paired the least in the history
+    fn plan(
+        keys: &[String],
+        days: usize,
+        history: &HashMap<(String, String), u32>,
+    ) -> Vec<Vec<Vec<String>>> {
+        let rounds = Self::rounds(keys);
+        let history_score = |round: &Vec<Vec<String>>| {
+            let mut score = 0;
+            for pair in round {
+                for (index, a) in pair.iter().enumerate() {
+                    for b in pair[index + 1..].iter() {
+                        let key = (a.min(b).to_owned(), a.max(b).to_owned());
+                        score += history.get(&key).copied().unwrap_or_default();
+                    }
+                }
+            }
+            score
+        };
+        let scores: Vec<u32> = rounds.iter().map(history_score).collect();
+
+        let mut uses = vec![0; rounds.len()];
+        let mut plan = Vec::new();
+        for _ in 0..days {
+            let index = (0..rounds.len())
+                .min_by_key(|x| (uses[*x], scores[*x]))
+                .unwrap_or_default();
+            uses[index] += 1;
+            plan.push(rounds[index].clone());
+        }
+        plan
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::{Commit, Contributor, MockCommitLogRepo, MockTeamMemberRepo};
+    use std::collections::HashSet;
+
+    fn create_mock_team_member_repo() -> MockTeamMemberRepo {
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        let team_members = [
+            ("lm", "Leo Messi", "leo.messi@example.com"),
+            ("em", "Emi Martinez", "emi.martinez@example.com"),
+            ("sa", "Sergio Aguero", "sergio.aguero@example.com"),
+            ("dm", "Diego Maradona", "diego.maradona@example.com"),
+            ("adm", "Angel Di Maria", "angel.dimaria@example.com"),
+        ];
+        mock_team_member_repo.expect_get().returning(move |key| {
+            team_members
+                .iter()
+                .find(|(x, _, _)| *x == key)
+                .map(|(key, name, email)| TeamMember::new(key, name, email))
+                .transpose()
+        });
+        mock_team_member_repo.expect_list().returning(move || {
+            team_members
+                .iter()
+                .map(|(key, name, email)| TeamMember::new(key, name, email))
+                .collect()
+        });
+        mock_team_member_repo
+            .expect_list_aliases()
+            .returning(|| Ok(vec![]));
+        mock_team_member_repo
+    }
+
+    fn keys(keys: &[&str]) -> Vec<String> {
+        keys.iter().map(|x| x.to_string()).collect()
+    }
+
+    fn pairs_of(round: &[Vec<String>]) -> Vec<(String, String)> {
+        let mut pairs = Vec::new();
+        for group in round {
+            for (index, a) in group.iter().enumerate() {
+                for b in group[index + 1..].iter() {
+                    pairs.push(if a < b {
+                        (a.to_owned(), b.to_owned())
+                    } else {
+                        (b.to_owned(), a.to_owned())
+                    });
+                }
+            }
+        }
+        pairs
+    }
+
+    #[test]
+    fn test_rounds_given_even_headcount() {
+        let rounds = Schedule::rounds(&keys(&["lm", "em", "sa", "dm"]));
+
+        assert_eq!(rounds.len(), 3);
+        let pairs: HashSet<(String, String)> = rounds.iter().flat_map(|x| pairs_of(x)).collect();
+        assert_eq!(pairs.len(), 6);
+        assert!(
+            rounds
+                .iter()
+                .all(|x| x.len() == 2 && x.iter().all(|x| x.len() == 2))
+        );
+    }
+
+    #[test]
+    fn test_rounds_given_odd_headcount() {
+        let rounds = Schedule::rounds(&keys(&["lm", "em", "sa"]));
+
+        assert_eq!(
+            rounds,
+            vec![
+                vec![keys(&["lm", "em", "sa"])],
+                vec![keys(&["sa", "lm", "em"])],
+                vec![keys(&["em", "sa", "lm"])],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plan_given_history() {
+        let history = HashMap::from([
+            (("em".to_owned(), "lm".to_owned()), 5),
+            (("dm".to_owned(), "sa".to_owned()), 2),
+        ]);
+
+        let plan = Schedule::plan(&keys(&["lm", "em", "sa", "dm"]), 4, &history);
+
+        // rounds without history come first and the cycle repeats afterwards
+        assert_eq!(plan.len(), 4);
+        assert_eq!(
+            plan[2],
+            Schedule::rounds(&keys(&["lm", "em", "sa", "dm"]))[2]
+        );
+        assert_eq!(plan[3], plan[0]);
+        assert!(!pairs_of(&plan[0]).contains(&("em".to_owned(), "lm".to_owned())));
+    }
+
+    #[test]
+    fn test_schedule() -> Result<()> {
+        let schedule_cmd = Schedule {
+            members: Some(keys(&["lm", "em", "sa", "dm", "adm"])),
+            days: 5,
+            seed: Some(42),
+            weight_history: false,
+            format: OutputFormat::Text,
+        };
+
+        let mut out = Vec::new();
+        schedule_cmd.handle(
+            &create_mock_team_member_repo(),
+            &MockCommitLogRepo::new(),
+            &mut out,
+        )?;
+        let output = String::from_utf8(out)?;
+
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 8);
+        assert!(lines[0].starts_with("Day  Pair 1"));
+        assert!(lines[0].ends_with("Pair 2"));
+        assert!(lines[1].starts_with("1    "));
+        assert_eq!(lines[7], "Generated with --seed 42");
+
+        // same seed generates the same schedule
+        let mut out = Vec::new();
+        schedule_cmd.handle(
+            &create_mock_team_member_repo(),
+            &MockCommitLogRepo::new(),
+            &mut out,
+        )?;
+        assert_eq!(String::from_utf8(out)?, output);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_schedule_given_markdown_format() -> Result<()> {
+        let schedule_cmd = Schedule {
+            members: Some(keys(&["lm", "em"])),
+            days: 2,
+            seed: Some(7),
+            weight_history: false,
+            format: OutputFormat::Markdown,
+        };
+
+        let mut out = Vec::new();
+        schedule_cmd.handle(
+            &create_mock_team_member_repo(),
+            &MockCommitLogRepo::new(),
+            &mut out,
+        )?;
+
+        let output = String::from_utf8(out)?;
+        assert!(output.starts_with("| Day | Pair 1 |\n| --- | --- |\n| 1 | "));
+        assert!(output.ends_with("\n\nGenerated with `--seed 7`\n"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_schedule_given_json_format_and_history() -> Result<()> {
+        let mut mock_commit_log_repo = MockCommitLogRepo::new();
+        mock_commit_log_repo
+            .expect_list_commits()
+            .once()
+            .returning(|_, _| {
+                Ok(vec![Commit {
+                    authored_at: Local::now(),
+                    author: Contributor {
+                        name: "Leo Messi".to_owned(),
+                        email: "leo.messi@example.com".to_owned(),
+                    },
+                    coauthors: vec![Contributor {
+                        name: "Emi Martinez".to_owned(),
+                        email: "emi.martinez@example.com".to_owned(),
+                    }],
+                }])
+            });
+
+        let schedule_cmd = Schedule {
+            members: Some(keys(&["lm", "em", "sa", "dm"])),
+            days: 1,
+            seed: Some(1),
+            weight_history: true,
+            format: OutputFormat::Json,
+        };
+
+        let mut out = Vec::new();
+        schedule_cmd.handle(
+            &create_mock_team_member_repo(),
+            &mock_commit_log_repo,
+            &mut out,
+        )?;
+
+        let schedule: serde_json::Value = serde_json::from_slice(&out)?;
+        assert_eq!(schedule["version"], 1);
+        assert_eq!(schedule["seed"], 1);
+        assert_eq!(schedule["days"][0]["day"], 1);
+        let pairs: Vec<Vec<String>> = serde_json::from_value(schedule["days"][0]["pairs"].clone())?;
+        assert_eq!(pairs.len(), 2);
+        assert!(!pairs_of(&pairs).contains(&("em".to_owned(), "lm".to_owned())));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_schedule_when_team_member_not_found() -> Result<()> {
+        let schedule_cmd = Schedule {
+            members: Some(keys(&["lm", "jk"])),
+            days: 1,
+            seed: None,
+            weight_history: false,
+            format: OutputFormat::Text,
+        };
+
+        let mut out = Vec::new();
+        let result = schedule_cmd.handle(
+            &create_mock_team_member_repo(),
+            &MockCommitLogRepo::new(),
+            &mut out,
+        );
+
+        assert!(result.is_err_and(|x| x.to_string() == "No team member found with key: jk"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_schedule_given_one_team_member() -> Result<()> {
+        let schedule_cmd = Schedule {
+            members: Some(keys(&["lm", "lm"])),
+            days: 1,
+            seed: None,
+            weight_history: false,
+            format: OutputFormat::Text,
+        };
+
+        let mut out = Vec::new();
+        let result = schedule_cmd.handle(
+            &create_mock_team_member_repo(),
+            &MockCommitLogRepo::new(),
+            &mut out,
+        );
+
+        assert!(result.is_err_and(
+            |x| x.to_string() == "At least two team members are needed to make a schedule"
+        ));
+
+        Ok(())
+    }
+}
@@ -26,6 +26,9 @@ pub(crate) struct Setup {
 }
 
 impl Setup {
+    pub(crate) const POST_COMMIT_HOOK: &'static str = "post-commit";
+    const POST_COMMIT_HOOK_COMMAND: &'static str = "git mob hook post-commit";
+
     pub(crate) fn handle(&self, out: &mut impl Write) -> Result<()> {
         if self.local {
             self.handle_local(out)?;
@@ -57,14 +60,15 @@ impl Setup {
         if !hooks_dir.exists() {
             fs::create_dir_all(&hooks_dir)?;
         } else if prepare_commit_msg_path.exists() {
-            Self::backup_prepare_commit_msg_hook(out, &prepare_commit_msg_path)?;
+            Self::backup_hook(out, &prepare_commit_msg_path)?;
         }
 
-        Self::create_prepare_commit_msg_hook(
+        Self::create_hook(
             out,
             &prepare_commit_msg_path,
             include_str!("prepare-commit-msg"),
         )?;
+        Self::create_post_commit_hook(out, &hooks_dir)?;
 
         writeln!(out, "Setup complete")?;
         Ok(())
@@ -81,20 +85,48 @@ impl Setup {
         if !hooks_dir.exists() {
             fs::create_dir_all(&hooks_dir)?;
         } else if prepare_commit_msg_path.exists() {
-            Self::backup_prepare_commit_msg_hook(out, &prepare_commit_msg_path)?;
+            Self::backup_hook(out, &prepare_commit_msg_path)?;
         }
 
-        Self::create_prepare_commit_msg_hook(
+        Self::create_hook(
             out,
             &prepare_commit_msg_path,
             include_str!("prepare-commit-msg.local"),
         )?;
+        Self::create_post_commit_hook(out, &hooks_dir)?;
 
         writeln!(out, "Setup complete")?;
         Ok(())
     }
 
-    fn get_hooks_dir(scope: &str) -> Result<Option<PathBuf>> {
+    /// Creates the post-commit githook used by ping-pong mode unless the hooks directory already
+    /// has one. A post-commit githook which does not invoke git mob is left untouched as it may
+    /// belong to another tool, so the user has to chain it themselves.
+    fn create_post_commit_hook(out: &mut impl Write, hooks_dir: &Path) -> Result<()> {
+        let post_commit_path = hooks_dir.join(Self::POST_COMMIT_HOOK).clean();
+
+        if !post_commit_path.exists() {
+            return Self::create_hook(out, &post_commit_path, include_str!("post-commit"));
+        }
+
+        if !Self::has_post_commit_hook(&post_commit_path) {
+            writeln!(
+                out,
+                "Skipped existing post-commit githook: {}. Add `{}` to it to use ping-pong mode",
+                post_commit_path.to_string_lossy(),
+                Self::POST_COMMIT_HOOK_COMMAND
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks whether the post-commit githook at the path exists and invokes git mob
+    pub(crate) fn has_post_commit_hook(path: &Path) -> bool {
+        fs::read_to_string(path).is_ok_and(|x| x.contains(Self::POST_COMMIT_HOOK_COMMAND))
+    }
+
+    pub(crate) fn get_hooks_dir(scope: &str) -> Result<Option<PathBuf>> {
         let output = Command::new("git")
             .args(["config", scope, "core.hooksPath"])
             .output()?;
@@ -128,11 +160,7 @@ impl Setup {
         Ok(())
     }
 
-    fn create_prepare_commit_msg_hook(
-        out: &mut impl Write,
-        path: &Path,
-        contents: &str,
-    ) -> Result<()> {
+    pub(crate) fn create_hook(out: &mut impl Write, path: &Path, contents: &str) -> Result<()> {
         let clean_path = path.to_path_buf().clean();
         fs::write(&clean_path, contents)?;
 
@@ -145,24 +173,30 @@ impl Setup {
 
         writeln!(
             out,
-            "Created new prepare-commit-msg githook: {}",
+            "Created new {} githook: {}",
+            Self::hook_name(&clean_path),
             &clean_path.to_string_lossy()
         )?;
 
         Ok(())
     }
 
-    fn backup_prepare_commit_msg_hook(out: &mut impl Write, path: &Path) -> Result<()> {
+    pub(crate) fn backup_hook(out: &mut impl Write, path: &Path) -> Result<()> {
         let clean_path = path.to_path_buf().clean();
         let backup_path = clean_path.with_extension("bak").clean();
         fs::rename(&clean_path, &backup_path)?;
 
         writeln!(
             out,
-            "Backed up existing prepare-commit-msg githook: {}",
+            "Backed up existing {} githook: {}",
+            Self::hook_name(&clean_path),
             &backup_path.to_string_lossy()
         )?;
 
         Ok(())
     }
+
+    fn hook_name(path: &Path) -> std::borrow::Cow<'_, str> {
+        path.file_name().unwrap_or_default().to_string_lossy()
+    }
 }
@@ -0,0 +1,500 @@
+use crate::Result;
+use crate::commands::output_format::{OutputFormat, Record, write_json};
+use crate::repositories::{Commit, CommitLogRepo, Contributor, TeamMember, TeamMemberRepo};
+use chrono::{DateTime, Local, TimeDelta};
+use clap::Parser;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io::Write;
+
+#[derive(Parser)]
+pub(crate) struct Stats {
+    /// Only counts commits in the given revision range
+    ///
+    /// Usage example: git mob stats main..feature
+    #[arg(value_name = "REVISION_RANGE")]
+    pub(crate) revision_range: Option<String>,
+    /// Only counts commits more recent than the given date
+    ///
+    /// Usage example: git mob stats --since "3 months ago"
+    #[arg(long = "since", value_name = "DATE")]
+    pub(crate) since: Option<String>,
+    /// Lists team members who have not paired in the given number of weeks
+    #[arg(long = "unpaired-weeks", value_name = "WEEKS", default_value_t = 4)]
+    pub(crate) unpaired_weeks: u32,
+    /// Format of the output, set by the global --format
+    #[arg(from_global)]
+    pub(crate) format: OutputFormat,
+}
+
+/// Author or co-author of the commits, who is a team member if their email is in the roster
+struct Member {
+    label: String,
+    is_team_member: bool,
+}
+
+/// Number of commits which two members paired on
+#[derive(Serialize)]
+struct PairRow<'a> {
+    member: &'a str,
+    paired_with: &'a str,
+    commits: u32,
+    last_paired_at: Option<DateTime<Local>>,
+}
+
+impl Record for PairRow<'_> {
+    const COLUMNS: &'static [&'static str] =
+        &["member", "paired_with", "commits", "last_paired_at"];
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.member.to_owned(),
+            self.paired_with.to_owned(),
+            self.commits.to_string(),
+            self.last_paired_at
+                .map(|x| x.to_rfc3339())
+                .unwrap_or_default(),
+        ]
+    }
+}
+
+#[derive(Serialize)]
+struct StatsJson<'a> {
+    pairs: Vec<PairRow<'a>>,
+    not_paired: Vec<&'a str>,
+}
+
+#[derive(Default)]
+struct Pairing {
+    commits: u32,
+    last_paired_at: Option<DateTime<Local>>,
+}
+
+struct PairingMatrix {
+    members: Vec<Member>,
+    // keyed by indices of both members in either order
+    pairings: HashMap<(usize, usize), Pairing>,
+}
+
+impl PairingMatrix {
+    fn new(team_members: Vec<TeamMember>, commits: &[Commit]) -> Self {
+        let mut members: Vec<Member> = Vec::new();
+        let mut indices: HashMap<String, usize> = HashMap::new();
+        for team_member in team_members {
+            indices
+                .entry(team_member.email.to_lowercase())
+                .or_insert_with(|| {
+                    members.push(Member {
+                        label: team_member.key,
+                        is_team_member: true,
+                    });
+                    members.len() - 1
+                });
+        }
+
+        let mut pairings: HashMap<(usize, usize), Pairing> = HashMap::new();
+        let mut index_of = |contributor: &Contributor| {
+            *indices
+                .entry(contributor.email.to_lowercase())
+                .or_insert_with(|| {
+                    members.push(Member {
+                        label: contributor.name.to_owned(),
+                        is_team_member: false,
+                    });
+                    members.len() - 1
+                })
+        };
+
+        // everyone in a commit paired with everyone else in it, as co-authors of a mob commit work together too
+        for commit in commits {
+            let mut participants: Vec<usize> = [&commit.author]
+                .into_iter()
+                .chain(&commit.coauthors)
+                .map(&mut index_of)
+                .collect();
+            participants.sort_unstable();
+            participants.dedup();
+
+            for a in participants.iter() {
+                for b in participants.iter().filter(|b| *b != a) {
+                    let pairing = pairings.entry((*a, *b)).or_default();
+                    pairing.commits += 1;
+                    pairing.last_paired_at = pairing.last_paired_at.max(Some(commit.authored_at));
+                }
+            }
+        }
+
+        // members outside the roster are only of interest if they have paired
+        let paired: Vec<bool> = (0..members.len())
+            .map(|x| pairings.keys().any(|(member, _)| *member == x))
+            .collect();
+        let mut kept = Vec::new();
+        let mut new_indices = HashMap::new();
+        for (index, member) in members.into_iter().enumerate() {
+            if member.is_team_member || paired[index] {
+                new_indices.insert(index, kept.len());
+                kept.push(member);
+            }
+        }
+        let pairings = pairings
+            .into_iter()
+            .map(|((a, b), pairing)| ((new_indices[&a], new_indices[&b]), pairing))
+            .collect();
+
+        Self {
+            members: kept,
+            pairings,
+        }
+    }
+
+    fn rows(&self) -> Vec<PairRow<'_>> {
+        let mut rows = Vec::new();
+        for (a, member) in self.members.iter().enumerate() {
+            for (b, paired_with) in self.members.iter().enumerate().filter(|(b, _)| *b != a) {
+                let pairing = self.pairings.get(&(a, b));
+                rows.push(PairRow {
+                    member: &member.label,
+                    paired_with: &paired_with.label,
+                    commits: pairing.map_or(0, |x| x.commits),
+                    last_paired_at: pairing.and_then(|x| x.last_paired_at),
+                });
+            }
+        }
+        rows
+    }
+
+    /// Lists team members who have not paired since the given time
+    fn not_paired_since(&self, since: DateTime<Local>) -> Vec<&str> {
+        self.members
+            .iter()
+            .enumerate()
+            .filter(|(_, member)| member.is_team_member)
+            .filter(|(a, _)| {
+                !self.pairings.iter().any(|((member, _), pairing)| {
+                    member == a && pairing.last_paired_at.is_some_and(|x| x >= since)
+                })
+            })
+            .map(|(_, member)| member.label.as_str())
+            .collect()
+    }
+
+    fn format_matrix(&self) -> String {
+        let label_width = self
+            .members
+            .iter()
+            .map(|x| x.label.chars().count())
+            .max()
+            .unwrap_or_default();
+        let cell = |a: usize, b: usize| match self.pairings.get(&(a, b)) {
+            _ if a == b => "-".to_owned(),
+            Some(pairing) => pairing.commits.to_string(),
+            None => "0".to_owned(),
+        };
+        let widths: Vec<usize> = (0..self.members.len())
+            .map(|b| {
+                (0..self.members.len())
+                    .map(|a| cell(a, b).len())
+                    .chain([self.members[b].label.chars().count()])
+                    .max()
+                    .unwrap_or_default()
+            })
+            .collect();
+
+        let mut output = format!("{:label_width$}", "");
+        for (member, width) in self.members.iter().zip(&widths) {
+            output.push_str(&format!("  {:>width$}", member.label));
+        }
+        output.push('\n');
+        for (a, member) in self.members.iter().enumerate() {
+            output.push_str(&format!("{:label_width$}", member.label));
+            for (b, width) in widths.iter().enumerate() {
+                output.push_str(&format!("  {:>width$}", cell(a, b)));
+            }
+            output.push('\n');
+        }
+        output
+    }
+}
+
+impl Stats {
+    pub(crate) fn handle(
+        &self,
+        team_member_repo: &impl TeamMemberRepo,
+        commit_log_repo: &impl CommitLogRepo,
+        out: &mut impl Write,
+    ) -> Result<()> {
+        let unpaired_since = TimeDelta::try_weeks(self.unpaired_weeks.into())
+            .and_then(|x| Local::now().checked_sub_signed(x))
+            .ok_or_else(|| {
+                format!(
+                    "Invalid number of weeks: {}. It goes back too far",
+                    self.unpaired_weeks
+                )
+            })?;
+
+        let commits =
+            commit_log_repo.list_commits(self.since.as_deref(), self.revision_range.as_deref())?;
+        let matrix = PairingMatrix::new(team_member_repo.list()?, &commits);
+        let not_paired = matrix.not_paired_since(unpaired_since);
+
+        match self.format {
+            OutputFormat::Text => {
+                if matrix.members.is_empty() {
+                    writeln!(out, "No team members or paired commits found")?;
+                    return Ok(());
+                }
+
+                writeln!(out, "Commits paired on:")?;
+                write!(out, "{}", matrix.format_matrix())?;
+                if !not_paired.is_empty() {
+                    writeln!(out)?;
+                    writeln!(out, "Not paired in the last {} weeks:", self.unpaired_weeks)?;
+                    writeln!(out, "{}", not_paired.join("\n"))?;
+                }
+            }
+            OutputFormat::Json => {
+                let stats = StatsJson {
+                    pairs: matrix.rows(),
+                    not_paired,
+                };
+                write_json(out, &stats)?;
+            }
+            // one row per pair of members, as the matrix has no fixed columns
+            format => format.write_records(out, "pairs", &matrix.rows())?,
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::{MockCommitLogRepo, MockTeamMemberRepo, TeamMember};
+
+    fn contributor(name: &str, email: &str) -> Contributor {
+        Contributor {
+            name: name.to_owned(),
+            email: email.to_owned(),
+        }
+    }
+
+    fn create_mock_team_member_repo() -> MockTeamMemberRepo {
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo.expect_list().returning(|| {
+            Ok(vec![
+                TeamMember::new("lm", "Leo Messi", "leo.messi@example.com")?,
+                TeamMember::new("em", "Emi Martinez", "emi.martinez@example.com")?,
+                TeamMember::new("sa", "Sergio Aguero", "sergio.aguero@example.com")?,
+            ])
+        });
+        mock_team_member_repo
+    }
+
+    fn create_mock_commit_log_repo() -> MockCommitLogRepo {
+        let mut mock_commit_log_repo = MockCommitLogRepo::new();
+        mock_commit_log_repo
+            .expect_list_commits()
+            .returning(|_, _| {
+                let now = Local::now();
+                Ok(vec![
+                    Commit {
+                        authored_at: now - TimeDelta::days(1),
+                        author: contributor("Leo Messi", "Leo.Messi@example.com"),
+                        coauthors: vec![
+                            contributor("Emi Martinez", "emi.martinez@example.com"),
+                            contributor("Diego Maradona", "diego.maradona@example.com"),
+                        ],
+                    },
+                    Commit {
+                        authored_at: now - TimeDelta::days(2),
+                        author: contributor("Emi Martinez", "emi.martinez@example.com"),
+                        coauthors: vec![contributor("Leo Messi", "leo.messi@example.com")],
+                    },
+                    Commit {
+                        authored_at: now - TimeDelta::weeks(6),
+                        author: contributor("Sergio Aguero", "sergio.aguero@example.com"),
+                        coauthors: vec![contributor("Leo Messi", "leo.messi@example.com")],
+                    },
+                    Commit {
+                        authored_at: now - TimeDelta::weeks(6),
+                        author: contributor("Angel Di Maria", "angel.dimaria@example.com"),
+                        coauthors: vec![],
+                    },
+                ])
+            });
+        mock_commit_log_repo
+    }
+
+    #[test]
+    fn test_stats() -> Result<()> {
+        let stats_cmd = Stats {
+            revision_range: None,
+            since: None,
+            unpaired_weeks: 4,
+            format: OutputFormat::Text,
+        };
+
+        let mut out = Vec::new();
+        stats_cmd.handle(
+            &create_mock_team_member_repo(),
+            &create_mock_commit_log_repo(),
+            &mut out,
+        )?;
+
+        assert_eq!(
+            String::from_utf8(out)?,
+            "Commits paired on:\n\
+             \x20               lm  em  sa  Diego Maradona\n\
+             lm               -   2   1               1\n\
+             em               2   -   0               1\n\
+             sa               1   0   -               0\n\
+             Diego Maradona   1   1   0               -\n\
+             \n\
+             Not paired in the last 4 weeks:\n\
+             sa\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_stats_given_since_and_revision_range() -> Result<()> {
+        let mut mock_commit_log_repo = MockCommitLogRepo::new();
+        mock_commit_log_repo
+            .expect_list_commits()
+            .withf(|since, revision_range| {
+                *since == Some("2 weeks ago") && *revision_range == Some("main..")
+            })
+            .once()
+            .returning(|_, _| Ok(vec![]));
+
+        let stats_cmd = Stats {
+            revision_range: Some("main..".to_owned()),
+            since: Some("2 weeks ago".to_owned()),
+            unpaired_weeks: 2,
+            format: OutputFormat::Text,
+        };
+
+        let mut out = Vec::new();
+        stats_cmd.handle(
+            &create_mock_team_member_repo(),
+            &mock_commit_log_repo,
+            &mut out,
+        )?;
+
+        assert_eq!(
+            String::from_utf8(out)?,
+            "Commits paired on:\n    lm  em  sa\n\
+             lm   -   0   0\n\
+             em   0   -   0\n\
+             sa   0   0   -\n\
+             \n\
+             Not paired in the last 2 weeks:\n\
+             lm\nem\nsa\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_stats_given_too_many_unpaired_weeks() -> Result<()> {
+        let mut mock_commit_log_repo = MockCommitLogRepo::new();
+        mock_commit_log_repo.expect_list_commits().never();
+
+        let stats_cmd = Stats {
+            revision_range: None,
+            since: None,
+            unpaired_weeks: u32::MAX,
+            format: OutputFormat::Text,
+        };
+
+        let mut out = Vec::new();
+        let result = stats_cmd.handle(&MockTeamMemberRepo::new(), &mock_commit_log_repo, &mut out);
+
+        assert!(result.is_err_and(|x| x.to_string()
+            == format!(
+                "Invalid number of weeks: {}. It goes back too far",
+                u32::MAX
+            )));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_stats_given_csv_format() -> Result<()> {
+        let stats_cmd = Stats {
+            revision_range: None,
+            since: None,
+            unpaired_weeks: 4,
+            format: OutputFormat::Csv,
+        };
+
+        let mut out = Vec::new();
+        stats_cmd.handle(
+            &create_mock_team_member_repo(),
+            &create_mock_commit_log_repo(),
+            &mut out,
+        )?;
+
+        let output = String::from_utf8(out)?;
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 13);
+        assert_eq!(lines[0], "member,paired_with,commits,last_paired_at");
+        assert!(lines[1].starts_with("lm,em,2,"));
+        assert_eq!(lines[5], "em,sa,0,");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_stats_given_json_format() -> Result<()> {
+        let stats_cmd = Stats {
+            revision_range: None,
+            since: None,
+            unpaired_weeks: 4,
+            format: OutputFormat::Json,
+        };
+
+        let mut out = Vec::new();
+        stats_cmd.handle(
+            &create_mock_team_member_repo(),
+            &create_mock_commit_log_repo(),
+            &mut out,
+        )?;
+
+        let stats: serde_json::Value = serde_json::from_slice(&out)?;
+        assert_eq!(stats["version"], 1);
+        assert_eq!(stats["pairs"].as_array().map(|x| x.len()), Some(12));
+        assert_eq!(stats["pairs"][0]["member"], "lm");
+        assert_eq!(stats["pairs"][0]["paired_with"], "em");
+        assert_eq!(stats["pairs"][0]["commits"], 2);
+        assert_eq!(stats["not_paired"], serde_json::json!(["sa"]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_stats_when_no_team_members_or_commits() -> Result<()> {
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo.expect_list().returning(|| Ok(vec![]));
+        let mut mock_commit_log_repo = MockCommitLogRepo::new();
+        mock_commit_log_repo
+            .expect_list_commits()
+            .returning(|_, _| Ok(vec![]));
+
+        let stats_cmd = Stats {
+            revision_range: None,
+            since: None,
+            unpaired_weeks: 4,
+            format: OutputFormat::Text,
+        };
+
+        let mut out = Vec::new();
+        stats_cmd.handle(&mock_team_member_repo, &mock_commit_log_repo, &mut out)?;
+
+        assert_eq!(out, b"No team members or paired commits found\n");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,399 @@
+use crate::Result;
+use crate::commands::Mob;
+use crate::commands::mob::CoauthorResolver;
+use crate::commands::output_format::OutputFormat;
+use crate::repositories::{
+    CommitLogRepo, HistoryRepo, MobSessionRepo, PresetRepo, TeamGroupRepo, TeamMember,
+    TeamMemberRepo,
+};
+use chrono::{DateTime, Local};
+use clap::Parser;
+use inquire::Select;
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{IsTerminal, Write, stdin};
+
+#[derive(Parser)]
+pub(crate) struct SuggestPair {
+    /// Suggests pairs for the team member instead of the current user
+    ///
+    /// Usage example: git mob suggest-pair --for lm
+    #[arg(long = "for", value_name = "TEAM_MEMBER_KEY")]
+    pub(crate) for_key: Option<String>,
+    /// Uses the mob/pair programming session of the current repository instead of the global one
+    ///
+    /// Usage example: git mob suggest-pair --local
+    #[arg(long = "local")]
+    pub(crate) local: bool,
+}
+
+/// Team member with the time they last co-authored a commit with the person looking for a pair
+struct Suggestion {
+    team_member: TeamMember,
+    last_paired_at: Option<DateTime<Local>>,
+}
+
+impl fmt::Display for Suggestion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.last_paired_at {
+            Some(last_paired_at) => write!(
+                f,
+                "{} {} (last paired {})",
+                self.team_member.key,
+                self.team_member,
+                last_paired_at.format("%Y-%m-%d")
+            ),
+            None => write!(
+                f,
+                "{} {} (never paired)",
+                self.team_member.key, self.team_member
+            ),
+        }
+    }
+}
+
+impl SuggestPair {
+    #[allow(clippy::too_many_arguments)] // one repository per kind of stored data
+    pub(crate) fn handle(
+        &self,
+        team_member_repo: &impl TeamMemberRepo,
+        team_group_repo: &impl TeamGroupRepo,
+        mob_repo: &impl MobSessionRepo,
+        preset_repo: &impl PresetRepo,
+        commit_log_repo: &impl CommitLogRepo,
+        history_repo: &impl HistoryRepo,
+        out: &mut impl Write,
+    ) -> Result<()> {
+        let suggestions = self.suggest(team_member_repo, commit_log_repo, out)?;
+
+        // only offers to start the session when someone can answer the prompt
+        if !stdin().is_terminal() {
+            return Ok(());
+        }
+        let result = Select::new("Select who to pair with:", suggestions).prompt_skippable()?;
+        if let Some(suggestion) = result {
+            let mut keys = Vec::from_iter(self.for_key.clone());
+            keys.push(suggestion.team_member.key);
+
+            let mob = Mob {
+                with: Some(keys),
+                add: None,
+                clear: false,
+                driver: None,
+                list: false,
+                trailers: false,
+                local: self.local,
+                expire_after: None,
+                allow_unusual: false,
+                verbose: false,
+                format: OutputFormat::Text,
+            };
+            mob.handle(
+                team_member_repo,
+                team_group_repo,
+                mob_repo,
+                preset_repo,
+                history_repo,
+                out,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Lists suggested pairs for the team member or the current user
+    fn suggest(
+        &self,
+        team_member_repo: &impl TeamMemberRepo,
+        commit_log_repo: &impl CommitLogRepo,
+        out: &mut impl Write,
+    ) -> Result<Vec<Suggestion>> {
+        let (name, email) = match self.for_key.as_deref() {
+            Some(token) => {
+                let team_member =
+                    CoauthorResolver::new(team_member_repo, false)?.resolve_team_member(token)?;
+                (team_member.name, team_member.email)
+            }
+            None => match commit_log_repo.get_user()? {
+                Some(user) => (user.name, user.email),
+                None => {
+                    return Err(
+                        "Failed to find the current user. Set user.name and user.email git config or use --for"
+                            .into(),
+                    );
+                }
+            },
+        };
+
+        let suggestions = Self::rank(
+            &email,
+            team_member_repo.list()?,
+            &Self::last_paired(&email, commit_log_repo)?,
+        );
+        if suggestions.is_empty() {
+            return Err("No other team member(s) found to pair with".into());
+        }
+
+        writeln!(
+            out,
+            "Suggested pairs for {name} <{email}>, least recently paired first:"
+        )?;
+        for suggestion in suggestions.iter() {
+            writeln!(out, "{suggestion}")?;
+        }
+
+        Ok(suggestions)
+    }
+
+    /// Gets the time each person (by lowercase email) last co-authored a commit with the given person
+    fn last_paired(
+        email: &str,
+        commit_log_repo: &impl CommitLogRepo,
+    ) -> Result<HashMap<String, DateTime<Local>>> {
+        let email = email.to_lowercase();
+        let mut last_paired: HashMap<String, DateTime<Local>> = HashMap::new();
+
+        for commit in commit_log_repo.list_commits(None, None)? {
+            let emails: Vec<String> = [&commit.author]
+                .into_iter()
+                .chain(&commit.coauthors)
+                .map(|x| x.email.to_lowercase())
+                .collect();
+            if !emails.contains(&email) {
+                continue;
+            }
+
+            for other in emails.into_iter().filter(|x| *x != email) {
+                let paired_at = last_paired.entry(other).or_insert(commit.authored_at);
+                *paired_at = (*paired_at).max(commit.authored_at);
+            }
+        }
+
+        Ok(last_paired)
+    }
+
+    /// Ranks team members other than the given person, starting with the ones never paired with
+    /// and then the least recently paired with
+    fn rank(
+        email: &str,
+        team_members: Vec<TeamMember>,
+        last_paired: &HashMap<String, DateTime<Local>>,
+    ) -> Vec<Suggestion> {
+        let mut suggestions: Vec<Suggestion> = team_members
+            .into_iter()
+            .filter(|x| !x.email.eq_ignore_ascii_case(email))
+            .map(|team_member| Suggestion {
+                last_paired_at: last_paired.get(&team_member.email.to_lowercase()).copied(),
+                team_member,
+            })
+            .collect();
+
+        // stable sort keeps the roster order among team members never paired with
+        suggestions.sort_by_key(|x| x.last_paired_at);
+        suggestions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::{Commit, Contributor, MockCommitLogRepo, MockTeamMemberRepo};
+    use chrono::TimeZone;
+
+    fn contributor(name: &str, email: &str) -> Contributor {
+        Contributor {
+            name: name.to_owned(),
+            email: email.to_owned(),
+        }
+    }
+
+    fn create_mock_team_member_repo() -> MockTeamMemberRepo {
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo
+            .expect_list_aliases()
+            .returning(|| Ok(vec![]));
+        mock_team_member_repo.expect_list().returning(|| {
+            Ok(vec![
+                TeamMember::new("lm", "Leo Messi", "leo.messi@example.com")?,
+                TeamMember::new("em", "Emi Martinez", "emi.martinez@example.com")?,
+                TeamMember::new("sa", "Sergio Aguero", "sergio.aguero@example.com")?,
+                TeamMember::new("adm", "Angel Di Maria", "angel.dimaria@example.com")?,
+            ])
+        });
+        mock_team_member_repo
+            .expect_get()
+            .withf(|key| key == "em")
+            .returning(|_| {
+                Ok(Some(TeamMember::new(
+                    "em",
+                    "Emi Martinez",
+                    "emi.martinez@example.com",
+                )?))
+            });
+        mock_team_member_repo
+    }
+
+    fn create_mock_commit_log_repo() -> MockCommitLogRepo {
+        let mut mock_commit_log_repo = MockCommitLogRepo::new();
+        mock_commit_log_repo
+            .expect_list_commits()
+            .withf(|since, revision_range| since.is_none() && revision_range.is_none())
+            .returning(|_, _| {
+                Ok(vec![
+                    Commit {
+                        authored_at: Local.with_ymd_and_hms(2026, 10, 17, 9, 0, 0).unwrap(),
+                        author: contributor("Leo Messi", "leo.messi@example.com"),
+                        coauthors: vec![contributor("Emi Martinez", "Emi.Martinez@example.com")],
+                    },
+                    Commit {
+                        authored_at: Local.with_ymd_and_hms(2026, 9, 1, 9, 0, 0).unwrap(),
+                        author: contributor("Sergio Aguero", "sergio.aguero@example.com"),
+                        coauthors: vec![contributor("Leo Messi", "leo.messi@example.com")],
+                    },
+                    Commit {
+                        authored_at: Local.with_ymd_and_hms(2026, 8, 1, 9, 0, 0).unwrap(),
+                        author: contributor("Leo Messi", "leo.messi@example.com"),
+                        coauthors: vec![contributor("Emi Martinez", "emi.martinez@example.com")],
+                    },
+                    Commit {
+                        authored_at: Local.with_ymd_and_hms(2026, 7, 1, 9, 0, 0).unwrap(),
+                        author: contributor("Angel Di Maria", "angel.dimaria@example.com"),
+                        coauthors: vec![contributor("Emi Martinez", "emi.martinez@example.com")],
+                    },
+                ])
+            });
+        mock_commit_log_repo
+    }
+
+    #[test]
+    fn test_suggest_pair_for_current_user() -> Result<()> {
+        let mut mock_commit_log_repo = create_mock_commit_log_repo();
+        mock_commit_log_repo
+            .expect_get_user()
+            .once()
+            .returning(|| Ok(Some(contributor("Leo Messi", "leo.messi@example.com"))));
+
+        let suggest_pair_cmd = SuggestPair {
+            for_key: None,
+            local: false,
+        };
+
+        let mut out = Vec::new();
+        suggest_pair_cmd.suggest(
+            &create_mock_team_member_repo(),
+            &mock_commit_log_repo,
+            &mut out,
+        )?;
+
+        assert_eq!(
+            String::from_utf8(out)?,
+            "Suggested pairs for Leo Messi <leo.messi@example.com>, least recently paired first:\n\
+             adm Angel Di Maria <angel.dimaria@example.com> (never paired)\n\
+             sa Sergio Aguero <sergio.aguero@example.com> (last paired 2026-09-01)\n\
+             em Emi Martinez <emi.martinez@example.com> (last paired 2026-10-17)\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_suggest_pair_for_team_member() -> Result<()> {
+        let suggest_pair_cmd = SuggestPair {
+            for_key: Some("em".to_owned()),
+            local: false,
+        };
+
+        let mut out = Vec::new();
+        suggest_pair_cmd.suggest(
+            &create_mock_team_member_repo(),
+            &create_mock_commit_log_repo(),
+            &mut out,
+        )?;
+
+        assert_eq!(
+            String::from_utf8(out)?,
+            "Suggested pairs for Emi Martinez <emi.martinez@example.com>, least recently paired first:\n\
+             sa Sergio Aguero <sergio.aguero@example.com> (never paired)\n\
+             adm Angel Di Maria <angel.dimaria@example.com> (last paired 2026-07-01)\n\
+             lm Leo Messi <leo.messi@example.com> (last paired 2026-10-17)\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_suggest_pair_when_team_member_not_found() -> Result<()> {
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo
+            .expect_list_aliases()
+            .returning(|| Ok(vec![]));
+        mock_team_member_repo.expect_list().returning(|| Ok(vec![]));
+        mock_team_member_repo.expect_get().returning(|_| Ok(None));
+
+        let suggest_pair_cmd = SuggestPair {
+            for_key: Some("jk".to_owned()),
+            local: false,
+        };
+
+        let mut out = Vec::new();
+        let result =
+            suggest_pair_cmd.suggest(&mock_team_member_repo, &MockCommitLogRepo::new(), &mut out);
+
+        assert!(result.is_err_and(|x| x.to_string() == "No team member found with key: jk"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_suggest_pair_when_current_user_not_found() -> Result<()> {
+        let mut mock_commit_log_repo = MockCommitLogRepo::new();
+        mock_commit_log_repo
+            .expect_get_user()
+            .returning(|| Ok(None));
+
+        let suggest_pair_cmd = SuggestPair {
+            for_key: None,
+            local: false,
+        };
+
+        let mut out = Vec::new();
+        let result =
+            suggest_pair_cmd.suggest(&MockTeamMemberRepo::new(), &mock_commit_log_repo, &mut out);
+
+        assert!(result.is_err_and(|x| x.to_string()
+            == "Failed to find the current user. Set user.name and user.email git config or use --for"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_suggest_pair_when_no_other_team_members() -> Result<()> {
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo.expect_list().returning(|| {
+            Ok(vec![TeamMember::new(
+                "lm",
+                "Leo Messi",
+                "leo.messi@example.com",
+            )?])
+        });
+        let mut mock_commit_log_repo = create_mock_commit_log_repo();
+        mock_commit_log_repo
+            .expect_get_user()
+            .returning(|| Ok(Some(contributor("Leo Messi", "leo.messi@example.com"))));
+
+        let suggest_pair_cmd = SuggestPair {
+            for_key: None,
+            local: false,
+        };
+
+        let mut out = Vec::new();
+        let result =
+            suggest_pair_cmd.suggest(&mock_team_member_repo, &mock_commit_log_repo, &mut out);
+
+        assert!(
+            result.is_err_and(|x| x.to_string() == "No other team member(s) found to pair with")
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,265 @@
+use crate::Result;
+use crate::commands::mob::CoauthorResolver;
+use crate::repositories::{TeamGroup, TeamGroupRepo, TeamMemberRepo};
+use clap::Parser;
+use std::io::Write;
+
+#[derive(Parser)]
+#[command(arg_required_else_help = true)]
+pub(crate) struct Team {
+    /// Adds team group with the given team member(s), replacing any existing group with the same name
+    ///
+    /// The group can then be used with --with e.g. git mob --with @frontend
+    ///
+    /// Usage example: git mob team --add frontend lm em sa
+    #[arg(short = 'a', long = "add", num_args=2.., value_names=["GROUP_NAME", "TEAM_MEMBER_KEY"])]
+    pub(crate) add: Option<Vec<String>>,
+    /// Removes team group
+    ///
+    /// Usage example: git mob team --delete frontend
+    #[arg(short = 'd', long = "delete", value_name = "GROUP_NAME")]
+    pub(crate) delete: Option<String>,
+    /// Lists team group(s) with the keys of their team member(s)
+    ///
+    /// Usage example: git mob team --list
+    #[arg(short = 'l', long = "list")]
+    pub(crate) list: bool,
+}
+
+impl Team {
+    pub(crate) fn handle(
+        &self,
+        team_member_repo: &impl TeamMemberRepo,
+        team_group_repo: &impl TeamGroupRepo,
+        out: &mut impl Write,
+    ) -> Result<()> {
+        if let Some(name) = self.delete.as_deref() {
+            match team_group_repo.get(name)? {
+                Some(_) => team_group_repo.remove(name)?,
+                None => return Err(format!("No team group found with name: {name}").into()),
+            }
+        }
+        if self.list {
+            for team_group in team_group_repo.list()? {
+                writeln!(out, "@{} {}", team_group.name, team_group.keys.join(" "))?;
+            }
+        }
+        if let Some([name, keys @ ..]) = self.add.as_deref() {
+            // group members are stored by key so that they are never guessed when the group is used
+            let mut resolver = CoauthorResolver::new(team_member_repo, false)?;
+            let mut unique_keys: Vec<String> = Vec::new();
+            for token in keys {
+                let key = resolver.resolve_team_member(token)?.key;
+                if !unique_keys.contains(&key) {
+                    unique_keys.push(key);
+                }
+            }
+
+            let team_group = TeamGroup::new(name, &unique_keys)?;
+            team_group_repo.add(&team_group)?;
+            writeln!(out, "{team_group}")?
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::{MockTeamGroupRepo, MockTeamMemberRepo, TeamMember};
+    use mockall::predicate;
+
+    fn create_mock_team_member_repo() -> MockTeamMemberRepo {
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo
+            .expect_get()
+            .returning(|key| match key {
+                "lm" => Ok(Some(TeamMember::parse(
+                    key,
+                    "Leo Messi <leo.messi@example.com>",
+                )?)),
+                "em" => Ok(Some(TeamMember::parse(
+                    key,
+                    "Emi Martinez <emi.martinez@example.com>",
+                )?)),
+                _ => Ok(None),
+            });
+        mock_team_member_repo
+            .expect_list_aliases()
+            .returning(|| Ok(vec![]));
+        mock_team_member_repo.expect_list().returning(|| {
+            Ok(vec![
+                TeamMember::parse("lm", "Leo Messi <leo.messi@example.com>")?,
+                TeamMember::parse("em", "Emi Martinez <emi.martinez@example.com>")?,
+            ])
+        });
+        mock_team_member_repo
+    }
+
+    #[test]
+    fn test_add_team_group() -> Result<()> {
+        let mut mock_team_group_repo = MockTeamGroupRepo::new();
+        mock_team_group_repo
+            .expect_add()
+            .with(predicate::eq(TeamGroup::new(
+                "frontend",
+                &["lm".into(), "em".into()],
+            )?))
+            .once()
+            .returning(|_| Ok(()));
+
+        let team_cmd = Team {
+            add: Some(vec![
+                "frontend".into(),
+                "lm".into(),
+                "em".into(),
+                "lm".into(),
+            ]),
+            delete: None,
+            list: false,
+        };
+
+        let mut out = Vec::new();
+        team_cmd.handle(
+            &create_mock_team_member_repo(),
+            &mock_team_group_repo,
+            &mut out,
+        )?;
+
+        assert_eq!(String::from_utf8(out)?, "@frontend (lm, em)\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_team_group_given_key_prefix_and_email() -> Result<()> {
+        let mut mock_team_group_repo = MockTeamGroupRepo::new();
+        mock_team_group_repo
+            .expect_add()
+            .with(predicate::eq(TeamGroup::new(
+                "frontend",
+                &["lm".into(), "em".into()],
+            )?))
+            .once()
+            .returning(|_| Ok(()));
+
+        let team_cmd = Team {
+            add: Some(vec![
+                "frontend".into(),
+                "l".into(),
+                "Emi.Martinez@example.com".into(),
+            ]),
+            delete: None,
+            list: false,
+        };
+
+        let mut out = Vec::new();
+        team_cmd.handle(
+            &create_mock_team_member_repo(),
+            &mock_team_group_repo,
+            &mut out,
+        )?;
+
+        assert_eq!(String::from_utf8(out)?, "@frontend (lm, em)\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_team_group_when_team_member_not_found() -> Result<()> {
+        let mut mock_team_group_repo = MockTeamGroupRepo::new();
+        mock_team_group_repo.expect_add().never();
+
+        let team_cmd = Team {
+            add: Some(vec!["frontend".into(), "lm".into(), "sa".into()]),
+            delete: None,
+            list: false,
+        };
+
+        let mut out = Vec::new();
+        let result = team_cmd.handle(
+            &create_mock_team_member_repo(),
+            &mock_team_group_repo,
+            &mut out,
+        );
+
+        assert!(result.is_err_and(|x| x.to_string() == "No team member found with key: sa"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_delete_team_group() -> Result<()> {
+        let mut mock_team_group_repo = MockTeamGroupRepo::new();
+        mock_team_group_repo
+            .expect_get()
+            .with(predicate::eq("frontend"))
+            .once()
+            .returning(|name| Ok(Some(TeamGroup::new(name, &["lm".into()])?)));
+        mock_team_group_repo
+            .expect_remove()
+            .with(predicate::eq("frontend"))
+            .once()
+            .returning(|_| Ok(()));
+
+        let team_cmd = Team {
+            add: None,
+            delete: Some("frontend".into()),
+            list: false,
+        };
+
+        let mut out = Vec::new();
+        team_cmd.handle(&MockTeamMemberRepo::new(), &mock_team_group_repo, &mut out)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_delete_team_group_when_not_found() -> Result<()> {
+        let mut mock_team_group_repo = MockTeamGroupRepo::new();
+        mock_team_group_repo
+            .expect_get()
+            .with(predicate::eq("frontend"))
+            .once()
+            .returning(|_| Ok(None));
+        mock_team_group_repo.expect_remove().never();
+
+        let team_cmd = Team {
+            add: None,
+            delete: Some("frontend".into()),
+            list: false,
+        };
+
+        let mut out = Vec::new();
+        let result = team_cmd.handle(&MockTeamMemberRepo::new(), &mock_team_group_repo, &mut out);
+
+        assert!(result.is_err_and(|x| x.to_string() == "No team group found with name: frontend"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_team_groups() -> Result<()> {
+        let mut mock_team_group_repo = MockTeamGroupRepo::new();
+        mock_team_group_repo.expect_list().once().returning(|| {
+            Ok(vec![
+                TeamGroup::new("frontend", &["lm".into(), "em".into()])?,
+                TeamGroup::new("backend", &["jh".into()])?,
+            ])
+        });
+
+        let team_cmd = Team {
+            add: None,
+            delete: None,
+            list: true,
+        };
+
+        let mut out = Vec::new();
+        team_cmd.handle(&MockTeamMemberRepo::new(), &mock_team_group_repo, &mut out)?;
+
+        assert_eq!(String::from_utf8(out)?, "@frontend lm em\n@backend jh\n");
+
+        Ok(())
+    }
+}
@@ -1,85 +1,809 @@
 use crate::Result;
-use crate::repositories::TeamMemberRepo;
-use clap::{Parser, arg};
+use crate::commands::importers::ImportSource;
+use crate::commands::output_format::{OutputFormat, Record};
+use crate::commands::roster_format::RosterFormat;
+use crate::helpers::CommandRunner;
+use crate::repositories::{
+    self, CommitLogRepo, Contributor, PresetRepo, TeamGroupRepo, TeamMemberRepo, TeamMemberSource,
+};
+use crate::validation;
+use clap::{ArgGroup, Parser, ValueEnum};
+use inquire::{MultiSelect, Text};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
 use std::io::Write;
+use std::path::{Path, PathBuf};
 
 #[derive(Parser)]
 #[command(arg_required_else_help = true)]
+#[command(group(ArgGroup::new("roster_file").args(["export", "import"])))]
+#[command(group(ArgGroup::new("changes").args(["add", "edit", "alias", "delete", "hide", "import", "import_from_log"]).multiple(true)))]
 pub(crate) struct TeamMember {
     /// Adds team member to team member repository
     ///
     /// Usage example: git mob team-member --add lm "Leo Messi" leo.messi@example.com
     #[arg(short = 'a', long = "add", num_args=3, value_names=["TEAM_MEMBER_KEY", "TEAM_MEMBER_NAME", "TEAM_MEMBER_EMAIL"])]
     pub(crate) add: Option<Vec<String>>,
+    /// Overwrites the team member if the key given to --add already exists
+    #[arg(long = "force", requires = "add")]
+    pub(crate) force: bool,
+    /// Edits team member, prompting for the key, name and email if none of them is given
+    ///
+    /// Team groups and presets which reference the team member are updated too.
+    ///
+    /// Usage example: git mob team-member --edit lm --email leo@example.com
+    #[arg(short = 'e', long = "edit", value_name = "TEAM_MEMBER_KEY")]
+    pub(crate) edit: Option<String>,
+    /// Sets the new key of the edited team member
+    #[arg(long = "key", value_name = "TEAM_MEMBER_KEY", requires = "edit")]
+    pub(crate) new_key: Option<String>,
+    /// Sets the new name of the edited team member
+    #[arg(long = "name", value_name = "TEAM_MEMBER_NAME", requires = "edit")]
+    pub(crate) name: Option<String>,
+    /// Sets the new email of the edited team member
+    #[arg(long = "email", value_name = "TEAM_MEMBER_EMAIL", requires = "edit")]
+    pub(crate) email: Option<String>,
+    /// Adds alias which can be used instead of the key of the team member
+    ///
+    /// Usage example: git mob team-member --alias leo lm
+    #[arg(long = "alias", num_args = 2, value_names = ["ALIAS", "TEAM_MEMBER_KEY"])]
+    pub(crate) alias: Option<Vec<String>>,
     /// Remove team member from team member repository
     ///
+    /// Their aliases are removed too. An alias can be given to only remove the alias.
+    ///
     /// Usage example: git mob team-member --delete lm
     #[arg(short = 'd', long = "delete", value_name = "TEAM_MEMBER_KEY")]
     pub(crate) delete: Option<String>,
+    /// Hides team member which comes from a further layer of the roster (e.g. system roster)
+    ///
+    /// Usage example: git mob team-member --hide lm
+    #[arg(long = "hide", value_name = "TEAM_MEMBER_KEY")]
+    pub(crate) hide: Option<String>,
+    /// Writes the changes to the roster file committed in the current repository instead of your global team members
+    ///
+    /// Usage example: git mob team-member --add lm "Leo Messi" leo.messi@example.com --repo
+    #[arg(long = "repo", requires = "changes", conflicts_with = "system")]
+    pub(crate) repo: bool,
+    /// Writes the changes to the system-wide roster file instead of your global team members
+    ///
+    /// Usage example: git mob team-member --hide lm --system
+    #[arg(long = "system", requires = "changes")]
+    pub(crate) system: bool,
     /// Lists team member(s) with keys(s) from team member repository
     ///
+    /// The layer of the roster (repo, user, git-coauthors or system) which each team member comes from is shown too,
+    /// followed by their aliases
+    ///
     /// Usage example: git mob team-member --list
     #[arg(short = 'l', long = "list")]
     pub(crate) list: bool,
+    /// Imports team member(s) from authors and co-authors of commits in the current repository
+    ///
+    /// Team members are deduplicated by email and given keys based on their initials.
+    /// The team member(s) to import are confirmed with a multi-select prompt.
+    ///
+    /// Usage example: git mob team-member --import-from-log --since "6 months ago" main
+    #[arg(long = "import-from-log")]
+    pub(crate) import_from_log: bool,
+    /// Only imports from commits more recent than the given date
+    #[arg(long = "since", value_name = "DATE", requires = "import_from_log")]
+    pub(crate) since: Option<String>,
+    /// Only imports from commits in the given revision range
+    #[arg(value_name = "REVISION_RANGE", requires = "import_from_log")]
+    pub(crate) revision_range: Option<String>,
+    /// Exports team member(s) from team member repository to stdout
+    ///
+    /// Usage example: git mob team-member --export --roster-format csv > roster.csv
+    #[arg(long = "export")]
+    pub(crate) export: bool,
+    /// Imports team member(s) from a file to team member repository
+    ///
+    /// The file can be omitted when importing from another tool with --from.
+    ///
+    /// Usage example: git mob team-member --import roster.json --on-conflict rename
+    #[arg(long = "import", value_name = "FILE", num_args = 0..=1)]
+    pub(crate) import: Option<Option<PathBuf>>,
+    /// Imports from the author file or git config of another pair/mob programming tool
+    ///
+    /// Entries which cannot be converted (e.g. without an email address) are reported and skipped.
+    ///
+    /// Usage example: git mob team-member --import --from git-duet
+    #[arg(
+        long = "from",
+        value_enum,
+        value_name = "TOOL",
+        requires = "import",
+        conflicts_with = "roster_format"
+    )]
+    pub(crate) from: Option<ImportSource>,
+    /// Format of the output of --list, set by the global --format
+    #[arg(from_global)]
+    pub(crate) format: OutputFormat,
+    /// Sets format of the exported or imported team member(s)
+    ///
+    /// Defaults to the extension of the imported file or json.
+    ///
+    /// Usage example: git mob team-member --export --roster-format git-coauthors
+    #[arg(
+        long = "roster-format",
+        value_enum,
+        value_name = "FORMAT",
+        requires = "roster_file"
+    )]
+    pub(crate) roster_format: Option<RosterFormat>,
+    /// Sets what to do with an imported team member whose key already exists
+    #[arg(long = "on-conflict", value_enum, value_name = "STRATEGY", default_value_t = OnConflict::Skip, requires = "import")]
+    pub(crate) on_conflict: OnConflict,
+    /// Shows changes the import would make without making them
+    #[arg(long = "dry-run", requires = "import")]
+    pub(crate) dry_run: bool,
+    /// Allows unusual but valid keys, names and emails of added, edited or imported team member(s)
+    ///
+    /// e.g. emails without a domain name, names wrapped in quotes or keys with underscores (in a roster file)
+    ///
+    /// Usage example: git mob team-member --add jd "John Doe" john@localhost --allow-unusual
+    #[arg(long = "allow-unusual")]
+    pub(crate) allow_unusual: bool,
+}
+
+/// What to do with an imported team member whose key already exists
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub(crate) enum OnConflict {
+    /// Keep the existing team member
+    Skip,
+    /// Replace the existing team member
+    Overwrite,
+    /// Add the imported team member with a new key
+    Rename,
+}
+
+/// Team member in the structured output formats of --list
+#[derive(Serialize)]
+struct TeamMemberRecord {
+    key: String,
+    name: String,
+    email: String,
+    source: Option<String>,
+    aliases: Vec<String>,
+}
+
+impl Record for TeamMemberRecord {
+    const COLUMNS: &'static [&'static str] = &["key", "name", "email", "source", "aliases"];
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.key.to_owned(),
+            self.name.to_owned(),
+            self.email.to_owned(),
+            self.source.clone().unwrap_or_default(),
+            self.aliases.join(","),
+        ]
+    }
 }
 
 impl TeamMember {
     pub(crate) fn handle(
         &self,
         team_member_repo: &impl TeamMemberRepo,
+        team_group_repo: &impl TeamGroupRepo,
+        preset_repo: &impl PresetRepo,
+        commit_log_repo: &impl CommitLogRepo,
+        command_runner: &impl CommandRunner,
         out: &mut impl Write,
     ) -> Result<()> {
+        if self.repo {
+            team_member_repo.write_to(TeamMemberSource::Repo)?;
+        } else if self.system {
+            team_member_repo.write_to(TeamMemberSource::System)?;
+        }
         if let Some(key) = self.delete.as_deref() {
+            let aliases = team_member_repo.list_aliases()?;
+            if aliases.iter().any(|(alias, _)| alias == key) {
+                team_member_repo.remove_alias(key)?;
+            } else {
+                match team_member_repo.get(key)? {
+                    Some(_) => team_member_repo.remove(key)?,
+                    None => return Err(format!("No team member found with key: {key}").into()),
+                }
+                for (alias, _) in aliases.iter().filter(|(_, x)| x == key) {
+                    team_member_repo.remove_alias(alias)?;
+                }
+                // a team member of another roster with the same key keeps its team groups
+                if team_member_repo.get(key)?.is_none() {
+                    Self::remove_from_team_groups(key, team_group_repo, out)?;
+                }
+            }
+        }
+        if let Some(key) = self.hide.as_deref() {
             match team_member_repo.get(key)? {
-                Some(_) => team_member_repo.remove(key)?,
+                Some(team_member) => team_member_repo.hide(&team_member.key)?,
                 None => return Err(format!("No team member found with key: {key}").into()),
             }
         }
         if self.list {
-            let team_members = team_member_repo.list(true)?;
-            if !team_members.is_empty() {
-                writeln!(out, "{}", team_members.join("\n"))?
+            let aliases = team_member_repo.list_aliases()?;
+            let aliases_of = |key: &str| -> Vec<String> {
+                aliases
+                    .iter()
+                    .filter(|(_, x)| x == key)
+                    .map(|(alias, _)| alias.to_owned())
+                    .collect()
+            };
+
+            match self.format {
+                OutputFormat::Text => {
+                    for team_member in team_member_repo.list()? {
+                        match team_member.source {
+                            Some(source) => {
+                                writeln!(out, "{} {team_member} ({source})", team_member.key)?
+                            }
+                            None => writeln!(out, "{} {team_member}", team_member.key)?,
+                        }
+                        let team_member_aliases = aliases_of(&team_member.key);
+                        if !team_member_aliases.is_empty() {
+                            writeln!(out, "  aliases: {}", team_member_aliases.join(", "))?;
+                        }
+                    }
+                }
+                format => {
+                    let records: Vec<TeamMemberRecord> = team_member_repo
+                        .list()?
+                        .into_iter()
+                        .map(|x| TeamMemberRecord {
+                            aliases: aliases_of(&x.key),
+                            source: x.source.map(|source| source.to_string()),
+                            key: x.key,
+                            name: x.name,
+                            email: x.email,
+                        })
+                        .collect();
+                    format.write_records(out, "team_members", &records)?;
+                }
+            }
+        }
+        if let Some([alias, key]) = self.alias.as_deref() {
+            if !validation::is_identifier(alias) {
+                return Err(format!(
+                    "Invalid alias: {alias}. It must start with a letter and only contain letters, digits and hyphens"
+                )
+                .into());
+            }
+            if let Some(team_member) = team_member_repo.get(alias)? {
+                return Err(
+                    format!("{alias} is already the key or an alias of {team_member}").into(),
+                );
             }
+            let team_member = team_member_repo
+                .get(key)?
+                .ok_or_else(|| format!("No team member found with key: {key}"))?;
+
+            team_member_repo.add_alias(alias, &team_member.key)?;
+            writeln!(out, "{alias} {team_member}")?
         }
         if let Some([key, name, email]) = self.add.as_deref() {
-            let team_member = format!("{name} <{email}>");
-            team_member_repo.add(key, &team_member)?;
+            let team_member = repositories::TeamMember::new(key, name, email)?;
+            if !self.allow_unusual {
+                team_member.check_usual()?;
+            }
+            if !self.force && team_member_repo.get(&team_member.key)?.is_some() {
+                return Err(format!(
+                    "Team member with key {} already exists. Use --force to overwrite it",
+                    team_member.key
+                )
+                .into());
+            }
+            team_member_repo.add(&team_member)?;
             writeln!(out, "{team_member}")?
         }
+        if let Some(key) = self.edit.as_deref() {
+            self.edit(key, team_member_repo, team_group_repo, preset_repo, out)?;
+        }
+        if self.import_from_log {
+            self.import_from_log(team_member_repo, commit_log_repo, out)?;
+        }
+        if self.export {
+            let format = self.roster_format.unwrap_or_default();
+            write!(out, "{}", format.serialize(&team_member_repo.list()?)?)?;
+        }
+        if let Some(path) = &self.import {
+            self.import(team_member_repo, command_runner, path.as_deref(), out)?;
+        }
+
+        Ok(())
+    }
+
+    /// Removes the deleted team member from the team groups which reference them by key, removing
+    /// the groups which are left empty
+    fn remove_from_team_groups(
+        key: &str,
+        team_group_repo: &impl TeamGroupRepo,
+        out: &mut impl Write,
+    ) -> Result<()> {
+        for team_group in team_group_repo.list()? {
+            if !team_group.keys.iter().any(|x| x == key) {
+                continue;
+            }
+            let keys: Vec<String> = team_group.keys.into_iter().filter(|x| x != key).collect();
+            if keys.is_empty() {
+                team_group_repo.remove(&team_group.name)?;
+                writeln!(out, "Removed team group @{}", team_group.name)?;
+            } else {
+                team_group_repo.add(&repositories::TeamGroup::new(&team_group.name, &keys)?)?;
+                writeln!(out, "Updated team group @{}", team_group.name)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn edit(
+        &self,
+        key: &str,
+        team_member_repo: &impl TeamMemberRepo,
+        team_group_repo: &impl TeamGroupRepo,
+        preset_repo: &impl PresetRepo,
+        out: &mut impl Write,
+    ) -> Result<()> {
+        let existing = team_member_repo
+            .get(key)?
+            .ok_or_else(|| format!("No team member found with key: {key}"))?;
+
+        let (new_key, name, email) =
+            if self.new_key.is_none() && self.name.is_none() && self.email.is_none() {
+                (
+                    Text::new("Key:")
+                        .with_initial_value(&existing.key)
+                        .prompt()?,
+                    Text::new("Name:")
+                        .with_initial_value(&existing.name)
+                        .prompt()?,
+                    Text::new("Email:")
+                        .with_initial_value(&existing.email)
+                        .prompt()?,
+                )
+            } else {
+                (
+                    self.new_key.clone().unwrap_or_else(|| existing.key.clone()),
+                    self.name.clone().unwrap_or_else(|| existing.name.clone()),
+                    self.email.clone().unwrap_or_else(|| existing.email.clone()),
+                )
+            };
+
+        let edited = repositories::TeamMember::new(&new_key, &name, &email)?;
+        if !self.allow_unusual {
+            edited.check_usual()?;
+        }
+        let is_rekeyed = edited.key != existing.key;
+        if is_rekeyed && team_member_repo.get(&edited.key)?.is_some() {
+            return Err(format!("Team member with key {} already exists", edited.key).into());
+        }
+
+        team_member_repo.replace(&existing.key, &edited)?;
+        writeln!(out, "{edited}")?;
+
+        // aliases and team groups reference team members by key while presets keep "Name <email>"
+        if is_rekeyed {
+            for (alias, _) in team_member_repo
+                .list_aliases()?
+                .into_iter()
+                .filter(|(_, x)| *x == existing.key)
+            {
+                team_member_repo.add_alias(&alias, &edited.key)?;
+            }
+            for team_group in team_group_repo.list()? {
+                if team_group.keys.contains(&existing.key) {
+                    let keys: Vec<String> = team_group
+                        .keys
+                        .iter()
+                        .map(|x| {
+                            if *x == existing.key {
+                                edited.key.clone()
+                            } else {
+                                x.clone()
+                            }
+                        })
+                        .collect();
+                    team_group_repo.add(&repositories::TeamGroup::new(&team_group.name, &keys)?)?;
+                    writeln!(out, "Updated team group @{}", team_group.name)?;
+                }
+            }
+        }
+        let (existing_coauthor, edited_coauthor) = (existing.to_string(), edited.to_string());
+        if edited_coauthor != existing_coauthor {
+            let edit = |x: &String| {
+                if *x == existing_coauthor {
+                    edited_coauthor.clone()
+                } else {
+                    x.clone()
+                }
+            };
+            for preset in preset_repo.list()? {
+                if preset.coauthors.contains(&existing_coauthor)
+                    || preset.driver.as_ref() == Some(&existing_coauthor)
+                {
+                    preset_repo.save(&repositories::Preset {
+                        driver: preset.driver.as_ref().map(edit),
+                        ..repositories::Preset::new(
+                            &preset.name,
+                            &preset.coauthors.iter().map(edit).collect::<Vec<_>>(),
+                        )?
+                    })?;
+                    writeln!(out, "Updated preset {}", preset.name)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn import(
+        &self,
+        team_member_repo: &impl TeamMemberRepo,
+        command_runner: &impl CommandRunner,
+        path: Option<&Path>,
+        out: &mut impl Write,
+    ) -> Result<()> {
+        let imported_team_members = match self.from {
+            Some(source) => {
+                let conversion = source.load(command_runner, path)?;
+                for (key, reason) in conversion.failures {
+                    writeln!(out, "! {key} (cannot convert: {reason})")?;
+                }
+                conversion.team_members
+            }
+            None => {
+                let path = path.ok_or("A file to import is required unless --from is given")?;
+                let format = self
+                    .roster_format
+                    .or_else(|| RosterFormat::from_path(path))
+                    .unwrap_or_default();
+                let content = fs::read_to_string(path)
+                    .map_err(|err| format!("Failed to read {}: {err}", path.display()))?;
+                format
+                    .deserialize(&content)
+                    .map_err(|err| format!("Failed to import {}: {err}", path.display()))?
+            }
+        };
+
+        let mut team_members: HashMap<String, repositories::TeamMember> = team_member_repo
+            .list()?
+            .into_iter()
+            .map(|x| (x.key.to_owned(), x))
+            .collect();
+        // renamed keys must not be aliases either, as an alias resolves to its team member instead
+        let aliases: HashSet<String> = team_member_repo
+            .list_aliases()?
+            .into_iter()
+            .map(|(alias, _)| alias)
+            .collect();
+
+        for team_member in imported_team_members {
+            if !self.allow_unusual
+                && let Err(err) = team_member.check_usual()
+            {
+                writeln!(out, "! {} {team_member} ({err})", team_member.key)?;
+                continue;
+            }
+            let team_member = match team_members.get(&team_member.key) {
+                None => team_member,
+                Some(existing)
+                    if existing.name == team_member.name && existing.email == team_member.email =>
+                {
+                    continue;
+                }
+                Some(existing) => match self.on_conflict {
+                    OnConflict::Skip => {
+                        writeln!(out, "! {} {team_member} (skipped)", team_member.key)?;
+                        continue;
+                    }
+                    OnConflict::Overwrite => {
+                        writeln!(out, "- {} {existing}", existing.key)?;
+                        team_member
+                    }
+                    OnConflict::Rename => {
+                        let key = next_available_key(&team_member.key, |x| {
+                            team_members.contains_key(x) || aliases.contains(x)
+                        });
+                        repositories::TeamMember { key, ..team_member }
+                    }
+                },
+            };
+
+            writeln!(out, "+ {} {team_member}", team_member.key)?;
+            if !self.dry_run {
+                team_member_repo.add(&team_member)?;
+            }
+            team_members.insert(team_member.key.to_owned(), team_member);
+        }
+
+        Ok(())
+    }
+
+    fn import_from_log(
+        &self,
+        team_member_repo: &impl TeamMemberRepo,
+        commit_log_repo: &impl CommitLogRepo,
+        out: &mut impl Write,
+    ) -> Result<()> {
+        let contributors = commit_log_repo
+            .list_contributors(self.since.as_deref(), self.revision_range.as_deref())?;
+        let mut team_members = Vec::new();
+        for team_member in find_new_team_members(&contributors, &team_member_repo.list()?) {
+            if !self.allow_unusual
+                && let Err(err) = team_member.check_usual()
+            {
+                writeln!(out, "! {} {team_member} ({err})", team_member.key)?;
+                continue;
+            }
+            team_members.push(team_member);
+        }
+
+        if team_members.is_empty() {
+            writeln!(out, "No new team member(s) found in git log")?;
+            return Ok(());
+        }
+
+        let options = team_members
+            .iter()
+            .map(|x| format!("{} {x}", x.key))
+            .collect();
+        let result = MultiSelect::new("Select team member(s) to import:", options)
+            .with_all_selected_by_default()
+            .raw_prompt_skippable()?;
+
+        if let Some(selected) = result {
+            for option in selected {
+                team_member_repo.add(&team_members[option.index])?;
+                writeln!(out, "{}", option.value)?;
+            }
+        }
 
         Ok(())
     }
 }
 
+/// Proposes team members for contributors whose emails are not already in the team member repository
+fn find_new_team_members(
+    contributors: &[Contributor],
+    existing_team_members: &[repositories::TeamMember],
+) -> Vec<repositories::TeamMember> {
+    let mut seen_emails: HashSet<String> = existing_team_members
+        .iter()
+        .map(|x| x.email.to_lowercase())
+        .collect();
+    let mut taken_keys: HashSet<String> = existing_team_members
+        .iter()
+        .map(|x| x.key.to_owned())
+        .collect();
+
+    let mut team_members = Vec::new();
+    for contributor in contributors {
+        if !seen_emails.insert(contributor.email.to_lowercase()) {
+            continue;
+        }
+
+        let key = propose_key(contributor, &taken_keys);
+        if let Ok(team_member) =
+            repositories::TeamMember::new(&key, &contributor.name, &contributor.email)
+        {
+            taken_keys.insert(key);
+            team_members.push(team_member);
+        }
+    }
+
+    team_members
+}
+
+/// Proposes key from initials of the contributor's name e.g. "lm" for "Leo Messi"
+fn propose_key(contributor: &Contributor, taken_keys: &HashSet<String>) -> String {
+    let mut initials: String = contributor
+        .name
+        .split_whitespace()
+        .filter_map(|x| x.chars().find(char::is_ascii_alphanumeric))
+        .map(|x| x.to_ascii_lowercase())
+        .collect();
+
+    if initials.is_empty() {
+        initials = contributor
+            .email
+            .chars()
+            .take_while(|x| *x != '@')
+            .filter(char::is_ascii_alphanumeric)
+            .map(|x| x.to_ascii_lowercase())
+            .collect();
+    }
+
+    next_available_key(&initials, |x| taken_keys.contains(x))
+}
+
+/// Returns the key if it is not taken, otherwise appends the lowest number which makes it available
+fn next_available_key(key: &str, is_taken: impl Fn(&str) -> bool) -> String {
+    if !is_taken(key) {
+        return key.to_owned();
+    }
+
+    (2..)
+        .map(|x| format!("{key}{x}"))
+        .find(|x| !is_taken(x))
+        .unwrap_or_else(|| key.to_owned())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::repositories::MockTeamMemberRepo;
+    use crate::helpers::MockCommandRunner;
+    use crate::repositories::{
+        MockCommitLogRepo, MockPresetRepo, MockTeamGroupRepo, MockTeamMemberRepo, TeamGroup,
+    };
     use mockall::predicate;
 
     #[test]
     fn test_delete_team_member() -> Result<()> {
         let key = "lm";
         let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo.expect_list_aliases().returning(|| {
+            Ok(vec![
+                ("leo".to_owned(), "lm".to_owned()),
+                ("messi".to_owned(), "lm".to_owned()),
+                ("dibu".to_owned(), "em".to_owned()),
+            ])
+        });
+        // the team member is no longer found once removed
+        let mut is_removed = false;
         mock_team_member_repo
             .expect_get()
             .with(predicate::eq(key))
-            .once()
-            .returning(|_| Ok(Some("Leo Messi <leo.messi@example.com>".to_owned())));
+            .times(2)
+            .returning(move |key| {
+                if is_removed {
+                    return Ok(None);
+                }
+                is_removed = true;
+                Ok(Some(repositories::TeamMember::new(
+                    key,
+                    "Leo Messi",
+                    "leo.messi@example.com",
+                )?))
+            });
         mock_team_member_repo
             .expect_remove()
             .with(predicate::eq(key))
             .once()
             .returning(|_| Ok(()));
+        mock_team_member_repo
+            .expect_remove_alias()
+            .withf(|alias| alias == "leo" || alias == "messi")
+            .times(2)
+            .returning(|_| Ok(()));
+        let mut mock_team_group_repo = MockTeamGroupRepo::new();
+        mock_team_group_repo.expect_list().once().returning(|| {
+            Ok(vec![
+                TeamGroup::new("frontend", &["lm".to_owned(), "em".to_owned()])?,
+                TeamGroup::new("backend", &["sa".to_owned()])?,
+                TeamGroup::new("strikers", &["lm".to_owned()])?,
+            ])
+        });
+        mock_team_group_repo
+            .expect_add()
+            .with(predicate::eq(TeamGroup::new(
+                "frontend",
+                &["em".to_owned()],
+            )?))
+            .once()
+            .returning(|_| Ok(()));
+        mock_team_group_repo
+            .expect_remove()
+            .with(predicate::eq("strikers"))
+            .once()
+            .returning(|_| Ok(()));
 
         let team_member_cmd = TeamMember {
             delete: Some(key.to_owned()),
+            hide: None,
+            repo: false,
+            system: false,
             add: None,
             list: false,
+            import_from_log: false,
+            since: None,
+            revision_range: None,
+            export: false,
+            import: None,
+            from: None,
+            format: OutputFormat::Text,
+            roster_format: None,
+            on_conflict: OnConflict::Skip,
+            dry_run: false,
+            allow_unusual: false,
+            force: false,
+            alias: None,
+            edit: None,
+            new_key: None,
+            name: None,
+            email: None,
         };
 
         let mut out = Vec::new();
-        team_member_cmd.handle(&mock_team_member_repo, &mut out)?;
+        team_member_cmd.handle(
+            &mock_team_member_repo,
+            &mock_team_group_repo,
+            &MockPresetRepo::new(),
+            &MockCommitLogRepo::new(),
+            &MockCommandRunner::new(),
+            &mut out,
+        )?;
+
+        assert_eq!(
+            String::from_utf8(out)?,
+            "Updated team group @frontend\nRemoved team group @strikers\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_hide_team_member_given_system_roster() -> Result<()> {
+        let key = "lm";
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo
+            .expect_write_to()
+            .with(predicate::eq(TeamMemberSource::System))
+            .once()
+            .returning(|_| Ok(()));
+        mock_team_member_repo
+            .expect_get()
+            .with(predicate::eq(key))
+            .once()
+            .returning(|key| {
+                Ok(Some(repositories::TeamMember::new(
+                    key,
+                    "Leo Messi",
+                    "leo.messi@example.com",
+                )?))
+            });
+        mock_team_member_repo
+            .expect_hide()
+            .with(predicate::eq(key))
+            .once()
+            .returning(|_| Ok(()));
+
+        let team_member_cmd = TeamMember {
+            delete: None,
+            hide: Some(key.to_owned()),
+            repo: false,
+            system: true,
+            add: None,
+            list: false,
+            import_from_log: false,
+            since: None,
+            revision_range: None,
+            export: false,
+            import: None,
+            from: None,
+            format: OutputFormat::Text,
+            roster_format: None,
+            on_conflict: OnConflict::Skip,
+            dry_run: false,
+            allow_unusual: false,
+            force: false,
+            alias: None,
+            edit: None,
+            new_key: None,
+            name: None,
+            email: None,
+        };
+
+        let mut out = Vec::new();
+        team_member_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &MockPresetRepo::new(),
+            &MockCommitLogRepo::new(),
+            &MockCommandRunner::new(),
+            &mut out,
+        )?;
 
         assert!(out.is_empty());
 
@@ -90,6 +814,9 @@ mod tests {
     fn test_delete_team_member_when_team_member_not_found() -> Result<()> {
         let key = "em";
         let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo
+            .expect_list_aliases()
+            .returning(|| Ok(vec![]));
         mock_team_member_repo
             .expect_get()
             .with(predicate::eq(key))
@@ -98,12 +825,39 @@ mod tests {
 
         let team_member_cmd = TeamMember {
             delete: Some(key.to_owned()),
+            hide: None,
+            repo: false,
+            system: false,
             add: None,
             list: false,
+            import_from_log: false,
+            since: None,
+            revision_range: None,
+            export: false,
+            import: None,
+            from: None,
+            format: OutputFormat::Text,
+            roster_format: None,
+            on_conflict: OnConflict::Skip,
+            dry_run: false,
+            allow_unusual: false,
+            force: false,
+            alias: None,
+            edit: None,
+            new_key: None,
+            name: None,
+            email: None,
         };
 
         let mut out = Vec::new();
-        let result = team_member_cmd.handle(&mock_team_member_repo, &mut out);
+        let result = team_member_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &MockPresetRepo::new(),
+            &MockCommitLogRepo::new(),
+            &MockCommandRunner::new(),
+            &mut out,
+        );
 
         assert!(
             result.is_err_and(
@@ -114,6 +868,182 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_add_alias() -> Result<()> {
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo
+            .expect_get()
+            .with(predicate::eq("leo"))
+            .once()
+            .returning(|_| Ok(None));
+        mock_team_member_repo
+            .expect_get()
+            .with(predicate::eq("lm"))
+            .once()
+            .returning(|key| {
+                Ok(Some(repositories::TeamMember::new(
+                    key,
+                    "Leo Messi",
+                    "leo.messi@example.com",
+                )?))
+            });
+        mock_team_member_repo
+            .expect_add_alias()
+            .with(predicate::eq("leo"), predicate::eq("lm"))
+            .once()
+            .returning(|_, _| Ok(()));
+
+        let team_member_cmd = TeamMember {
+            alias: Some(vec!["leo".to_owned(), "lm".to_owned()]),
+            delete: None,
+            add: None,
+            force: false,
+            edit: None,
+            new_key: None,
+            name: None,
+            email: None,
+            hide: None,
+            repo: false,
+            system: false,
+            list: false,
+            import_from_log: false,
+            since: None,
+            revision_range: None,
+            export: false,
+            import: None,
+            from: None,
+            format: OutputFormat::Text,
+            roster_format: None,
+            on_conflict: OnConflict::Skip,
+            dry_run: false,
+            allow_unusual: false,
+        };
+
+        let mut out = Vec::new();
+        team_member_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &MockPresetRepo::new(),
+            &MockCommitLogRepo::new(),
+            &MockCommandRunner::new(),
+            &mut out,
+        )?;
+
+        assert_eq!(out, b"leo Leo Messi <leo.messi@example.com>\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_alias_when_alias_taken() -> Result<()> {
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo
+            .expect_get()
+            .with(predicate::eq("leo"))
+            .once()
+            .returning(|_| {
+                Ok(Some(repositories::TeamMember::new(
+                    "lp",
+                    "Leandro Paredes",
+                    "leandro.paredes@example.com",
+                )?))
+            });
+        mock_team_member_repo.expect_add_alias().never();
+
+        let team_member_cmd = TeamMember {
+            alias: Some(vec!["leo".to_owned(), "lm".to_owned()]),
+            delete: None,
+            add: None,
+            force: false,
+            edit: None,
+            new_key: None,
+            name: None,
+            email: None,
+            hide: None,
+            repo: false,
+            system: false,
+            list: false,
+            import_from_log: false,
+            since: None,
+            revision_range: None,
+            export: false,
+            import: None,
+            from: None,
+            format: OutputFormat::Text,
+            roster_format: None,
+            on_conflict: OnConflict::Skip,
+            dry_run: false,
+            allow_unusual: false,
+        };
+
+        let mut out = Vec::new();
+        let result = team_member_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &MockPresetRepo::new(),
+            &MockCommitLogRepo::new(),
+            &MockCommandRunner::new(),
+            &mut out,
+        );
+
+        assert!(result.is_err_and(|err| err.to_string()
+            == "leo is already the key or an alias of Leandro Paredes <leandro.paredes@example.com>"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_delete_alias() -> Result<()> {
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo
+            .expect_list_aliases()
+            .returning(|| Ok(vec![("leo".to_owned(), "lm".to_owned())]));
+        mock_team_member_repo
+            .expect_remove_alias()
+            .with(predicate::eq("leo"))
+            .once()
+            .returning(|_| Ok(()));
+        mock_team_member_repo.expect_remove().never();
+
+        let team_member_cmd = TeamMember {
+            alias: None,
+            delete: Some("leo".to_owned()),
+            add: None,
+            force: false,
+            edit: None,
+            new_key: None,
+            name: None,
+            email: None,
+            hide: None,
+            repo: false,
+            system: false,
+            list: false,
+            import_from_log: false,
+            since: None,
+            revision_range: None,
+            export: false,
+            import: None,
+            from: None,
+            format: OutputFormat::Text,
+            roster_format: None,
+            on_conflict: OnConflict::Skip,
+            dry_run: false,
+            allow_unusual: false,
+        };
+
+        let mut out = Vec::new();
+        team_member_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &MockPresetRepo::new(),
+            &MockCommitLogRepo::new(),
+            &MockCommandRunner::new(),
+            &mut out,
+        )?;
+
+        Ok(())
+    }
+
     #[test]
     fn test_add_team_member() -> Result<()> {
         let key = "lm";
@@ -121,23 +1051,54 @@ mod tests {
         let email = "leo.messi@example.com";
 
         let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo
+            .expect_get()
+            .with(predicate::eq(key))
+            .once()
+            .returning(|_| Ok(None));
         mock_team_member_repo
             .expect_add()
-            .with(
-                predicate::eq(key),
-                predicate::eq(format!("{name} <{email}>")),
-            )
+            .with(predicate::eq(repositories::TeamMember::new(
+                key, name, email,
+            )?))
             .once()
-            .returning(|_, _| Ok(()));
+            .returning(|_| Ok(()));
 
         let team_member_cmd = TeamMember {
             add: Some(vec![key.to_owned(), name.to_owned(), email.to_owned()]),
             delete: None,
+            hide: None,
+            repo: false,
+            system: false,
             list: false,
+            import_from_log: false,
+            since: None,
+            revision_range: None,
+            export: false,
+            import: None,
+            from: None,
+            format: OutputFormat::Text,
+            roster_format: None,
+            on_conflict: OnConflict::Skip,
+            dry_run: false,
+            allow_unusual: false,
+            force: false,
+            alias: None,
+            edit: None,
+            new_key: None,
+            name: None,
+            email: None,
         };
 
         let mut out = Vec::new();
-        team_member_cmd.handle(&mock_team_member_repo, &mut out)?;
+        team_member_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &MockPresetRepo::new(),
+            &MockCommitLogRepo::new(),
+            &MockCommandRunner::new(),
+            &mut out,
+        )?;
 
         assert_eq!(out, format!("{name} <{email}>\n").as_bytes());
 
@@ -145,52 +1106,1107 @@ mod tests {
     }
 
     #[test]
-    fn test_list_team_members() -> Result<()> {
-        let team_members = vec![
-            "lm Leo Messi <leo.messi@example.com>".to_owned(),
-            "em Emi Martinez <emi.martinez@example.com>".to_owned(),
-        ];
-
-        let expected_output = format!("{}\n", team_members.join("\n"));
-
+    fn test_add_team_member_when_key_exists() -> Result<()> {
         let mut mock_team_member_repo = MockTeamMemberRepo::new();
         mock_team_member_repo
-            .expect_list()
+            .expect_get()
+            .with(predicate::eq("lm"))
             .once()
-            .returning(move |_| Ok(team_members.to_owned()));
+            .returning(|key| {
+                Ok(Some(repositories::TeamMember::new(
+                    key,
+                    "Lionel Messi",
+                    "lionel.messi@example.com",
+                )?))
+            });
+        mock_team_member_repo.expect_add().never();
 
         let team_member_cmd = TeamMember {
-            list: true,
+            add: Some(vec![
+                "lm".to_owned(),
+                "Leo Messi".to_owned(),
+                "leo.messi@example.com".to_owned(),
+            ]),
+            force: false,
+            alias: None,
+            edit: None,
+            new_key: None,
+            name: None,
+            email: None,
             delete: None,
-            add: None,
+            hide: None,
+            repo: false,
+            system: false,
+            list: false,
+            import_from_log: false,
+            since: None,
+            revision_range: None,
+            export: false,
+            import: None,
+            from: None,
+            format: OutputFormat::Text,
+            roster_format: None,
+            on_conflict: OnConflict::Skip,
+            dry_run: false,
+            allow_unusual: false,
         };
 
         let mut out = Vec::new();
-        team_member_cmd.handle(&mock_team_member_repo, &mut out)?;
+        let result = team_member_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &MockPresetRepo::new(),
+            &MockCommitLogRepo::new(),
+            &MockCommandRunner::new(),
+            &mut out,
+        );
 
-        assert_eq!(out, expected_output.as_bytes());
+        assert!(result.is_err_and(|err| err.to_string()
+            == "Team member with key lm already exists. Use --force to overwrite it"));
 
         Ok(())
     }
 
     #[test]
-    fn test_list_team_members_when_no_team_members_added() -> Result<()> {
+    fn test_add_team_member_given_force() -> Result<()> {
         let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo.expect_get().never();
         mock_team_member_repo
-            .expect_list()
+            .expect_add()
+            .with(predicate::eq(repositories::TeamMember::new(
+                "lm",
+                "Leo Messi",
+                "leo.messi@example.com",
+            )?))
             .once()
-            .returning(move |_| Ok(vec![]));
+            .returning(|_| Ok(()));
 
         let team_member_cmd = TeamMember {
-            list: true,
+            add: Some(vec![
+                "lm".to_owned(),
+                "Leo Messi".to_owned(),
+                "leo.messi@example.com".to_owned(),
+            ]),
+            force: true,
+            alias: None,
+            edit: None,
+            new_key: None,
+            name: None,
+            email: None,
             delete: None,
-            add: None,
+            hide: None,
+            repo: false,
+            system: false,
+            list: false,
+            import_from_log: false,
+            since: None,
+            revision_range: None,
+            export: false,
+            import: None,
+            from: None,
+            format: OutputFormat::Text,
+            roster_format: None,
+            on_conflict: OnConflict::Skip,
+            dry_run: false,
+            allow_unusual: false,
         };
 
         let mut out = Vec::new();
-        team_member_cmd.handle(&mock_team_member_repo, &mut out)?;
+        team_member_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &MockPresetRepo::new(),
+            &MockCommitLogRepo::new(),
+            &MockCommandRunner::new(),
+            &mut out,
+        )?;
 
-        assert_eq!(out, b"");
+        Ok(())
+    }
+
+    #[test]
+    fn test_edit_team_member_given_new_key() -> Result<()> {
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo
+            .expect_list_aliases()
+            .returning(|| Ok(vec![("messi".to_owned(), "lm".to_owned())]));
+        mock_team_member_repo
+            .expect_add_alias()
+            .with(predicate::eq("messi"), predicate::eq("leo"))
+            .once()
+            .returning(|_, _| Ok(()));
+        mock_team_member_repo
+            .expect_get()
+            .with(predicate::eq("lm"))
+            .once()
+            .returning(|key| {
+                Ok(Some(repositories::TeamMember::new(
+                    key,
+                    "Leo Messi",
+                    "leo.messi@example.com",
+                )?))
+            });
+        mock_team_member_repo
+            .expect_get()
+            .with(predicate::eq("leo"))
+            .once()
+            .returning(|_| Ok(None));
+        mock_team_member_repo
+            .expect_replace()
+            .with(
+                predicate::eq("lm"),
+                predicate::eq(repositories::TeamMember::new(
+                    "leo",
+                    "Leo Messi",
+                    "leo.messi@example.com",
+                )?),
+            )
+            .once()
+            .returning(|_, _| Ok(()));
+        let mut mock_team_group_repo = MockTeamGroupRepo::new();
+        mock_team_group_repo.expect_list().once().returning(|| {
+            Ok(vec![
+                repositories::TeamGroup::new("frontend", &["em".into(), "lm".into()])?,
+                repositories::TeamGroup::new("backend", &["jh".into()])?,
+            ])
+        });
+        mock_team_group_repo
+            .expect_add()
+            .with(predicate::eq(repositories::TeamGroup::new(
+                "frontend",
+                &["em".into(), "leo".into()],
+            )?))
+            .once()
+            .returning(|_| Ok(()));
+        let mut mock_preset_repo = MockPresetRepo::new();
+        mock_preset_repo.expect_list().never();
+
+        let team_member_cmd = TeamMember {
+            add: None,
+            force: false,
+            alias: None,
+            edit: Some("lm".to_owned()),
+            new_key: Some("leo".to_owned()),
+            name: None,
+            email: None,
+            delete: None,
+            hide: None,
+            repo: false,
+            system: false,
+            list: false,
+            import_from_log: false,
+            since: None,
+            revision_range: None,
+            export: false,
+            import: None,
+            from: None,
+            format: OutputFormat::Text,
+            roster_format: None,
+            on_conflict: OnConflict::Skip,
+            dry_run: false,
+            allow_unusual: false,
+        };
+
+        let mut out = Vec::new();
+        team_member_cmd.handle(
+            &mock_team_member_repo,
+            &mock_team_group_repo,
+            &mock_preset_repo,
+            &MockCommitLogRepo::new(),
+            &MockCommandRunner::new(),
+            &mut out,
+        )?;
+
+        assert_eq!(
+            String::from_utf8(out)?,
+            "Leo Messi <leo.messi@example.com>\nUpdated team group @frontend\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_edit_team_member_given_new_email() -> Result<()> {
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo
+            .expect_get()
+            .with(predicate::eq("lm"))
+            .once()
+            .returning(|key| {
+                Ok(Some(repositories::TeamMember::new(
+                    key,
+                    "Leo Messi",
+                    "leo.messi@example.com",
+                )?))
+            });
+        mock_team_member_repo
+            .expect_replace()
+            .with(
+                predicate::eq("lm"),
+                predicate::eq(repositories::TeamMember::new(
+                    "lm",
+                    "Leo Messi",
+                    "leo@example.com",
+                )?),
+            )
+            .once()
+            .returning(|_, _| Ok(()));
+        let mut mock_team_group_repo = MockTeamGroupRepo::new();
+        mock_team_group_repo.expect_list().never();
+        let mut mock_preset_repo = MockPresetRepo::new();
+        mock_preset_repo.expect_list().once().returning(|| {
+            Ok(vec![
+                repositories::Preset::new(
+                    "payments-mob",
+                    &[
+                        "Leo Messi <leo.messi@example.com>".to_owned(),
+                        "Emi Martinez <emi.martinez@example.com>".to_owned(),
+                    ],
+                )?,
+                repositories::Preset::new(
+                    "search",
+                    &["Emi Martinez <emi.martinez@example.com>".to_owned()],
+                )?,
+            ])
+        });
+        mock_preset_repo
+            .expect_save()
+            .with(predicate::eq(repositories::Preset::new(
+                "payments-mob",
+                &[
+                    "Leo Messi <leo@example.com>".to_owned(),
+                    "Emi Martinez <emi.martinez@example.com>".to_owned(),
+                ],
+            )?))
+            .once()
+            .returning(|_| Ok(()));
+
+        let team_member_cmd = TeamMember {
+            add: None,
+            force: false,
+            alias: None,
+            edit: Some("lm".to_owned()),
+            new_key: None,
+            name: None,
+            email: Some("leo@example.com".to_owned()),
+            delete: None,
+            hide: None,
+            repo: false,
+            system: false,
+            list: false,
+            import_from_log: false,
+            since: None,
+            revision_range: None,
+            export: false,
+            import: None,
+            from: None,
+            format: OutputFormat::Text,
+            roster_format: None,
+            on_conflict: OnConflict::Skip,
+            dry_run: false,
+            allow_unusual: false,
+        };
+
+        let mut out = Vec::new();
+        team_member_cmd.handle(
+            &mock_team_member_repo,
+            &mock_team_group_repo,
+            &mock_preset_repo,
+            &MockCommitLogRepo::new(),
+            &MockCommandRunner::new(),
+            &mut out,
+        )?;
+
+        assert_eq!(
+            String::from_utf8(out)?,
+            "Leo Messi <leo@example.com>\nUpdated preset payments-mob\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_edit_team_member_when_new_key_exists() -> Result<()> {
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo.expect_get().returning(|key| {
+            Ok(Some(repositories::TeamMember::new(
+                key,
+                "Leo Messi",
+                "leo.messi@example.com",
+            )?))
+        });
+        mock_team_member_repo.expect_add().never();
+
+        let team_member_cmd = TeamMember {
+            add: None,
+            force: false,
+            alias: None,
+            edit: Some("lm".to_owned()),
+            new_key: Some("em".to_owned()),
+            name: None,
+            email: None,
+            delete: None,
+            hide: None,
+            repo: false,
+            system: false,
+            list: false,
+            import_from_log: false,
+            since: None,
+            revision_range: None,
+            export: false,
+            import: None,
+            from: None,
+            format: OutputFormat::Text,
+            roster_format: None,
+            on_conflict: OnConflict::Skip,
+            dry_run: false,
+            allow_unusual: false,
+        };
+
+        let mut out = Vec::new();
+        let result = team_member_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &MockPresetRepo::new(),
+            &MockCommitLogRepo::new(),
+            &MockCommandRunner::new(),
+            &mut out,
+        );
+
+        assert!(
+            result.is_err_and(|err| err.to_string() == "Team member with key em already exists")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_team_member_when_email_invalid() -> Result<()> {
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo.expect_add().never();
+
+        let team_member_cmd = TeamMember {
+            add: Some(vec![
+                "lm".to_owned(),
+                "Leo Messi".to_owned(),
+                "<leo.messi@example.com>".to_owned(),
+            ]),
+            delete: None,
+            hide: None,
+            repo: false,
+            system: false,
+            list: false,
+            import_from_log: false,
+            since: None,
+            revision_range: None,
+            export: false,
+            import: None,
+            from: None,
+            format: OutputFormat::Text,
+            roster_format: None,
+            on_conflict: OnConflict::Skip,
+            dry_run: false,
+            allow_unusual: false,
+            force: false,
+            alias: None,
+            edit: None,
+            new_key: None,
+            name: None,
+            email: None,
+        };
+
+        let mut out = Vec::new();
+        let result = team_member_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &MockPresetRepo::new(),
+            &MockCommitLogRepo::new(),
+            &MockCommandRunner::new(),
+            &mut out,
+        );
+
+        assert!(result.is_err_and(|err| err.to_string()
+            == "Invalid email: <leo.messi@example.com>. It must not contain < or >"));
+        assert!(out.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_team_members() -> Result<()> {
+        let team_members = vec![
+            repositories::TeamMember::new("lm", "Leo Messi", "leo.messi@example.com")?,
+            repositories::TeamMember::new("em", "Emi Martinez", "emi.martinez@example.com")?
+                .with_source(TeamMemberSource::System),
+        ];
+
+        let expected_output = "lm Leo Messi <leo.messi@example.com>\n  aliases: leo, messi\nem Emi Martinez <emi.martinez@example.com> (system)\n  aliases: dibu\n";
+
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo.expect_list_aliases().returning(|| {
+            Ok(vec![
+                ("leo".to_owned(), "lm".to_owned()),
+                ("messi".to_owned(), "lm".to_owned()),
+                ("dibu".to_owned(), "em".to_owned()),
+            ])
+        });
+        mock_team_member_repo
+            .expect_list()
+            .once()
+            .returning(move || Ok(team_members.to_owned()));
+
+        let team_member_cmd = TeamMember {
+            list: true,
+            delete: None,
+            hide: None,
+            repo: false,
+            system: false,
+            add: None,
+            import_from_log: false,
+            since: None,
+            revision_range: None,
+            export: false,
+            import: None,
+            from: None,
+            format: OutputFormat::Text,
+            roster_format: None,
+            on_conflict: OnConflict::Skip,
+            dry_run: false,
+            allow_unusual: false,
+            force: false,
+            alias: None,
+            edit: None,
+            new_key: None,
+            name: None,
+            email: None,
+        };
+
+        let mut out = Vec::new();
+        team_member_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &MockPresetRepo::new(),
+            &MockCommitLogRepo::new(),
+            &MockCommandRunner::new(),
+            &mut out,
+        )?;
+
+        assert_eq!(out, expected_output.as_bytes());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_team_members_given_tsv_format() -> Result<()> {
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo.expect_list_aliases().returning(|| {
+            Ok(vec![
+                ("leo".to_owned(), "lm".to_owned()),
+                ("messi".to_owned(), "lm".to_owned()),
+            ])
+        });
+        mock_team_member_repo.expect_list().once().returning(|| {
+            Ok(vec![
+                repositories::TeamMember::new("lm", "Leo Messi", "leo.messi@example.com")?
+                    .with_source(TeamMemberSource::Repo),
+                repositories::TeamMember::new("em", "Emi Martinez", "emi.martinez@example.com")?,
+            ])
+        });
+
+        let team_member_cmd = TeamMember {
+            list: true,
+            delete: None,
+            hide: None,
+            repo: false,
+            system: false,
+            add: None,
+            import_from_log: false,
+            since: None,
+            revision_range: None,
+            export: false,
+            import: None,
+            from: None,
+            format: OutputFormat::Tsv,
+            roster_format: None,
+            on_conflict: OnConflict::Skip,
+            dry_run: false,
+            allow_unusual: false,
+            force: false,
+            alias: None,
+            edit: None,
+            new_key: None,
+            name: None,
+            email: None,
+        };
+
+        let mut out = Vec::new();
+        team_member_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &MockPresetRepo::new(),
+            &MockCommitLogRepo::new(),
+            &MockCommandRunner::new(),
+            &mut out,
+        )?;
+
+        assert_eq!(
+            String::from_utf8(out)?,
+            "key\tname\temail\tsource\taliases\n\
+             lm\tLeo Messi\tleo.messi@example.com\trepo\tleo,messi\n\
+             em\tEmi Martinez\temi.martinez@example.com\t\t\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_team_members_when_no_team_members_added() -> Result<()> {
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo
+            .expect_list_aliases()
+            .returning(|| Ok(vec![]));
+        mock_team_member_repo
+            .expect_list()
+            .once()
+            .returning(move || Ok(vec![]));
+
+        let team_member_cmd = TeamMember {
+            list: true,
+            delete: None,
+            hide: None,
+            repo: false,
+            system: false,
+            add: None,
+            import_from_log: false,
+            since: None,
+            revision_range: None,
+            export: false,
+            import: None,
+            from: None,
+            format: OutputFormat::Text,
+            roster_format: None,
+            on_conflict: OnConflict::Skip,
+            dry_run: false,
+            allow_unusual: false,
+            force: false,
+            alias: None,
+            edit: None,
+            new_key: None,
+            name: None,
+            email: None,
+        };
+
+        let mut out = Vec::new();
+        team_member_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &MockPresetRepo::new(),
+            &MockCommitLogRepo::new(),
+            &MockCommandRunner::new(),
+            &mut out,
+        )?;
+
+        assert_eq!(out, b"");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_hide_team_member() -> Result<()> {
+        let key = "lm";
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo
+            .expect_get()
+            .with(predicate::eq(key))
+            .once()
+            .returning(|key| {
+                Ok(Some(
+                    repositories::TeamMember::new(key, "Leo Messi", "leo.messi@example.com")?
+                        .with_source(TeamMemberSource::System),
+                ))
+            });
+        mock_team_member_repo
+            .expect_hide()
+            .with(predicate::eq(key))
+            .once()
+            .returning(|_| Ok(()));
+
+        let team_member_cmd = TeamMember {
+            hide: Some(key.to_owned()),
+            repo: false,
+            system: false,
+            delete: None,
+            add: None,
+            list: false,
+            import_from_log: false,
+            since: None,
+            revision_range: None,
+            export: false,
+            import: None,
+            from: None,
+            format: OutputFormat::Text,
+            roster_format: None,
+            on_conflict: OnConflict::Skip,
+            dry_run: false,
+            allow_unusual: false,
+            force: false,
+            alias: None,
+            edit: None,
+            new_key: None,
+            name: None,
+            email: None,
+        };
+
+        let mut out = Vec::new();
+        team_member_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &MockPresetRepo::new(),
+            &MockCommitLogRepo::new(),
+            &MockCommandRunner::new(),
+            &mut out,
+        )?;
+
+        assert!(out.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_hide_team_member_when_team_member_not_found() -> Result<()> {
+        let key = "em";
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo
+            .expect_get()
+            .with(predicate::eq(key))
+            .once()
+            .returning(|_| Ok(None));
+        mock_team_member_repo.expect_hide().never();
+
+        let team_member_cmd = TeamMember {
+            hide: Some(key.to_owned()),
+            repo: false,
+            system: false,
+            delete: None,
+            add: None,
+            list: false,
+            import_from_log: false,
+            since: None,
+            revision_range: None,
+            export: false,
+            import: None,
+            from: None,
+            format: OutputFormat::Text,
+            roster_format: None,
+            on_conflict: OnConflict::Skip,
+            dry_run: false,
+            allow_unusual: false,
+            force: false,
+            alias: None,
+            edit: None,
+            new_key: None,
+            name: None,
+            email: None,
+        };
+
+        let mut out = Vec::new();
+        let result = team_member_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &MockPresetRepo::new(),
+            &MockCommitLogRepo::new(),
+            &MockCommandRunner::new(),
+            &mut out,
+        );
+
+        assert!(
+            result.is_err_and(
+                |err| err.to_string() == format!("No team member found with key: {key}")
+            )
+        );
+
+        Ok(())
+    }
+
+    fn contributor(name: &str, email: &str) -> Contributor {
+        Contributor {
+            name: name.to_owned(),
+            email: email.to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_find_new_team_members() -> Result<()> {
+        let contributors = [
+            contributor("Leo Messi", "leo.messi@example.com"),
+            contributor("Lautaro Martinez", "lautaro.martinez@example.com"),
+            contributor("Emi Martinez", "Emi.Martinez@example.com"),
+            contributor("Lionel Messi", "LEO.MESSI@example.com"),
+            contributor("Luka Modric", "luka.modric@example.com"),
+            contributor("- -", "angel.dimaria@example.com"),
+        ];
+        let existing_team_members = [repositories::TeamMember::new(
+            "em",
+            "Emi Martinez",
+            "emi.martinez@example.com",
+        )?];
+
+        let result = find_new_team_members(&contributors, &existing_team_members);
+
+        assert_eq!(
+            result,
+            vec![
+                repositories::TeamMember::new("lm", "Leo Messi", "leo.messi@example.com")?,
+                repositories::TeamMember::new(
+                    "lm2",
+                    "Lautaro Martinez",
+                    "lautaro.martinez@example.com"
+                )?,
+                repositories::TeamMember::new("lm3", "Luka Modric", "luka.modric@example.com")?,
+                repositories::TeamMember::new("angeldimaria", "- -", "angel.dimaria@example.com")?,
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_from_log_when_no_new_team_members() -> Result<()> {
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo.expect_list().once().returning(|| {
+            Ok(vec![repositories::TeamMember::new(
+                "lm",
+                "Leo Messi",
+                "leo.messi@example.com",
+            )?])
+        });
+        mock_team_member_repo.expect_add().never();
+        let mut mock_commit_log_repo = MockCommitLogRepo::new();
+        mock_commit_log_repo
+            .expect_list_contributors()
+            .withf(|since, revision_range| {
+                *since == Some("1 week ago") && *revision_range == Some("main..")
+            })
+            .once()
+            .returning(|_, _| Ok(vec![contributor("Leo Messi", "leo.messi@example.com")]));
+
+        let team_member_cmd = TeamMember {
+            import_from_log: true,
+            since: Some("1 week ago".to_owned()),
+            revision_range: Some("main..".to_owned()),
+            export: false,
+            import: None,
+            from: None,
+            format: OutputFormat::Text,
+            roster_format: None,
+            on_conflict: OnConflict::Skip,
+            dry_run: false,
+            allow_unusual: false,
+            add: None,
+            delete: None,
+            hide: None,
+            repo: false,
+            system: false,
+            list: false,
+            force: false,
+            alias: None,
+            edit: None,
+            new_key: None,
+            name: None,
+            email: None,
+        };
+
+        let mut out = Vec::new();
+        team_member_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &MockPresetRepo::new(),
+            &mock_commit_log_repo,
+            &MockCommandRunner::new(),
+            &mut out,
+        )?;
+
+        assert_eq!(out, b"No new team member(s) found in git log\n");
+
+        Ok(())
+    }
+
+    fn create_import_cmd(path: &Path, on_conflict: OnConflict, dry_run: bool) -> TeamMember {
+        TeamMember {
+            import: Some(Some(path.to_owned())),
+            from: None,
+            on_conflict,
+            dry_run,
+            allow_unusual: false,
+            format: OutputFormat::Text,
+            roster_format: None,
+            export: false,
+            add: None,
+            delete: None,
+            hide: None,
+            repo: false,
+            system: false,
+            list: false,
+            import_from_log: false,
+            since: None,
+            revision_range: None,
+            force: false,
+            alias: None,
+            edit: None,
+            new_key: None,
+            name: None,
+            email: None,
+        }
+    }
+
+    fn create_import_file() -> Result<tempfile::NamedTempFile> {
+        let file = tempfile::Builder::new().suffix(".csv").tempfile()?;
+        fs::write(
+            file.path(),
+            "key,name,email\nlm,Leo Messi,leo.messi@example.com\nem,Emiliano Martinez,emi.martinez@example.com\nad,Angel Di Maria,angel.dimaria@example.com\n",
+        )?;
+        Ok(file)
+    }
+
+    fn create_mock_team_member_repo_for_import(
+        expected_added: Vec<repositories::TeamMember>,
+    ) -> Result<MockTeamMemberRepo> {
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo.expect_list().once().returning(|| {
+            Ok(vec![
+                repositories::TeamMember::new("lm", "Leo Messi", "leo.messi@example.com")?,
+                repositories::TeamMember::new("em", "Emi Martinez", "emi.martinez@example.com")?,
+            ])
+        });
+        mock_team_member_repo
+            .expect_list_aliases()
+            .once()
+            .returning(|| Ok(vec![]));
+        for team_member in expected_added {
+            mock_team_member_repo
+                .expect_add()
+                .with(predicate::eq(team_member))
+                .once()
+                .returning(|_| Ok(()));
+        }
+        Ok(mock_team_member_repo)
+    }
+
+    #[test]
+    fn test_import_team_members_given_skip_on_conflict() -> Result<()> {
+        let file = create_import_file()?;
+        let mock_team_member_repo =
+            create_mock_team_member_repo_for_import(vec![repositories::TeamMember::new(
+                "ad",
+                "Angel Di Maria",
+                "angel.dimaria@example.com",
+            )?])?;
+
+        let team_member_cmd = create_import_cmd(file.path(), OnConflict::Skip, false);
+
+        let mut out = Vec::new();
+        team_member_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &MockPresetRepo::new(),
+            &MockCommitLogRepo::new(),
+            &MockCommandRunner::new(),
+            &mut out,
+        )?;
+
+        assert_eq!(
+            String::from_utf8(out)?,
+            "! em Emiliano Martinez <emi.martinez@example.com> (skipped)\n+ ad Angel Di Maria <angel.dimaria@example.com>\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_team_members_given_overwrite_on_conflict() -> Result<()> {
+        let file = create_import_file()?;
+        let mock_team_member_repo = create_mock_team_member_repo_for_import(vec![
+            repositories::TeamMember::new("em", "Emiliano Martinez", "emi.martinez@example.com")?,
+            repositories::TeamMember::new("ad", "Angel Di Maria", "angel.dimaria@example.com")?,
+        ])?;
+
+        let team_member_cmd = create_import_cmd(file.path(), OnConflict::Overwrite, false);
+
+        let mut out = Vec::new();
+        team_member_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &MockPresetRepo::new(),
+            &MockCommitLogRepo::new(),
+            &MockCommandRunner::new(),
+            &mut out,
+        )?;
+
+        assert_eq!(
+            String::from_utf8(out)?,
+            "- em Emi Martinez <emi.martinez@example.com>\n+ em Emiliano Martinez <emi.martinez@example.com>\n+ ad Angel Di Maria <angel.dimaria@example.com>\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_team_members_given_rename_on_conflict() -> Result<()> {
+        let file = create_import_file()?;
+        let mock_team_member_repo = create_mock_team_member_repo_for_import(vec![
+            repositories::TeamMember::new("em2", "Emiliano Martinez", "emi.martinez@example.com")?,
+            repositories::TeamMember::new("ad", "Angel Di Maria", "angel.dimaria@example.com")?,
+        ])?;
+
+        let team_member_cmd = create_import_cmd(file.path(), OnConflict::Rename, false);
+
+        let mut out = Vec::new();
+        team_member_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &MockPresetRepo::new(),
+            &MockCommitLogRepo::new(),
+            &MockCommandRunner::new(),
+            &mut out,
+        )?;
+
+        assert_eq!(
+            String::from_utf8(out)?,
+            "+ em2 Emiliano Martinez <emi.martinez@example.com>\n+ ad Angel Di Maria <angel.dimaria@example.com>\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_team_members_given_rename_on_conflict_with_alias() -> Result<()> {
+        let file = create_import_file()?;
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo.expect_list().once().returning(|| {
+            Ok(vec![
+                repositories::TeamMember::new("lm", "Leo Messi", "leo.messi@example.com")?,
+                repositories::TeamMember::new("em", "Emi Martinez", "emi.martinez@example.com")?,
+            ])
+        });
+        mock_team_member_repo
+            .expect_list_aliases()
+            .once()
+            .returning(|| Ok(vec![("em2".to_owned(), "em".to_owned())]));
+        mock_team_member_repo
+            .expect_add()
+            .with(predicate::eq(repositories::TeamMember::new(
+                "em3",
+                "Emiliano Martinez",
+                "emi.martinez@example.com",
+            )?))
+            .once()
+            .returning(|_| Ok(()));
+        mock_team_member_repo
+            .expect_add()
+            .with(predicate::eq(repositories::TeamMember::new(
+                "ad",
+                "Angel Di Maria",
+                "angel.dimaria@example.com",
+            )?))
+            .once()
+            .returning(|_| Ok(()));
+
+        let team_member_cmd = create_import_cmd(file.path(), OnConflict::Rename, false);
+
+        let mut out = Vec::new();
+        team_member_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &MockPresetRepo::new(),
+            &MockCommitLogRepo::new(),
+            &MockCommandRunner::new(),
+            &mut out,
+        )?;
+
+        assert_eq!(
+            String::from_utf8(out)?,
+            "+ em3 Emiliano Martinez <emi.martinez@example.com>\n+ ad Angel Di Maria <angel.dimaria@example.com>\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_team_members_given_dry_run() -> Result<()> {
+        let file = create_import_file()?;
+        let mock_team_member_repo = create_mock_team_member_repo_for_import(vec![])?;
+
+        let team_member_cmd = create_import_cmd(file.path(), OnConflict::Overwrite, true);
+
+        let mut out = Vec::new();
+        team_member_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &MockPresetRepo::new(),
+            &MockCommitLogRepo::new(),
+            &MockCommandRunner::new(),
+            &mut out,
+        )?;
+
+        assert_eq!(
+            String::from_utf8(out)?,
+            "- em Emi Martinez <emi.martinez@example.com>\n+ em Emiliano Martinez <emi.martinez@example.com>\n+ ad Angel Di Maria <angel.dimaria@example.com>\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_export_team_members() -> Result<()> {
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo.expect_list().once().returning(|| {
+            Ok(vec![repositories::TeamMember::new(
+                "lm",
+                "Leo Messi",
+                "leo.messi@example.com",
+            )?])
+        });
+
+        let team_member_cmd = TeamMember {
+            export: true,
+            format: OutputFormat::Text,
+            roster_format: Some(RosterFormat::Csv),
+            import: None,
+            from: None,
+            on_conflict: OnConflict::Skip,
+            dry_run: false,
+            allow_unusual: false,
+            add: None,
+            delete: None,
+            hide: None,
+            repo: false,
+            system: false,
+            list: false,
+            import_from_log: false,
+            since: None,
+            revision_range: None,
+            force: false,
+            alias: None,
+            edit: None,
+            new_key: None,
+            name: None,
+            email: None,
+        };
+
+        let mut out = Vec::new();
+        team_member_cmd.handle(
+            &mock_team_member_repo,
+            &MockTeamGroupRepo::new(),
+            &MockPresetRepo::new(),
+            &MockCommitLogRepo::new(),
+            &MockCommandRunner::new(),
+            &mut out,
+        )?;
+
+        assert_eq!(
+            String::from_utf8(out)?,
+            "key,name,email\nlm,Leo Messi,leo.messi@example.com\n"
+        );
 
         Ok(())
     }
@@ -0,0 +1,467 @@
+use crate::Result;
+use crate::commands::mob::{CoauthorResolver, switch_driver};
+use crate::helpers::parse_duration;
+use crate::repositories::{MobSessionRepo, MobSessionScope, TeamMember, TeamMemberRepo};
+use chrono::TimeDelta;
+use clap::Parser;
+use std::io::{self, BufRead, Write};
+use std::num::NonZeroU32;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+#[derive(Parser)]
+pub(crate) struct Timer {
+    /// Length of each rotation e.g. 10m
+    #[arg(value_name = "DURATION", value_parser = parse_duration_arg)]
+    pub(crate) duration: TimeDelta,
+    /// Sets rotation order of the mob/pair programming session from team member(s)
+    ///
+    /// Defaults to the previous rotation order or the driver followed by the co-author(s)
+    ///
+    /// Usage example: git mob timer 10m --rotation lm em sa
+    #[arg(long = "rotation", num_args = 1.., value_name = "TEAM_MEMBER_KEY")]
+    pub(crate) rotation: Option<Vec<String>>,
+    /// Takes a break after every given number of rotations
+    ///
+    /// Usage example: git mob timer 10m --break-every 5 --break-length 15m
+    #[arg(long = "break-every", value_name = "ROTATIONS")]
+    pub(crate) break_every: Option<NonZeroU32>,
+    /// Length of each break
+    #[arg(long = "break-length", value_name = "DURATION", value_parser = parse_duration_arg, default_value = "10m", requires = "break_every")]
+    pub(crate) break_length: TimeDelta,
+}
+
+/// Input of the user while the timer is running
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum TimerInput {
+    /// Pauses/resumes the countdown or starts the next rotation
+    Enter,
+    Quit,
+}
+
+fn parse_duration_arg(value: &str) -> std::result::Result<TimeDelta, String> {
+    parse_duration(value)
+        .ok_or_else(|| format!("Invalid duration: {value}. Expected e.g. 30s, 10m or 1h"))
+}
+
+impl Timer {
+    const BELL: char = '\x07';
+
+    pub(crate) fn handle(
+        &self,
+        team_member_repo: &impl TeamMemberRepo,
+        mob_repo: &impl MobSessionRepo,
+        out: &mut impl Write,
+    ) -> Result<()> {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for line in io::stdin().lock().lines() {
+                let Ok(line) = line else { break };
+                let input = match line.trim() {
+                    "q" | "quit" => TimerInput::Quit,
+                    _ => TimerInput::Enter,
+                };
+                if sender.send(input).is_err() {
+                    break;
+                }
+            }
+        });
+
+        self.run(
+            team_member_repo,
+            mob_repo,
+            &receiver,
+            Duration::from_secs(1),
+            out,
+        )
+    }
+
+    /// Runs rotations until the user quits, counting down once every tick
+    pub(crate) fn run(
+        &self,
+        team_member_repo: &impl TeamMemberRepo,
+        mob_repo: &impl MobSessionRepo,
+        input: &Receiver<TimerInput>,
+        tick: Duration,
+        out: &mut impl Write,
+    ) -> Result<()> {
+        let (scope, coauthors) = mob_repo.list_active_coauthors()?;
+        let driver = mob_repo.get_driver(scope)?;
+        let rotation = self.get_rotation(team_member_repo, mob_repo, scope, &driver, &coauthors)?;
+
+        // Drivers are only switched automatically if the session already has a driver
+        let switch_drivers = driver.is_some();
+        let mut current = driver
+            .and_then(|driver| rotation.iter().position(|x| x.to_string() == driver))
+            .unwrap_or_default();
+
+        for rotation_count in 1.. {
+            writeln!(
+                out,
+                "{} is driving. Press Enter to pause/resume or q and Enter to quit",
+                rotation[current]
+            )?;
+            if !Self::countdown("Rotation", self.duration, input, tick, out)? {
+                return Ok(());
+            }
+
+            current = (current + 1) % rotation.len();
+            let next = &rotation[current];
+            writeln!(out, "{}Time's up! {next} drives next", Self::BELL)?;
+            if switch_drivers {
+                switch_driver(mob_repo, scope, next)?;
+                writeln!(out, "Driver: {next}")?;
+            }
+
+            if let Some(break_every) = self.break_every
+                && rotation_count % break_every.get() == 0
+            {
+                writeln!(out, "Time for a break!")?;
+                if !Self::countdown("Break", self.break_length, input, tick, out)? {
+                    return Ok(());
+                }
+                writeln!(out, "{}Break is over!", Self::BELL)?;
+            }
+
+            writeln!(
+                out,
+                "Press Enter to start the next rotation or q and Enter to quit"
+            )?;
+            if input.recv() != Ok(TimerInput::Enter) {
+                return Ok(());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Gets team members in the rotation order, saving it in the session if it has changed
+    fn get_rotation(
+        &self,
+        team_member_repo: &impl TeamMemberRepo,
+        mob_repo: &impl MobSessionRepo,
+        scope: MobSessionScope,
+        driver: &Option<String>,
+        coauthors: &[String],
+    ) -> Result<Vec<TeamMember>> {
+        let saved_keys = mob_repo.list_rotation(scope)?;
+
+        let rotation = match &self.rotation {
+            Some(tokens) => {
+                let mut resolver = CoauthorResolver::new(team_member_repo, false)?;
+                tokens
+                    .iter()
+                    .map(|x| resolver.resolve_team_member(x))
+                    .collect::<Result<Vec<_>>>()?
+            }
+            None => {
+                let keys = if saved_keys.is_empty() {
+                    // Only members of the session who are team members can be rotated
+                    let team_members = team_member_repo.list()?;
+                    driver
+                        .iter()
+                        .chain(coauthors)
+                        .filter_map(|x| team_members.iter().find(|y| y.to_string() == *x))
+                        .map(|x| x.key.to_owned())
+                        .collect()
+                } else {
+                    saved_keys.to_owned()
+                };
+                keys.iter()
+                    .map(|key| match team_member_repo.get(key)? {
+                        Some(team_member) => Ok(team_member),
+                        None => Err(format!("No team member found with key: {key}").into()),
+                    })
+                    .collect::<Result<Vec<_>>>()?
+            }
+        };
+
+        if rotation.is_empty() {
+            return Err(
+                "No team member(s) found in the mob session. Use --rotation to set the rotation order"
+                    .into(),
+            );
+        }
+
+        let keys: Vec<String> = rotation.iter().map(|x| x.key.to_owned()).collect();
+        if keys != saved_keys {
+            mob_repo.set_rotation(scope, &keys)?;
+        }
+
+        Ok(rotation)
+    }
+
+    /// Counts down the duration, returning false if the user quits
+    fn countdown(
+        label: &str,
+        duration: TimeDelta,
+        input: &Receiver<TimerInput>,
+        tick: Duration,
+        out: &mut impl Write,
+    ) -> Result<bool> {
+        let mut remaining = duration.num_seconds();
+        let mut paused = false;
+
+        while remaining > 0 {
+            let status = if paused { " (paused)" } else { "         " };
+            write!(
+                out,
+                "\r{label} {:02}:{:02}{status}",
+                remaining / 60,
+                remaining % 60
+            )?;
+            out.flush()?;
+
+            let result = if paused {
+                input.recv().map_err(|_| RecvTimeoutError::Disconnected)
+            } else {
+                input.recv_timeout(tick)
+            };
+
+            match result {
+                Ok(TimerInput::Enter) => paused = !paused,
+                Ok(TimerInput::Quit) => {
+                    writeln!(out)?;
+                    return Ok(false);
+                }
+                Err(RecvTimeoutError::Timeout) => remaining -= 1,
+                // Without input, the countdown can only keep going
+                Err(RecvTimeoutError::Disconnected) => {
+                    paused = false;
+                    thread::sleep(tick);
+                    remaining -= 1;
+                }
+            }
+        }
+
+        writeln!(out, "\r{label} 00:00         ")?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::{MockMobSessionRepo, MockTeamMemberRepo};
+    use mockall::predicate;
+
+    const TICK: Duration = Duration::from_millis(1);
+
+    fn team_members() -> Result<Vec<TeamMember>> {
+        Ok(vec![
+            TeamMember::new("lm", "Leo Messi", "leo.messi@example.com")?,
+            TeamMember::new("em", "Emi Martinez", "emi.martinez@example.com")?,
+        ])
+    }
+
+    fn create_mock_team_member_repo() -> Result<MockTeamMemberRepo> {
+        let team_members = team_members()?;
+        let cloned_team_members = team_members.clone();
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        mock_team_member_repo
+            .expect_list()
+            .returning(move || Ok(cloned_team_members.clone()));
+        mock_team_member_repo
+            .expect_get()
+            .returning(move |key| Ok(team_members.iter().find(|x| x.key == key).cloned()));
+        mock_team_member_repo
+            .expect_list_aliases()
+            .returning(|| Ok(vec![]));
+        Ok(mock_team_member_repo)
+    }
+
+    fn create_timer_cmd(rotation: Option<Vec<String>>, break_every: Option<u32>) -> Timer {
+        Timer {
+            duration: TimeDelta::seconds(2),
+            rotation,
+            break_every: break_every.and_then(NonZeroU32::new),
+            break_length: TimeDelta::seconds(1),
+        }
+    }
+
+    fn create_input(inputs: Vec<TimerInput>) -> Receiver<TimerInput> {
+        let (sender, receiver) = mpsc::channel();
+        for input in inputs {
+            sender.send(input).unwrap();
+        }
+        receiver
+    }
+
+    #[test]
+    fn test_timer_given_driver() -> Result<()> {
+        let mock_team_member_repo = create_mock_team_member_repo()?;
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo.expect_list_active_coauthors().returning(|| {
+            Ok((
+                MobSessionScope::Local,
+                vec!["Emi Martinez <emi.martinez@example.com>".to_owned()],
+            ))
+        });
+        mock_mob_repo
+            .expect_get_driver()
+            .returning(|_| Ok(Some("Leo Messi <leo.messi@example.com>".to_owned())));
+        mock_mob_repo
+            .expect_list_rotation()
+            .returning(|_| Ok(vec![]));
+        mock_mob_repo
+            .expect_set_rotation()
+            .withf(|scope, keys| *scope == MobSessionScope::Local && keys == ["lm", "em"])
+            .once()
+            .returning(|_, _| Ok(()));
+        mock_mob_repo
+            .expect_list_coauthors()
+            .returning(|_| Ok(vec!["Emi Martinez <emi.martinez@example.com>".to_owned()]));
+        mock_mob_repo
+            .expect_set_driver()
+            .withf(|scope, driver| *scope == MobSessionScope::Local && driver.key == "em")
+            .once()
+            .returning(|_, _| Ok(()));
+        mock_mob_repo
+            .expect_remove_coauthor()
+            .with(
+                predicate::eq(MobSessionScope::Local),
+                predicate::eq("Emi Martinez <emi.martinez@example.com>"),
+            )
+            .once()
+            .returning(|_, _| Ok(()));
+        mock_mob_repo
+            .expect_add_coauthor()
+            .with(
+                predicate::eq(MobSessionScope::Local),
+                predicate::eq("Leo Messi <leo.messi@example.com>"),
+            )
+            .once()
+            .returning(|_, _| Ok(()));
+
+        let timer_cmd = create_timer_cmd(None, None);
+
+        let mut out = Vec::new();
+        let input = create_input(vec![]);
+        timer_cmd.run(
+            &mock_team_member_repo,
+            &mock_mob_repo,
+            &input,
+            TICK,
+            &mut out,
+        )?;
+
+        assert_eq!(
+            String::from_utf8(out)?,
+            "Leo Messi <leo.messi@example.com> is driving. Press Enter to pause/resume or q and Enter to quit\n\
+             \rRotation 00:02         \rRotation 00:01         \rRotation 00:00         \n\
+             \x07Time's up! Emi Martinez <emi.martinez@example.com> drives next\n\
+             Driver: Emi Martinez <emi.martinez@example.com>\n\
+             Press Enter to start the next rotation or q and Enter to quit\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_timer_given_pause_and_quit() -> Result<()> {
+        let mock_team_member_repo = create_mock_team_member_repo()?;
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo
+            .expect_list_active_coauthors()
+            .returning(|| Ok((MobSessionScope::Global, vec![])));
+        mock_mob_repo.expect_get_driver().returning(|_| Ok(None));
+        mock_mob_repo
+            .expect_list_rotation()
+            .returning(|_| Ok(vec!["em".to_owned(), "lm".to_owned()]));
+        mock_mob_repo.expect_set_rotation().never();
+        mock_mob_repo.expect_set_driver().never();
+
+        let timer_cmd = create_timer_cmd(None, None);
+
+        let mut out = Vec::new();
+        // pausing, resuming and then quitting during the first rotation
+        let input = create_input(vec![TimerInput::Enter, TimerInput::Enter, TimerInput::Quit]);
+        timer_cmd.run(
+            &mock_team_member_repo,
+            &mock_mob_repo,
+            &input,
+            TICK,
+            &mut out,
+        )?;
+
+        assert_eq!(
+            String::from_utf8(out)?,
+            "Emi Martinez <emi.martinez@example.com> is driving. Press Enter to pause/resume or q and Enter to quit\n\
+             \rRotation 00:02         \rRotation 00:02 (paused)\rRotation 00:02         \n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_timer_given_break() -> Result<()> {
+        let mock_team_member_repo = create_mock_team_member_repo()?;
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo
+            .expect_list_active_coauthors()
+            .returning(|| Ok((MobSessionScope::Global, vec![])));
+        mock_mob_repo.expect_get_driver().returning(|_| Ok(None));
+        mock_mob_repo
+            .expect_list_rotation()
+            .returning(|_| Ok(vec!["lm".to_owned()]));
+        mock_mob_repo
+            .expect_set_rotation()
+            .withf(|_, keys| keys == ["em", "lm"])
+            .once()
+            .returning(|_, _| Ok(()));
+
+        let timer_cmd = create_timer_cmd(Some(vec!["em".to_owned(), "lm".to_owned()]), Some(1));
+
+        let mut out = Vec::new();
+        let input = create_input(vec![]);
+        timer_cmd.run(
+            &mock_team_member_repo,
+            &mock_mob_repo,
+            &input,
+            TICK,
+            &mut out,
+        )?;
+
+        let out = String::from_utf8(out)?;
+        assert!(out.contains("Time's up! Leo Messi <leo.messi@example.com> drives next\n"));
+        assert!(out.contains(
+            "Time for a break!\n\rBreak 00:01         \rBreak 00:00         \n\x07Break is over!\n"
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_timer_given_no_team_members_in_mob_session() -> Result<()> {
+        let mock_team_member_repo = create_mock_team_member_repo()?;
+        let mut mock_mob_repo = MockMobSessionRepo::new();
+        mock_mob_repo.expect_list_active_coauthors().returning(|| {
+            Ok((
+                MobSessionScope::Global,
+                vec!["Diego Maradona <diego.maradona@example.com>".to_owned()],
+            ))
+        });
+        mock_mob_repo.expect_get_driver().returning(|_| Ok(None));
+        mock_mob_repo
+            .expect_list_rotation()
+            .returning(|_| Ok(vec![]));
+
+        let timer_cmd = create_timer_cmd(None, None);
+
+        let mut out = Vec::new();
+        let input = create_input(vec![]);
+        let result = timer_cmd.run(
+            &mock_team_member_repo,
+            &mock_mob_repo,
+            &input,
+            TICK,
+            &mut out,
+        );
+
+        assert!(result.is_err_and(|x| x.to_string()
+            == "No team member(s) found in the mob session. Use --rotation to set the rotation order"));
+
+        Ok(())
+    }
+}
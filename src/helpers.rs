@@ -1,4 +1,6 @@
 use crate::Result;
+use chrono::TimeDelta;
+use std::path::PathBuf;
 use std::process::Command;
 
 pub struct CmdOutput {
@@ -29,10 +31,52 @@ impl CommandRunner for StdCommandRunner {
     }
 }
 
+/// Gets the root directory of the current git repository, which is none outside a git repository
+pub fn get_repo_dir(command_runner: &impl CommandRunner) -> Result<Option<PathBuf>> {
+    let output = command_runner.execute("git", &["rev-parse", "--show-toplevel"])?;
+
+    if output.status_code != Some(0) {
+        return Ok(None);
+    }
+
+    Ok(Some(PathBuf::from(
+        String::from_utf8(output.stdout)?.trim_end(),
+    )))
+}
+
+/// Parses a positive duration with a unit e.g. 30s, 10m, 8h or 1d
+pub fn parse_duration(value: &str) -> Option<TimeDelta> {
+    let value = value.trim().to_lowercase();
+    let unit_index = value.find(|x: char| !x.is_ascii_digit())?;
+    let (amount, unit) = value.split_at(unit_index);
+    let amount: i64 = amount.parse().ok()?;
+    let duration = match unit.trim() {
+        "s" => TimeDelta::try_seconds(amount),
+        "m" | "min" => TimeDelta::try_minutes(amount),
+        "h" => TimeDelta::try_hours(amount),
+        "d" => TimeDelta::try_days(amount),
+        _ => None,
+    };
+
+    duration.filter(|_| amount > 0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("45s"), Some(TimeDelta::seconds(45)));
+        assert_eq!(parse_duration("10m"), Some(TimeDelta::minutes(10)));
+        assert_eq!(parse_duration("8H"), Some(TimeDelta::hours(8)));
+        assert_eq!(parse_duration("2d"), Some(TimeDelta::days(2)));
+        assert_eq!(parse_duration("0m"), None);
+        assert_eq!(parse_duration("10"), None);
+        assert_eq!(parse_duration("m"), None);
+        assert_eq!(parse_duration("10 minutes"), None);
+    }
+
     #[test]
     fn test_execute_success() -> Result<()> {
         let result = StdCommandRunner.execute("git", &["--version"])?;
@@ -4,3 +4,4 @@ pub mod cli;
 mod commands;
 pub mod helpers;
 pub mod repositories;
+pub mod validation;
@@ -1,18 +1,75 @@
 use git_mob_tool::{
     Result, cli,
-    helpers::StdCommandRunner,
-    repositories::{GitConfigMobRepo, GitConfigTeamMemberRepo},
+    helpers::{StdCommandRunner, get_repo_dir},
+    repositories::{
+        CoauthorsFileTeamMemberRepo, FileHistoryRepo, GitCommitLogRepo, GitConfigMobRepo,
+        GitConfigPresetRepo, GitConfigTeamGroupRepo, GitConfigTeamMemberRepo,
+        LayeredTeamMemberRepo, RosterFileTeamMemberRepo, SyncedTeamMemberRepo, TeamMemberRepo,
+        TeamMemberSource,
+    },
 };
 use std::io::stdout;
 
 fn main() -> Result<()> {
-    let team_member_repo = GitConfigTeamMemberRepo {
+    // team members are resolved from the repo roster file, then the user's global git config
+    // and then the system-wide roster file
+    let mut layers: Vec<(TeamMemberSource, Box<dyn TeamMemberRepo>)> = Vec::new();
+    if let Some(repo_roster) = RosterFileTeamMemberRepo::find(&StdCommandRunner)? {
+        layers.push((TeamMemberSource::Repo, Box::new(repo_roster)));
+    }
+    let user_repo = GitConfigTeamMemberRepo {
+        command_runner: StdCommandRunner,
+    };
+    match CoauthorsFileTeamMemberRepo::find(&StdCommandRunner)? {
+        // ~/.git-coauthors of the JavaScript git-mob tool is kept in sync with the user's git config
+        // if they opted in to it, and the team members only in the file are read from a further layer
+        Some(coauthors_file_repo) => {
+            let path = coauthors_file_repo.path.clone();
+            layers.push((
+                TeamMemberSource::User,
+                Box::new(SyncedTeamMemberRepo {
+                    primary: user_repo,
+                    mirror: coauthors_file_repo,
+                }),
+            ));
+            layers.push((
+                TeamMemberSource::CoauthorsFile,
+                Box::new(CoauthorsFileTeamMemberRepo { path }),
+            ));
+        }
+        None => layers.push((TeamMemberSource::User, Box::new(user_repo))),
+    }
+    if let Some(system_roster) = RosterFileTeamMemberRepo::find_system() {
+        layers.push((TeamMemberSource::System, Box::new(system_roster)));
+    }
+
+    let team_member_repo = LayeredTeamMemberRepo::new(layers);
+    let team_group_repo = GitConfigTeamGroupRepo {
         command_runner: StdCommandRunner,
     };
     let mob_repo = GitConfigMobRepo {
         command_runner: StdCommandRunner,
     };
+    let commit_log_repo = GitCommitLogRepo {
+        command_runner: StdCommandRunner,
+    };
+    let history_repo = FileHistoryRepo {
+        path: FileHistoryRepo::default_path(),
+        repo_dir: get_repo_dir(&StdCommandRunner)?,
+    };
+    let preset_repo = GitConfigPresetRepo {
+        command_runner: StdCommandRunner,
+    };
     let out = &mut stdout();
-    cli::run(&team_member_repo, &mob_repo, out)?;
+    cli::run(
+        &team_member_repo,
+        &team_group_repo,
+        &mob_repo,
+        &commit_log_repo,
+        &history_repo,
+        &preset_repo,
+        &StdCommandRunner,
+        out,
+    )?;
     Ok(())
 }
@@ -0,0 +1,367 @@
+use crate::Result;
+use crate::helpers::CommandRunner;
+use crate::repositories::{TeamMember, TeamMemberRepo};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+/// Team member repository backed by the `~/.git-coauthors` file of the JavaScript git-mob tool
+///
+/// Example file:
+///
+/// ```json
+/// {
+///   "coauthors": {
+///     "lm": {
+///       "name": "Leo Messi",
+///       "email": "leo.messi@example.com"
+///     }
+///   }
+/// }
+/// ```
+pub struct CoauthorsFileTeamMemberRepo {
+    pub path: PathBuf,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CoauthorsFileEntry {
+    name: String,
+    email: String,
+}
+
+impl CoauthorsFileTeamMemberRepo {
+    pub const FILE_NAME: &'static str = ".git-coauthors";
+    const COAUTHORS_KEY: &'static str = "coauthors";
+
+    /// Environment variable which overrides the location of the file (same as the JavaScript git-mob tool)
+    pub const PATH_ENV_VAR: &'static str = "GITMOB_COAUTHORS_PATH";
+
+    /// Global git config which opts in to using and syncing the file
+    pub const SYNC_KEY: &'static str = "coauthors-mob-settings.syncCoauthorsFile";
+
+    /// Finds the `~/.git-coauthors` file if it exists and syncing it is enabled with the global git config
+    pub fn find(command_runner: &impl CommandRunner) -> Result<Option<Self>> {
+        let output = command_runner.execute(
+            "git",
+            &[
+                "config",
+                "--global",
+                "--type",
+                "bool",
+                "--get",
+                Self::SYNC_KEY,
+            ],
+        )?;
+
+        match output.status_code {
+            Some(0) if String::from_utf8(output.stdout)?.trim() == "true" => {}
+            Some(0 | 1) => return Ok(None),
+            Some(code) => {
+                return Err(format!("Git config command exited with status code: {code}").into());
+            }
+            None => return Err("Git config command terminated by signal".into()),
+        }
+
+        let path = match env::var_os(Self::PATH_ENV_VAR) {
+            Some(path) => PathBuf::from(path),
+            None => match env::home_dir() {
+                Some(home_dir) => home_dir.join(Self::FILE_NAME),
+                None => return Ok(None),
+            },
+        };
+
+        Ok(path.is_file().then_some(Self { path }))
+    }
+
+    /// Parses team members from the content of a `~/.git-coauthors` file
+    pub fn deserialize(content: &str) -> Result<Vec<TeamMember>> {
+        let document: Value = serde_json::from_str(content)?;
+
+        match document.get(Self::COAUTHORS_KEY) {
+            Some(Value::Object(coauthors)) => coauthors
+                .iter()
+                .map(|(key, value)| {
+                    let entry = CoauthorsFileEntry::deserialize(value)
+                        .map_err(|err| format!("Invalid team member with key {key}: {err}"))?;
+                    TeamMember::new(key, &entry.name, &entry.email)
+                })
+                .collect(),
+            Some(_) => Err(format!("Invalid {}. Expected an object", Self::COAUTHORS_KEY).into()),
+            None => Ok(vec![]),
+        }
+    }
+
+    /// Formats team members as the content of a `~/.git-coauthors` file
+    pub fn serialize(team_members: &[TeamMember]) -> Result<String> {
+        let mut document = Map::new();
+        document.insert(Self::COAUTHORS_KEY.to_owned(), Value::Object(Map::new()));
+        let mut document = Value::Object(document);
+
+        for team_member in team_members {
+            Self::insert(&mut document, team_member)?;
+        }
+
+        Self::to_string(&document)
+    }
+
+    fn insert(document: &mut Value, team_member: &TeamMember) -> Result<()> {
+        let coauthors = document
+            .as_object_mut()
+            .ok_or("Invalid file. Expected an object")?
+            .entry(Self::COAUTHORS_KEY)
+            .or_insert_with(|| Value::Object(Map::new()))
+            .as_object_mut()
+            .ok_or_else(|| format!("Invalid {}. Expected an object", Self::COAUTHORS_KEY))?;
+
+        coauthors.insert(
+            team_member.key.to_owned(),
+            serde_json::to_value(CoauthorsFileEntry {
+                name: team_member.name.to_owned(),
+                email: team_member.email.to_owned(),
+            })?,
+        );
+
+        Ok(())
+    }
+
+    fn to_string(document: &Value) -> Result<String> {
+        let mut output = serde_json::to_string_pretty(document)?;
+        output.push('\n');
+        Ok(output)
+    }
+
+    fn read(&self) -> Result<String> {
+        fs::read_to_string(&self.path)
+            .map_err(|err| format!("Failed to read {}: {err}", self.path.display()).into())
+    }
+
+    fn read_document(&self) -> Result<Value> {
+        serde_json::from_str(&self.read()?)
+            .map_err(|err| format!("Failed to parse {}: {err}", self.path.display()).into())
+    }
+
+    fn write_document(&self, document: &Value) -> Result<()> {
+        fs::write(&self.path, Self::to_string(document)?)?;
+        Ok(())
+    }
+}
+
+impl TeamMemberRepo for CoauthorsFileTeamMemberRepo {
+    fn list(&self) -> Result<Vec<TeamMember>> {
+        Self::deserialize(&self.read()?)
+            .map_err(|err| format!("Failed to parse {}: {err}", self.path.display()).into())
+    }
+
+    fn get(&self, key: &str) -> Result<Option<TeamMember>> {
+        Ok(self.list()?.into_iter().find(|x| x.key == key))
+    }
+
+    fn remove(&self, key: &str) -> Result<()> {
+        let mut document = self.read_document()?;
+
+        if let Some(coauthors) = document
+            .get_mut(Self::COAUTHORS_KEY)
+            .and_then(Value::as_object_mut)
+        {
+            coauthors.shift_remove(key);
+        }
+
+        self.write_document(&document)
+    }
+
+    fn add(&self, team_member: &TeamMember) -> Result<()> {
+        let mut document = self.read_document()?;
+        Self::insert(&mut document, team_member)?;
+        self.write_document(&document)
+    }
+
+    fn list_hidden(&self) -> Result<Vec<String>> {
+        Ok(vec![])
+    }
+
+    fn hide(&self, _key: &str) -> Result<()> {
+        Err(format!(
+            "Hiding team members is not supported in {}",
+            self.path.display()
+        )
+        .into())
+    }
+
+    fn list_aliases(&self) -> Result<Vec<(String, String)>> {
+        Ok(vec![])
+    }
+
+    fn add_alias(&self, _alias: &str, _key: &str) -> Result<()> {
+        Err(format!("Aliases are not supported in {}", self.path.display()).into())
+    }
+
+    fn remove_alias(&self, _alias: &str) -> Result<()> {
+        Err(format!("Aliases are not supported in {}", self.path.display()).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{CmdOutput, MockCommandRunner};
+    use tempfile::{TempDir, tempdir};
+
+    const COAUTHORS_FILE: &str = r#"{
+  "coauthors": {
+    "lm": {
+      "name": "Leo Messi",
+      "email": "leo.messi@example.com"
+    },
+    "em": {
+      "name": "Emi Martinez",
+      "email": "emi.martinez@example.com"
+    }
+  }
+}
+"#;
+
+    fn create_coauthors_file(content: &str) -> Result<(TempDir, CoauthorsFileTeamMemberRepo)> {
+        let dir = tempdir()?;
+        let path = dir.path().join(CoauthorsFileTeamMemberRepo::FILE_NAME);
+        fs::write(&path, content)?;
+        Ok((dir, CoauthorsFileTeamMemberRepo { path }))
+    }
+
+    fn team_members() -> Result<Vec<TeamMember>> {
+        Ok(vec![
+            TeamMember::new("lm", "Leo Messi", "leo.messi@example.com")?,
+            TeamMember::new("em", "Emi Martinez", "emi.martinez@example.com")?,
+        ])
+    }
+
+    #[test]
+    fn test_find_when_sync_not_enabled() -> Result<()> {
+        let mut command_runner = MockCommandRunner::new();
+        command_runner
+            .expect_execute()
+            .withf(|program, args| {
+                program == "git"
+                    && args
+                        == [
+                            "config",
+                            "--global",
+                            "--type",
+                            "bool",
+                            "--get",
+                            "coauthors-mob-settings.syncCoauthorsFile",
+                        ]
+            })
+            .once()
+            .returning(|_, _| {
+                Ok(CmdOutput {
+                    stdout: vec![],
+                    stderr: vec![],
+                    status_code: Some(1),
+                })
+            });
+
+        assert!(CoauthorsFileTeamMemberRepo::find(&command_runner)?.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list() -> Result<()> {
+        let (_dir, coauthors_file_repo) = create_coauthors_file(COAUTHORS_FILE)?;
+
+        assert_eq!(coauthors_file_repo.list()?, team_members()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_when_invalid_team_member() -> Result<()> {
+        let (_dir, coauthors_file_repo) =
+            create_coauthors_file(r#"{ "coauthors": { "lm": { "name": "Leo Messi" } } }"#)?;
+
+        let result = coauthors_file_repo.list();
+
+        assert!(result.is_err_and(|x| x.to_string()
+            == format!(
+                "Failed to parse {}: Invalid team member with key lm: missing field `email`",
+                coauthors_file_repo.path.display()
+            )));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get() -> Result<()> {
+        let (_dir, coauthors_file_repo) = create_coauthors_file(COAUTHORS_FILE)?;
+
+        assert_eq!(
+            coauthors_file_repo.get("em")?,
+            Some(TeamMember::new(
+                "em",
+                "Emi Martinez",
+                "emi.martinez@example.com"
+            )?)
+        );
+        assert_eq!(coauthors_file_repo.get("ad")?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_and_remove() -> Result<()> {
+        let (_dir, coauthors_file_repo) = create_coauthors_file("{\n  \"coauthors\": {}\n}\n")?;
+
+        for team_member in team_members()? {
+            coauthors_file_repo.add(&team_member)?;
+        }
+
+        assert_eq!(
+            fs::read_to_string(&coauthors_file_repo.path)?,
+            COAUTHORS_FILE
+        );
+
+        coauthors_file_repo.remove("lm")?;
+
+        assert_eq!(
+            coauthors_file_repo.list()?,
+            vec![TeamMember::new(
+                "em",
+                "Emi Martinez",
+                "emi.martinez@example.com"
+            )?]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_serialize_and_deserialize() -> Result<()> {
+        assert_eq!(
+            CoauthorsFileTeamMemberRepo::serialize(&team_members()?)?,
+            COAUTHORS_FILE
+        );
+        assert_eq!(
+            CoauthorsFileTeamMemberRepo::deserialize(COAUTHORS_FILE)?,
+            team_members()?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_hide() -> Result<()> {
+        let (_dir, coauthors_file_repo) = create_coauthors_file(COAUTHORS_FILE)?;
+
+        let result = coauthors_file_repo.hide("lm");
+
+        assert!(result.is_err_and(|x| x.to_string()
+            == format!(
+                "Hiding team members is not supported in {}",
+                coauthors_file_repo.path.display()
+            )));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,331 @@
+use crate::Result;
+use crate::helpers::CommandRunner;
+use chrono::{DateTime, Local};
+
+/// Person who authored or co-authored a commit
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Contributor {
+    pub name: String,
+    pub email: String,
+}
+
+/// Commit with its author and the co-authors in its Co-authored-by trailers
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Commit {
+    pub authored_at: DateTime<Local>,
+    pub author: Contributor,
+    pub coauthors: Vec<Contributor>,
+}
+
+#[cfg(test)]
+use mockall::{automock, predicate::*};
+#[cfg_attr(test, automock)]
+pub trait CommitLogRepo {
+    /// Lists authors and co-authors of commits from the most recent to the oldest
+    fn list_contributors<'a>(
+        &self,
+        since: Option<&'a str>,
+        revision_range: Option<&'a str>,
+    ) -> Result<Vec<Contributor>>;
+    /// Lists commits from the most recent to the oldest
+    fn list_commits<'a>(
+        &self,
+        since: Option<&'a str>,
+        revision_range: Option<&'a str>,
+    ) -> Result<Vec<Commit>>;
+    /// Gets the user who makes commits in the current repository (i.e. the committer identity)
+    fn get_user(&self) -> Result<Option<Contributor>>;
+}
+
+pub struct GitCommitLogRepo<Cmd> {
+    pub command_runner: Cmd,
+}
+
+impl<Cmd: CommandRunner> GitCommitLogRepo<Cmd> {
+    // author (respecting .mailmap) followed by values of Co-authored-by trailers, one per line
+    const LOG_FORMAT: &'static str = "--format=%aN <%aE>%n%(trailers:key=Co-authored-by,valueonly)";
+    // same as above but each commit starts with a record separator followed by the author timestamp
+    const COMMITS_LOG_FORMAT: &'static str =
+        "--format=%x1e%at%n%aN <%aE>%n%(trailers:key=Co-authored-by,valueonly)";
+
+    const EXIT_CODE_SUCCESS: i32 = 0;
+
+    fn parse_contributor(line: &str) -> Option<Contributor> {
+        let (name, email) = line.trim().strip_suffix('>')?.rsplit_once('<')?;
+        let (name, email) = (name.trim(), email.trim());
+
+        if name.is_empty() || email.is_empty() {
+            return None;
+        }
+
+        Some(Contributor {
+            name: name.to_owned(),
+            email: email.to_owned(),
+        })
+    }
+
+    fn parse_commit(record: &str) -> Option<Commit> {
+        let mut lines = record.lines();
+        let timestamp = lines.next()?.trim().parse().ok()?;
+        let authored_at = DateTime::from_timestamp(timestamp, 0)?.with_timezone(&Local);
+        let author = Self::parse_contributor(lines.next()?)?;
+        let coauthors = lines.filter_map(Self::parse_contributor).collect();
+
+        Some(Commit {
+            authored_at,
+            author,
+            coauthors,
+        })
+    }
+
+    fn log(
+        &self,
+        format: &str,
+        since: Option<&str>,
+        revision_range: Option<&str>,
+    ) -> Result<String> {
+        let since_arg = since.map(|x| format!("--since={x}"));
+
+        let mut args = vec!["log", format];
+        args.extend(since_arg.as_deref());
+        args.extend(revision_range);
+        args.push("--");
+
+        let output = self.command_runner.execute("git", &args)?;
+
+        match output.status_code {
+            Some(Self::EXIT_CODE_SUCCESS) => Ok(String::from_utf8(output.stdout)?),
+            _ => Err(format!(
+                "Failed to read git log: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )
+            .into()),
+        }
+    }
+}
+
+impl<Cmd: CommandRunner> CommitLogRepo for GitCommitLogRepo<Cmd> {
+    fn list_contributors(
+        &self,
+        since: Option<&str>,
+        revision_range: Option<&str>,
+    ) -> Result<Vec<Contributor>> {
+        Ok(self
+            .log(Self::LOG_FORMAT, since, revision_range)?
+            .lines()
+            .filter_map(Self::parse_contributor)
+            .collect())
+    }
+
+    fn list_commits(
+        &self,
+        since: Option<&str>,
+        revision_range: Option<&str>,
+    ) -> Result<Vec<Commit>> {
+        Ok(self
+            .log(Self::COMMITS_LOG_FORMAT, since, revision_range)?
+            .split('\x1e')
+            .filter_map(Self::parse_commit)
+            .collect())
+    }
+
+    fn get_user(&self) -> Result<Option<Contributor>> {
+        let output = self
+            .command_runner
+            .execute("git", &["var", "GIT_COMMITTER_IDENT"])?;
+
+        if output.status_code != Some(Self::EXIT_CODE_SUCCESS) {
+            return Ok(None);
+        }
+
+        // identity is followed by the timestamp and timezone e.g. "Leo Messi <leo.messi@example.com> 1760778000 +1100"
+        let ident = String::from_utf8(output.stdout)?;
+        Ok(ident
+            .rsplit_once('>')
+            .and_then(|(contributor, _)| Self::parse_contributor(&format!("{contributor}>"))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{CmdOutput, MockCommandRunner};
+
+    fn create_mock_command_runner(
+        args: &[&str],
+        stdout: Vec<u8>,
+        stderr: Vec<u8>,
+        status_code: Option<i32>,
+    ) -> MockCommandRunner {
+        let cloned_args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+
+        let mut mock_cmd_runner = MockCommandRunner::new();
+        mock_cmd_runner
+            .expect_execute()
+            .once()
+            .withf(move |program, args| program == "git" && args == cloned_args)
+            .returning(move |_, _| {
+                Ok(CmdOutput {
+                    stdout: stdout.clone(),
+                    stderr: stderr.clone(),
+                    status_code,
+                })
+            });
+        mock_cmd_runner
+    }
+
+    #[test]
+    fn test_list_contributors() -> Result<()> {
+        let args = &[
+            "log",
+            "--format=%aN <%aE>%n%(trailers:key=Co-authored-by,valueonly)",
+            "--",
+        ];
+        let stdout = b"Leo Messi <leo.messi@example.com>\nEmi Martinez <emi.martinez@example.com>\nAngel Di Maria <angel.dimaria@example.com>\n\nEmi Martinez <emi.martinez@example.com>\n\n".into();
+        let command_runner = create_mock_command_runner(args, stdout, vec![], Some(0));
+        let commit_log_repo = GitCommitLogRepo { command_runner };
+
+        let result = commit_log_repo.list_contributors(None, None)?;
+
+        assert_eq!(
+            result,
+            [
+                ("Leo Messi", "leo.messi@example.com"),
+                ("Emi Martinez", "emi.martinez@example.com"),
+                ("Angel Di Maria", "angel.dimaria@example.com"),
+                ("Emi Martinez", "emi.martinez@example.com"),
+            ]
+            .map(|(name, email)| Contributor {
+                name: name.to_owned(),
+                email: email.to_owned(),
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_contributors_given_since_and_revision_range() -> Result<()> {
+        let args = &[
+            "log",
+            "--format=%aN <%aE>%n%(trailers:key=Co-authored-by,valueonly)",
+            "--since=2 weeks ago",
+            "main..feature",
+            "--",
+        ];
+        let stdout = b"Leo Messi <leo.messi@example.com>\n\nnot a contributor\n".into();
+        let command_runner = create_mock_command_runner(args, stdout, vec![], Some(0));
+        let commit_log_repo = GitCommitLogRepo { command_runner };
+
+        let result =
+            commit_log_repo.list_contributors(Some("2 weeks ago"), Some("main..feature"))?;
+
+        assert_eq!(
+            result,
+            vec![Contributor {
+                name: "Leo Messi".to_owned(),
+                email: "leo.messi@example.com".to_owned(),
+            }]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_commits() -> Result<()> {
+        let args = &[
+            "log",
+            "--format=%x1e%at%n%aN <%aE>%n%(trailers:key=Co-authored-by,valueonly)",
+            "--since=2 weeks ago",
+            "--",
+        ];
+        let stdout = b"\x1e1760778000\nLeo Messi <leo.messi@example.com>\nEmi Martinez <emi.martinez@example.com>\nAngel Di Maria <angel.dimaria@example.com>\n\n\x1e1760691600\nEmi Martinez <emi.martinez@example.com>\n\n".into();
+        let command_runner = create_mock_command_runner(args, stdout, vec![], Some(0));
+        let commit_log_repo = GitCommitLogRepo { command_runner };
+
+        let result = commit_log_repo.list_commits(Some("2 weeks ago"), None)?;
+
+        let contributor = |name: &str, email: &str| Contributor {
+            name: name.to_owned(),
+            email: email.to_owned(),
+        };
+        assert_eq!(
+            result,
+            vec![
+                Commit {
+                    authored_at: DateTime::from_timestamp(1760778000, 0)
+                        .unwrap()
+                        .with_timezone(&Local),
+                    author: contributor("Leo Messi", "leo.messi@example.com"),
+                    coauthors: vec![
+                        contributor("Emi Martinez", "emi.martinez@example.com"),
+                        contributor("Angel Di Maria", "angel.dimaria@example.com"),
+                    ],
+                },
+                Commit {
+                    authored_at: DateTime::from_timestamp(1760691600, 0)
+                        .unwrap()
+                        .with_timezone(&Local),
+                    author: contributor("Emi Martinez", "emi.martinez@example.com"),
+                    coauthors: vec![],
+                },
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_user() -> Result<()> {
+        let stdout = b"Leo Messi <leo.messi@example.com> 1760778000 +1100\n".into();
+        let command_runner =
+            create_mock_command_runner(&["var", "GIT_COMMITTER_IDENT"], stdout, vec![], Some(0));
+        let commit_log_repo = GitCommitLogRepo { command_runner };
+
+        let result = commit_log_repo.get_user()?;
+
+        assert_eq!(
+            result,
+            Some(Contributor {
+                name: "Leo Messi".to_owned(),
+                email: "leo.messi@example.com".to_owned(),
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_user_when_identity_not_set() -> Result<()> {
+        let stderr = b"fatal: empty ident name not allowed\n".into();
+        let command_runner =
+            create_mock_command_runner(&["var", "GIT_COMMITTER_IDENT"], vec![], stderr, Some(128));
+        let commit_log_repo = GitCommitLogRepo { command_runner };
+
+        let result = commit_log_repo.get_user()?;
+
+        assert_eq!(result, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_contributors_when_git_log_fails() -> Result<()> {
+        let args = &[
+            "log",
+            "--format=%aN <%aE>%n%(trailers:key=Co-authored-by,valueonly)",
+            "--",
+        ];
+        let stderr =
+            b"fatal: not a git repository (or any of the parent directories): .git\n".into();
+        let command_runner = create_mock_command_runner(args, vec![], stderr, Some(128));
+        let commit_log_repo = GitCommitLogRepo { command_runner };
+
+        let result = commit_log_repo.list_contributors(None, None);
+
+        assert!(result.is_err_and(|x| x.to_string()
+            == "Failed to read git log: fatal: not a git repository (or any of the parent directories): .git"));
+
+        Ok(())
+    }
+}
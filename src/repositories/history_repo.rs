@@ -0,0 +1,280 @@
+use crate::Result;
+use crate::repositories::MobSessionScope;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+/// How a mob/pair programming session ended
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionEnd {
+    /// Session was cleared with `git mob --clear`
+    Cleared,
+    /// Session was replaced by a new one with `git mob --with`
+    Replaced,
+    /// Session was removed on commit after it expired
+    Expired,
+}
+
+impl fmt::Display for SessionEnd {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SessionEnd::Cleared => write!(f, "cleared"),
+            SessionEnd::Replaced => write!(f, "replaced"),
+            SessionEnd::Expired => write!(f, "expired"),
+        }
+    }
+}
+
+/// Change to a mob/pair programming session which is recorded in the history
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "lowercase")]
+pub enum HistoryEvent {
+    /// Session started with the members
+    Start,
+    /// Members were added to the session
+    Add,
+    /// Session with the members ended
+    End {
+        ended: SessionEnd,
+        started_at: Option<DateTime<Local>>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub timestamp: DateTime<Local>,
+    /// Root of the git repository in which the session changed if any
+    pub repo: Option<PathBuf>,
+    pub scope: MobSessionScope,
+    #[serde(flatten)]
+    pub event: HistoryEvent,
+    pub members: Vec<String>,
+}
+
+#[cfg(test)]
+use mockall::{automock, predicate::*};
+#[cfg_attr(test, automock)]
+pub trait HistoryRepo {
+    /// Appends an entry for the change to the session in the given scope
+    fn append(&self, scope: MobSessionScope, event: HistoryEvent, members: &[String])
+    -> Result<()>;
+    /// Lists entries from the oldest to the most recent
+    fn list(&self) -> Result<Vec<HistoryEntry>>;
+}
+
+/// History repository backed by a JSON lines file in the user's data directory
+pub struct FileHistoryRepo {
+    /// Location of the history file, which is none if the user has no data directory
+    pub path: Option<PathBuf>,
+    pub repo_dir: Option<PathBuf>,
+}
+
+impl FileHistoryRepo {
+    /// Environment variable which overrides the location of the history file
+    pub const FILE_ENV_VAR: &'static str = "GIT_MOB_HISTORY_FILE";
+
+    /// Gets the default location of the history file i.e. `$XDG_DATA_HOME/git-mob/history.jsonl`,
+    /// `~/.local/share/git-mob/history.jsonl` or `%LOCALAPPDATA%\git-mob\history.jsonl` on Windows
+    pub fn default_path() -> Option<PathBuf> {
+        if let Some(path) = env::var_os(Self::FILE_ENV_VAR) {
+            return Some(PathBuf::from(path));
+        }
+
+        let data_dir = if cfg!(windows) {
+            PathBuf::from(env::var_os("LOCALAPPDATA")?)
+        } else {
+            match env::var_os("XDG_DATA_HOME").filter(|x| !x.is_empty()) {
+                Some(data_dir) => PathBuf::from(data_dir),
+                None => PathBuf::from(env::var_os("HOME")?)
+                    .join(".local")
+                    .join("share"),
+            }
+        };
+
+        Some(data_dir.join("git-mob").join("history.jsonl"))
+    }
+
+    fn path(&self) -> Result<&Path> {
+        self.path.as_deref().ok_or_else(|| {
+            format!(
+                "Failed to find a directory for the mob session history. Set {} environment variable",
+                Self::FILE_ENV_VAR
+            )
+            .into()
+        })
+    }
+}
+
+impl HistoryRepo for FileHistoryRepo {
+    fn append(
+        &self,
+        scope: MobSessionScope,
+        event: HistoryEvent,
+        members: &[String],
+    ) -> Result<()> {
+        let entry = HistoryEntry {
+            timestamp: Local::now(),
+            repo: self.repo_dir.clone(),
+            scope,
+            event,
+            members: members.to_vec(),
+        };
+
+        let path = self.path()?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{}", serde_json::to_string(&entry)?)?;
+
+        Ok(())
+    }
+
+    fn list(&self) -> Result<Vec<HistoryEntry>> {
+        let path = self.path()?;
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => return Err(err.into()),
+        };
+
+        contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                serde_json::from_str(line).map_err(|err| {
+                    format!(
+                        "Failed to parse line {} of {}: {err}",
+                        index + 1,
+                        path.display()
+                    )
+                    .into()
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_append_and_list() -> Result<()> {
+        let dir = tempdir()?;
+        let history_repo = FileHistoryRepo {
+            path: Some(dir.path().join("git-mob").join("history.jsonl")),
+            repo_dir: Some(PathBuf::from("/path/to/repo")),
+        };
+        let members = vec![
+            "Leo Messi <leo.messi@example.com>".to_owned(),
+            "Emi Martinez <emi.martinez@example.com>".to_owned(),
+        ];
+        let started_at = Local.with_ymd_and_hms(2026, 10, 18, 9, 0, 0).unwrap();
+
+        history_repo.append(MobSessionScope::Global, HistoryEvent::Start, &members)?;
+        history_repo.append(
+            MobSessionScope::Local,
+            HistoryEvent::End {
+                ended: SessionEnd::Cleared,
+                started_at: Some(started_at),
+            },
+            &members,
+        )?;
+        let entries = history_repo.list()?;
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].scope, MobSessionScope::Global);
+        assert_eq!(entries[0].event, HistoryEvent::Start);
+        assert_eq!(entries[0].members, members);
+        assert_eq!(entries[0].repo, Some(PathBuf::from("/path/to/repo")));
+        assert_eq!(
+            entries[1].event,
+            HistoryEvent::End {
+                ended: SessionEnd::Cleared,
+                started_at: Some(started_at)
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_append_writes_json_lines() -> Result<()> {
+        let dir = tempdir()?;
+        let history_repo = FileHistoryRepo {
+            path: Some(dir.path().join("history.jsonl")),
+            repo_dir: None,
+        };
+
+        history_repo.append(
+            MobSessionScope::Global,
+            HistoryEvent::Add,
+            &["Leo Messi <leo.messi@example.com>".to_owned()],
+        )?;
+        let line = fs::read_to_string(dir.path().join("history.jsonl"))?;
+
+        assert!(line.ends_with(
+            r#""repo":null,"scope":"global","event":"add","members":["Leo Messi <leo.messi@example.com>"]}
+"#
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_when_file_not_found() -> Result<()> {
+        let dir = tempdir()?;
+        let history_repo = FileHistoryRepo {
+            path: Some(dir.path().join("history.jsonl")),
+            repo_dir: None,
+        };
+
+        assert_eq!(history_repo.list()?, vec![]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_when_no_data_directory() -> Result<()> {
+        let history_repo = FileHistoryRepo {
+            path: None,
+            repo_dir: None,
+        };
+
+        let result = history_repo.list();
+
+        assert!(result.is_err_and(|x| x.to_string()
+            == "Failed to find a directory for the mob session history. Set GIT_MOB_HISTORY_FILE environment variable"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_when_file_invalid() -> Result<()> {
+        let dir = tempdir()?;
+        let path = dir.path().join("history.jsonl");
+        fs::write(&path, "\nnot json\n")?;
+        let history_repo = FileHistoryRepo {
+            path: Some(path.clone()),
+            repo_dir: None,
+        };
+
+        let result = history_repo.list();
+
+        assert!(result.is_err_and(|x| {
+            x.to_string()
+                .starts_with(&format!("Failed to parse line 2 of {}: ", path.display()))
+        }));
+
+        Ok(())
+    }
+}
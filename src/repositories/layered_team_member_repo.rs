@@ -0,0 +1,605 @@
+use crate::Result;
+use crate::repositories::{TeamMember, TeamMemberRepo, TeamMemberSource};
+use std::cell::Cell;
+use std::collections::HashSet;
+
+/// Team member repository which resolves team members through an ordered chain of layers
+///
+/// Closer layers override team members with the same key from further layers and can
+/// hide team members of further layers. Changes are written to the user layer unless
+/// another layer is selected with [`TeamMemberRepo::write_to`].
+pub struct LayeredTeamMemberRepo {
+    pub layers: Vec<(TeamMemberSource, Box<dyn TeamMemberRepo>)>,
+    target: Cell<TeamMemberSource>,
+}
+
+impl LayeredTeamMemberRepo {
+    pub fn new(layers: Vec<(TeamMemberSource, Box<dyn TeamMemberRepo>)>) -> Self {
+        LayeredTeamMemberRepo {
+            layers,
+            target: Cell::new(TeamMemberSource::User),
+        }
+    }
+
+    fn target_layer(&self) -> Result<&dyn TeamMemberRepo> {
+        let target = self.target.get();
+
+        self.layers
+            .iter()
+            .find(|(source, _)| *source == target)
+            .map(|(_, layer)| layer.as_ref())
+            .ok_or_else(|| format!("No {target} roster found").into())
+    }
+
+    /// Checks if the layer of the source is closer than the target layer
+    fn is_closer(&self, source: TeamMemberSource) -> bool {
+        let position = |x| self.layers.iter().position(|(source, _)| *source == x);
+
+        position(source) < position(self.target.get())
+    }
+
+    fn get_by_key(&self, key: &str) -> Result<Option<TeamMember>> {
+        for (source, layer) in &self.layers {
+            if let Some(team_member) = layer.get(key)? {
+                return Ok(Some(team_member.with_source(*source)));
+            }
+            if layer.list_hidden()?.iter().any(|x| x == key) {
+                return Ok(None);
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+impl TeamMemberRepo for LayeredTeamMemberRepo {
+    fn list(&self) -> Result<Vec<TeamMember>> {
+        let mut team_members = Vec::new();
+        let mut resolved_keys = HashSet::new();
+
+        for (source, layer) in &self.layers {
+            let layer_team_members = layer.list()?;
+
+            for team_member in &layer_team_members {
+                if !resolved_keys.contains(&team_member.key) {
+                    team_members.push(team_member.clone().with_source(*source));
+                }
+            }
+
+            resolved_keys.extend(layer_team_members.into_iter().map(|x| x.key));
+            resolved_keys.extend(layer.list_hidden()?);
+        }
+
+        Ok(team_members)
+    }
+
+    /// Gets team member by key or else by alias
+    fn get(&self, key: &str) -> Result<Option<TeamMember>> {
+        if let Some(team_member) = self.get_by_key(key)? {
+            return Ok(Some(team_member));
+        }
+
+        // an alias resolves to a key, never to another alias
+        match self
+            .list_aliases()?
+            .into_iter()
+            .find(|(alias, _)| alias == key)
+        {
+            Some((_, key)) => self.get_by_key(&key),
+            None => Ok(None),
+        }
+    }
+
+    fn remove(&self, key: &str) -> Result<()> {
+        let target_layer = self.target_layer()?;
+
+        if target_layer.get(key)?.is_none()
+            && let Some(TeamMember {
+                source: Some(source),
+                ..
+            }) = self.get(key)?
+        {
+            let target = self.target.get();
+            return Err(if self.is_closer(source) {
+                format!("Team member with key {key} comes from the {source} roster, which overrides the {target} roster")
+            } else {
+                format!("Team member with key {key} comes from the {source} roster and can only be hidden")
+            }
+            .into());
+        }
+
+        target_layer.remove(key)
+    }
+
+    fn add(&self, team_member: &TeamMember) -> Result<()> {
+        self.target_layer()?.add(team_member)
+    }
+
+    /// Replaces the team member in the target layer. The old key of a team member from a
+    /// further layer is hidden instead of removed, and a team member from a closer layer is
+    /// refused as the target layer would not override it.
+    fn replace(&self, key: &str, team_member: &TeamMember) -> Result<()> {
+        let target = self.target.get();
+        let target_layer = self.target_layer()?;
+        let in_target_layer = target_layer.get(key)?.is_some();
+
+        if !in_target_layer
+            && let Some(TeamMember {
+                source: Some(source),
+                ..
+            }) = self.get_by_key(key)?
+            && self.is_closer(source)
+        {
+            return Err(format!(
+                "Team member with key {key} comes from the {source} roster, which overrides the {target} roster"
+            )
+            .into());
+        }
+
+        target_layer.add(team_member)?;
+        match (team_member.key == key, in_target_layer) {
+            (true, _) => Ok(()),
+            (false, true) => target_layer.remove(key),
+            (false, false) => target_layer.hide(key),
+        }
+    }
+
+    fn list_hidden(&self) -> Result<Vec<String>> {
+        self.target_layer()?.list_hidden()
+    }
+
+    fn hide(&self, key: &str) -> Result<()> {
+        let target = self.target.get();
+        let target_layer = self.target_layer()?;
+
+        if target_layer.get(key)?.is_some() {
+            return Err(format!(
+                "Team member with key {key} comes from the {target} roster and can only be deleted"
+            )
+            .into());
+        }
+        // a layer only hides team members of further layers
+        if let Some(TeamMember {
+            source: Some(source),
+            ..
+        }) = self.get_by_key(key)?
+            && self.is_closer(source)
+        {
+            return Err(format!(
+                "Team member with key {key} comes from the {source} roster, which overrides the {target} roster"
+            )
+            .into());
+        }
+
+        target_layer.hide(key)
+    }
+
+    fn list_aliases(&self) -> Result<Vec<(String, String)>> {
+        let mut aliases: Vec<(String, String)> = Vec::new();
+
+        for (_, layer) in &self.layers {
+            for (alias, key) in layer.list_aliases()? {
+                if !aliases.iter().any(|(x, _)| *x == alias) {
+                    aliases.push((alias, key));
+                }
+            }
+        }
+
+        Ok(aliases)
+    }
+
+    fn add_alias(&self, alias: &str, key: &str) -> Result<()> {
+        self.target_layer()?.add_alias(alias, key)
+    }
+
+    fn remove_alias(&self, alias: &str) -> Result<()> {
+        self.target_layer()?.remove_alias(alias)
+    }
+
+    fn write_to(&self, source: TeamMemberSource) -> Result<()> {
+        self.target.set(source);
+        self.target_layer().map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::MockTeamMemberRepo;
+    use mockall::predicate;
+
+    fn create_mock_layer(
+        team_members: Vec<TeamMember>,
+        hidden: Vec<&str>,
+        aliases: Vec<(&str, &str)>,
+    ) -> MockTeamMemberRepo {
+        let hidden: Vec<String> = hidden.into_iter().map(|x| x.to_owned()).collect();
+        let aliases: Vec<(String, String)> = aliases
+            .into_iter()
+            .map(|(alias, key)| (alias.to_owned(), key.to_owned()))
+            .collect();
+
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        let cloned_team_members = team_members.clone();
+        mock_team_member_repo
+            .expect_list()
+            .returning(move || Ok(cloned_team_members.clone()));
+        mock_team_member_repo
+            .expect_get()
+            .returning(move |key| Ok(team_members.iter().find(|x| x.key == key).cloned()));
+        mock_team_member_repo
+            .expect_list_hidden()
+            .returning(move || Ok(hidden.clone()));
+        mock_team_member_repo
+            .expect_list_aliases()
+            .returning(move || Ok(aliases.clone()));
+        mock_team_member_repo
+    }
+
+    fn create_layered_repo() -> Result<LayeredTeamMemberRepo> {
+        let repo_layer = create_mock_layer(
+            vec![TeamMember::new(
+                "lm",
+                "Lionel Messi",
+                "lionel.messi@example.com",
+            )?],
+            vec!["jf"],
+            vec![],
+        );
+        let user_layer = create_mock_layer(
+            vec![TeamMember::new(
+                "em",
+                "Emi Martinez",
+                "emi.martinez@example.com",
+            )?],
+            vec![],
+            vec![("dibu", "em")],
+        );
+        let system_layer = create_mock_layer(
+            vec![
+                TeamMember::new("lm", "Leo Messi", "leo.messi@example.com")?,
+                TeamMember::new("jf", "Julian Fernandez", "julian.fernandez@example.com")?,
+                TeamMember::new("ad", "Angel Di Maria", "angel.dimaria@example.com")?,
+            ],
+            vec![],
+            vec![("fideo", "ad"), ("dibu", "lm")],
+        );
+
+        Ok(LayeredTeamMemberRepo::new(vec![
+            (TeamMemberSource::Repo, Box::new(repo_layer)),
+            (TeamMemberSource::User, Box::new(user_layer)),
+            (TeamMemberSource::System, Box::new(system_layer)),
+        ]))
+    }
+
+    #[test]
+    fn test_list() -> Result<()> {
+        let layered_repo = create_layered_repo()?;
+
+        let result = layered_repo.list()?;
+
+        assert_eq!(
+            result,
+            vec![
+                TeamMember::new("lm", "Lionel Messi", "lionel.messi@example.com")?
+                    .with_source(TeamMemberSource::Repo),
+                TeamMember::new("em", "Emi Martinez", "emi.martinez@example.com")?
+                    .with_source(TeamMemberSource::User),
+                TeamMember::new("ad", "Angel Di Maria", "angel.dimaria@example.com")?
+                    .with_source(TeamMemberSource::System),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get() -> Result<()> {
+        let layered_repo = create_layered_repo()?;
+
+        assert_eq!(
+            layered_repo.get("lm")?,
+            Some(
+                TeamMember::new("lm", "Lionel Messi", "lionel.messi@example.com")?
+                    .with_source(TeamMemberSource::Repo)
+            )
+        );
+        assert_eq!(
+            layered_repo.get("ad")?,
+            Some(
+                TeamMember::new("ad", "Angel Di Maria", "angel.dimaria@example.com")?
+                    .with_source(TeamMemberSource::System)
+            )
+        );
+        assert_eq!(layered_repo.get("jf")?, None);
+        assert_eq!(layered_repo.get("rdp")?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_by_alias() -> Result<()> {
+        let layered_repo = create_layered_repo()?;
+
+        assert_eq!(
+            layered_repo.get("dibu")?,
+            Some(
+                TeamMember::new("em", "Emi Martinez", "emi.martinez@example.com")?
+                    .with_source(TeamMemberSource::User)
+            )
+        );
+        assert_eq!(
+            layered_repo.get("fideo")?,
+            Some(
+                TeamMember::new("ad", "Angel Di Maria", "angel.dimaria@example.com")?
+                    .with_source(TeamMemberSource::System)
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_aliases() -> Result<()> {
+        let layered_repo = create_layered_repo()?;
+
+        assert_eq!(
+            layered_repo.list_aliases()?,
+            vec![
+                ("dibu".to_owned(), "em".to_owned()),
+                ("fideo".to_owned(), "ad".to_owned()),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_add() -> Result<()> {
+        let team_member = TeamMember::new("rdp", "Rodrigo de Paul", "rodrigo.depaul@example.com")?;
+
+        let mut closest_layer = MockTeamMemberRepo::new();
+        closest_layer
+            .expect_add()
+            .with(predicate::eq(team_member.clone()))
+            .once()
+            .returning(|_| Ok(()));
+        let mut further_layer = MockTeamMemberRepo::new();
+        further_layer.expect_add().never();
+
+        let layered_repo = LayeredTeamMemberRepo::new(vec![
+            (TeamMemberSource::User, Box::new(closest_layer)),
+            (TeamMemberSource::System, Box::new(further_layer)),
+        ]);
+
+        layered_repo.add(&team_member)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove() -> Result<()> {
+        let mut closest_layer = create_mock_layer(
+            vec![TeamMember::new(
+                "em",
+                "Emi Martinez",
+                "emi.martinez@example.com",
+            )?],
+            vec![],
+            vec![],
+        );
+        closest_layer
+            .expect_remove()
+            .with(predicate::eq("em"))
+            .once()
+            .returning(|_| Ok(()));
+
+        let layered_repo =
+            LayeredTeamMemberRepo::new(vec![(TeamMemberSource::User, Box::new(closest_layer))]);
+
+        layered_repo.remove("em")?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove_when_team_member_from_further_layer() -> Result<()> {
+        let layered_repo = create_layered_repo()?;
+
+        let result = layered_repo.remove("ad");
+
+        assert!(result.is_err_and(|x| x.to_string()
+            == "Team member with key ad comes from the system roster and can only be hidden"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_hide() -> Result<()> {
+        let mut closest_layer = create_mock_layer(vec![], vec![], vec![]);
+        closest_layer
+            .expect_hide()
+            .with(predicate::eq("ad"))
+            .once()
+            .returning(|_| Ok(()));
+
+        let layered_repo =
+            LayeredTeamMemberRepo::new(vec![(TeamMemberSource::User, Box::new(closest_layer))]);
+
+        layered_repo.hide("ad")?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_hide_when_team_member_from_target_layer() -> Result<()> {
+        let layered_repo = create_layered_repo()?;
+
+        let result = layered_repo.hide("em");
+
+        assert!(result.is_err_and(|x| x.to_string()
+            == "Team member with key em comes from the user roster and can only be deleted"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_hide_when_team_member_from_closer_layer() -> Result<()> {
+        let layered_repo = create_layered_repo()?;
+
+        let result = layered_repo.hide("lm");
+
+        assert!(result.is_err_and(|x| x.to_string()
+            == "Team member with key lm comes from the repo roster, which overrides the user roster"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove_given_closer_layer() -> Result<()> {
+        let mut repo_layer = create_mock_layer(vec![], vec![], vec![]);
+        repo_layer.expect_remove().never();
+        let mut user_layer = create_mock_layer(
+            vec![TeamMember::new(
+                "em",
+                "Emi Martinez",
+                "emi.martinez@example.com",
+            )?],
+            vec![],
+            vec![],
+        );
+        user_layer
+            .expect_remove()
+            .with(predicate::eq("em"))
+            .once()
+            .returning(|_| Ok(()));
+
+        let layered_repo = LayeredTeamMemberRepo::new(vec![
+            (TeamMemberSource::Repo, Box::new(repo_layer)),
+            (TeamMemberSource::User, Box::new(user_layer)),
+        ]);
+
+        layered_repo.remove("em")?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_replace_given_new_key() -> Result<()> {
+        let team_member = TeamMember::new("emi", "Emi Martinez", "emi.martinez@example.com")?;
+
+        let mut user_layer = create_mock_layer(
+            vec![TeamMember::new(
+                "em",
+                "Emi Martinez",
+                "emi.martinez@example.com",
+            )?],
+            vec![],
+            vec![],
+        );
+        user_layer
+            .expect_add()
+            .with(predicate::eq(team_member.clone()))
+            .once()
+            .returning(|_| Ok(()));
+        user_layer
+            .expect_remove()
+            .with(predicate::eq("em"))
+            .once()
+            .returning(|_| Ok(()));
+        user_layer.expect_hide().never();
+
+        let layered_repo =
+            LayeredTeamMemberRepo::new(vec![(TeamMemberSource::User, Box::new(user_layer))]);
+
+        layered_repo.replace("em", &team_member)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_replace_given_new_key_when_team_member_from_further_layer() -> Result<()> {
+        let team_member = TeamMember::new("angel", "Angel Di Maria", "angel.dimaria@example.com")?;
+
+        let mut user_layer = create_mock_layer(vec![], vec![], vec![]);
+        user_layer
+            .expect_add()
+            .with(predicate::eq(team_member.clone()))
+            .once()
+            .returning(|_| Ok(()));
+        user_layer.expect_remove().never();
+        user_layer
+            .expect_hide()
+            .with(predicate::eq("ad"))
+            .once()
+            .returning(|_| Ok(()));
+        let system_layer = create_mock_layer(
+            vec![TeamMember::new(
+                "ad",
+                "Angel Di Maria",
+                "angel.dimaria@example.com",
+            )?],
+            vec![],
+            vec![],
+        );
+
+        let layered_repo = LayeredTeamMemberRepo::new(vec![
+            (TeamMemberSource::User, Box::new(user_layer)),
+            (TeamMemberSource::System, Box::new(system_layer)),
+        ]);
+
+        layered_repo.replace("ad", &team_member)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_replace_when_team_member_from_closer_layer() -> Result<()> {
+        let team_member = TeamMember::new("lm", "Leo Messi", "leo@example.com")?;
+
+        let result = create_layered_repo()?.replace("lm", &team_member);
+
+        assert!(result.is_err_and(|x| x.to_string()
+            == "Team member with key lm comes from the repo roster, which overrides the user roster"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_to() -> Result<()> {
+        let team_member = TeamMember::new("rdp", "Rodrigo de Paul", "rodrigo.depaul@example.com")?;
+
+        let mut repo_layer = MockTeamMemberRepo::new();
+        repo_layer
+            .expect_add()
+            .with(predicate::eq(team_member.clone()))
+            .once()
+            .returning(|_| Ok(()));
+        let mut user_layer = MockTeamMemberRepo::new();
+        user_layer.expect_add().never();
+
+        let layered_repo = LayeredTeamMemberRepo::new(vec![
+            (TeamMemberSource::Repo, Box::new(repo_layer)),
+            (TeamMemberSource::User, Box::new(user_layer)),
+        ]);
+
+        layered_repo.write_to(TeamMemberSource::Repo)?;
+        layered_repo.add(&team_member)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_to_when_layer_not_found() -> Result<()> {
+        let layered_repo = LayeredTeamMemberRepo::new(vec![(
+            TeamMemberSource::User,
+            Box::new(MockTeamMemberRepo::new()),
+        )]);
+
+        let result = layered_repo.write_to(TeamMemberSource::System);
+
+        assert!(result.is_err_and(|x| x.to_string() == "No system roster found"));
+
+        Ok(())
+    }
+}
@@ -1,13 +1,182 @@
 use crate::Result;
-use crate::helpers::{CmdOutput, CommandRunner};
+use crate::helpers::{CmdOutput, CommandRunner, parse_duration};
+use crate::repositories::TeamMember;
+use chrono::{DateTime, Days, Local, NaiveTime, TimeDelta};
+use serde::{Deserialize, Serialize};
+use std::sync::Once;
+use std::{fmt, str::FromStr};
+
+/// Scope of the mob/pair programming session
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MobSessionScope {
+    /// Session stored in the current repository's git config
+    Local,
+    /// Session stored in the user's global git config
+    Global,
+}
+
+impl MobSessionScope {
+    fn git_config_option(&self) -> &'static str {
+        match self {
+            MobSessionScope::Local => "--local",
+            MobSessionScope::Global => "--global",
+        }
+    }
+}
+
+impl fmt::Display for MobSessionScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MobSessionScope::Local => write!(f, "local"),
+            MobSessionScope::Global => write!(f, "global"),
+        }
+    }
+}
+
+/// How long a mob/pair programming session lasts after it starts
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SessionExpiry {
+    /// Session expires after the given duration
+    After(TimeDelta),
+    /// Session expires at the end of the day it started
+    EndOfDay,
+    /// Session never expires
+    Never,
+}
+
+impl SessionExpiry {
+    /// Longest duration after which a session can expire
+    const MAX_DURATION: TimeDelta = TimeDelta::days(36500);
+
+    fn expires_at(&self, started_at: DateTime<Local>) -> Option<DateTime<Local>> {
+        match self {
+            SessionExpiry::After(duration) => started_at.checked_add_signed(*duration),
+            SessionExpiry::EndOfDay => started_at
+                .date_naive()
+                .checked_add_days(Days::new(1))?
+                .and_time(NaiveTime::MIN)
+                .and_local_timezone(Local)
+                .earliest(),
+            SessionExpiry::Never => None,
+        }
+    }
+}
+
+impl FromStr for SessionExpiry {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let value = s.trim().to_lowercase();
+        let error = || {
+            format!(
+                "Invalid session expiry: {s}. Expected a duration (e.g. 30m, 8h, 1d), eod (end of day) or never"
+            )
+        };
+
+        match value.as_str() {
+            "eod" | "end-of-day" | "end of day" => return Ok(SessionExpiry::EndOfDay),
+            "never" => return Ok(SessionExpiry::Never),
+            _ => {}
+        }
+
+        let duration = parse_duration(&value).ok_or_else(error)?;
+        if duration > Self::MAX_DURATION {
+            return Err(format!(
+                "Invalid session expiry: {s}. Expected a duration of at most {}",
+                SessionExpiry::After(Self::MAX_DURATION)
+            ));
+        }
+
+        Ok(SessionExpiry::After(duration))
+    }
+}
+
+impl fmt::Display for SessionExpiry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SessionExpiry::After(duration) => {
+                let seconds = duration.num_seconds();
+                match seconds {
+                    _ if seconds % 86400 == 0 => write!(f, "{}d", seconds / 86400),
+                    _ if seconds % 3600 == 0 => write!(f, "{}h", seconds / 3600),
+                    _ if seconds % 60 == 0 => write!(f, "{}m", seconds / 60),
+                    _ => write!(f, "{seconds}s"),
+                }
+            }
+            SessionExpiry::EndOfDay => write!(f, "eod"),
+            SessionExpiry::Never => write!(f, "never"),
+        }
+    }
+}
+
+/// Mob/pair programming session which was removed because it had expired
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExpiredSession {
+    pub coauthors: Vec<String>,
+    pub driver: Option<String>,
+    pub started_at: Option<DateTime<Local>>,
+    pub expired_at: DateTime<Local>,
+}
 
 #[cfg(test)]
 use mockall::{automock, predicate::*};
 #[cfg_attr(test, automock)]
 pub trait MobSessionRepo {
-    fn list_coauthors(&self) -> Result<Vec<String>>;
-    fn add_coauthor(&self, coauthor: &str) -> Result<()>;
-    fn clear(&self) -> Result<()>;
+    /// Lists co-authors of the session, which is empty if the session has expired
+    fn list_coauthors(&self, scope: MobSessionScope) -> Result<Vec<String>>;
+    /// Lists co-authors of the active session i.e. the local session if it has co-authors or a driver,
+    /// otherwise the global one
+    fn list_active_coauthors(&self) -> Result<(MobSessionScope, Vec<String>)>;
+    /// Adds co-author to the session, starting a new session if there is none or it has expired
+    fn add_coauthor(&self, scope: MobSessionScope, coauthor: &str) -> Result<()>;
+    /// Removes co-author from the session
+    fn remove_coauthor(&self, scope: MobSessionScope, coauthor: &str) -> Result<()>;
+    /// Gets the driver (i.e. the commit author) of the session, which is none if the session has expired
+    fn get_driver(&self, scope: MobSessionScope) -> Result<Option<String>>;
+    /// Sets the driver of the session and makes them the commit author in the same scope,
+    /// starting a new session if there is none or it has expired. The commit author identity
+    /// configured in the scope before is saved in the session and restored when it is removed.
+    fn set_driver(&self, scope: MobSessionScope, driver: &TeamMember) -> Result<()>;
+    /// Lists keys of the team members in the rotation order of the session, which is empty if the session has expired
+    fn list_rotation(&self, scope: MobSessionScope) -> Result<Vec<String>>;
+    /// Sets rotation order of the session, starting a new session if there is none or it has expired
+    fn set_rotation(&self, scope: MobSessionScope, keys: &[String]) -> Result<()>;
+    /// Checks if the driver and co-author of the session swap after every commit
+    fn is_pingpong(&self, scope: MobSessionScope) -> Result<bool>;
+    fn set_pingpong(&self, scope: MobSessionScope, enabled: bool) -> Result<()>;
+    fn clear(&self, scope: MobSessionScope) -> Result<()>;
+    /// Gets the time at which the session started, which is none if the session has expired
+    fn started_at(&self, scope: MobSessionScope) -> Result<Option<DateTime<Local>>>;
+    /// Gets the time at which the session expired if it has expired
+    fn expired_at(&self, scope: MobSessionScope) -> Result<Option<DateTime<Local>>>;
+    /// Removes the session if it has expired, restoring the commit author identity which its driver replaced
+    fn remove_expired(&self, scope: MobSessionScope) -> Result<Option<ExpiredSession>>;
+    fn set_expire_after(&self, scope: MobSessionScope, expiry: SessionExpiry) -> Result<()>;
+    /// Gets the email of the effective commit author i.e. GIT_AUTHOR_EMAIL environment variable
+    /// (set by `git commit --author`), author.email or user.email git config,
+    /// which is none if there is no author identity
+    fn get_author_email(&self) -> Result<Option<String>>;
+}
+
+struct MobSession {
+    coauthors: Vec<String>,
+    driver: Option<String>,
+    /// Commit author identity configured in the scope before the driver replaced it
+    saved_author_name: Option<String>,
+    saved_author_email: Option<String>,
+    rotation: Vec<String>,
+    pingpong: bool,
+    started_at: Option<DateTime<Local>>,
+    expiry: SessionExpiry,
+}
+
+impl MobSession {
+    fn expired_at(&self, now: DateTime<Local>) -> Option<DateTime<Local>> {
+        self.started_at
+            .and_then(|started_at| self.expiry.expires_at(started_at))
+            .filter(|expires_at| *expires_at <= now)
+    }
 }
 
 pub struct GitConfigMobRepo<Cmd> {
@@ -17,62 +186,500 @@ pub struct GitConfigMobRepo<Cmd> {
 impl<Cmd: CommandRunner> GitConfigMobRepo<Cmd> {
     const COAUTHORS_MOB_SECTION: &'static str = "coauthors-mob";
     const COAUTHOR_MOB_KEY: &'static str = "entry";
+    const STARTED_KEY: &'static str = "started";
+    const DRIVER_KEY: &'static str = "driver";
+    const ROTATION_KEY: &'static str = "rotation";
+    const PINGPONG_KEY: &'static str = "pingpong";
+    // Commit author identity which git uses instead of user.name and user.email
+    const AUTHOR_NAME_KEY: &'static str = "author.name";
+    const AUTHOR_EMAIL_KEY: &'static str = "author.email";
+    const SAVED_AUTHOR_NAME_KEY: &'static str = "savedAuthorName";
+    const SAVED_AUTHOR_EMAIL_KEY: &'static str = "savedAuthorEmail";
+    // Settings are kept in a separate section so that they outlive the session
+    const COAUTHORS_MOB_SETTINGS_SECTION: &'static str = "coauthors-mob-settings";
+    const EXPIRE_AFTER_KEY: &'static str = "expireAfter";
 
     const EXIT_CODE_SUCCESS: i32 = 0;
     const EXIT_CODE_CONFIG_INVALID_KEY: i32 = 1;
+    const EXIT_CODE_CONFIG_KEY_NOT_SET: i32 = 5;
+    const EXIT_CODE_NOT_IN_GIT_REPO: i32 = 128;
 
-    fn git_config_error<T>(output: &CmdOutput) -> Result<T> {
+    fn git_config_error<T>(scope: MobSessionScope, output: &CmdOutput) -> Result<T> {
         match output.status_code {
+            Some(Self::EXIT_CODE_NOT_IN_GIT_REPO) if scope == MobSessionScope::Local => {
+                Err("Local mob session can only be used inside a git repository".into())
+            }
             Some(code) => Err(format!("Git config command exited with status code: {code}").into()),
             None => Err("Git config command terminated by signal".into()),
         }
     }
-}
 
-impl<Cmd: CommandRunner> MobSessionRepo for GitConfigMobRepo<Cmd> {
-    fn list_coauthors(&self) -> Result<Vec<String>> {
-        let full_key = format!("{}.{}", Self::COAUTHORS_MOB_SECTION, Self::COAUTHOR_MOB_KEY);
+    fn get_session(&self, scope: MobSessionScope) -> Result<MobSession> {
+        let search_regex = format!(
+            "^({}|{})\\.",
+            Self::COAUTHORS_MOB_SECTION,
+            Self::COAUTHORS_MOB_SETTINGS_SECTION
+        );
+
+        let output = self.command_runner.execute(
+            "git",
+            &[
+                "config",
+                scope.git_config_option(),
+                "--get-regexp",
+                &search_regex,
+            ],
+        )?;
+
+        let mut expire_after = None;
+        let mut session = MobSession {
+            coauthors: vec![],
+            driver: None,
+            saved_author_name: None,
+            saved_author_email: None,
+            rotation: vec![],
+            pingpong: false,
+            started_at: None,
+            expiry: SessionExpiry::Never,
+        };
+
+        match output.status_code {
+            Some(Self::EXIT_CODE_SUCCESS) => {}
+            Some(Self::EXIT_CODE_CONFIG_INVALID_KEY) => return Ok(session),
+            // There is no local session outside a git repository
+            Some(Self::EXIT_CODE_NOT_IN_GIT_REPO) if scope == MobSessionScope::Local => {
+                return Ok(session);
+            }
+            _ => return Self::git_config_error(scope, &output),
+        }
+
+        let coauthor_key = format!("{}.{}", Self::COAUTHORS_MOB_SECTION, Self::COAUTHOR_MOB_KEY);
+        let started_key = format!("{}.{}", Self::COAUTHORS_MOB_SECTION, Self::STARTED_KEY);
+        let driver_key = format!("{}.{}", Self::COAUTHORS_MOB_SECTION, Self::DRIVER_KEY);
+        let rotation_key = format!("{}.{}", Self::COAUTHORS_MOB_SECTION, Self::ROTATION_KEY);
+        let pingpong_key = format!("{}.{}", Self::COAUTHORS_MOB_SECTION, Self::PINGPONG_KEY);
+        let saved_author_name_key = format!(
+            "{}.{}",
+            Self::COAUTHORS_MOB_SECTION,
+            Self::SAVED_AUTHOR_NAME_KEY
+        )
+        .to_lowercase();
+        let saved_author_email_key = format!(
+            "{}.{}",
+            Self::COAUTHORS_MOB_SECTION,
+            Self::SAVED_AUTHOR_EMAIL_KEY
+        )
+        .to_lowercase();
+        let expire_after_key = format!(
+            "{}.{}",
+            Self::COAUTHORS_MOB_SETTINGS_SECTION,
+            Self::EXPIRE_AFTER_KEY
+        )
+        .to_lowercase();
+
+        for line in String::from_utf8(output.stdout)?.lines() {
+            let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+            if key == coauthor_key {
+                session.coauthors.push(value.to_owned());
+            } else if key == driver_key {
+                session.driver = Some(value.to_owned());
+            } else if key == rotation_key {
+                session.rotation.push(value.to_owned());
+            } else if key == pingpong_key {
+                session.pingpong = value == "true";
+            } else if key == saved_author_name_key {
+                session.saved_author_name = Some(value.to_owned());
+            } else if key == saved_author_email_key {
+                session.saved_author_email = Some(value.to_owned());
+            } else if key == started_key {
+                session.started_at = value
+                    .parse()
+                    .ok()
+                    .and_then(|x| DateTime::from_timestamp(x, 0))
+                    .map(|x| x.with_timezone(&Local));
+            } else if key == expire_after_key {
+                expire_after = Some(value.to_owned());
+            }
+        }
+
+        // A local session expires after the global setting unless the repository overrides it
+        if expire_after.is_none() && scope == MobSessionScope::Local && session.started_at.is_some()
+        {
+            expire_after = self.get_global_expire_after()?;
+        }
+        if let Some(value) = expire_after {
+            session.expiry = Self::parse_expiry(&value);
+        }
+
+        Ok(session)
+    }
+
+    fn get_global_expire_after(&self) -> Result<Option<String>> {
+        let full_key = format!(
+            "{}.{}",
+            Self::COAUTHORS_MOB_SETTINGS_SECTION,
+            Self::EXPIRE_AFTER_KEY
+        );
 
         let output = self
             .command_runner
-            .execute("git", &["config", "--global", "--get-all", &full_key])?;
+            .execute("git", &["config", "--global", "--get", &full_key])?;
 
         match output.status_code {
-            Some(Self::EXIT_CODE_SUCCESS) => Ok(String::from_utf8(output.stdout)?
-                .lines()
-                .map(|x| x.into())
-                .collect()),
+            Some(Self::EXIT_CODE_SUCCESS) => {
+                Ok(Some(String::from_utf8(output.stdout)?.trim().to_owned()))
+            }
+            Some(Self::EXIT_CODE_CONFIG_INVALID_KEY) => Ok(None),
+            _ => Self::git_config_error(MobSessionScope::Global, &output),
+        }
+    }
+
+    /// Parses the expiry setting, which never expires the session if it is malformed (e.g. edited
+    /// by hand) so that committing keeps working
+    fn parse_expiry(value: &str) -> SessionExpiry {
+        value.parse().unwrap_or_else(|error| {
+            static WARNING: Once = Once::new();
+            WARNING.call_once(|| {
+                eprintln!(
+                    "Warning: {error} in {}.{} git config. The mob session never expires",
+                    Self::COAUTHORS_MOB_SETTINGS_SECTION,
+                    Self::EXPIRE_AFTER_KEY
+                )
+            });
+            SessionExpiry::Never
+        })
+    }
+
+    fn remove_session(&self, scope: MobSessionScope, session: &MobSession) -> Result<()> {
+        // The commit author identity only belongs to the session if it has a driver
+        if session.driver.is_some() {
+            for (key, saved_value) in [
+                (Self::AUTHOR_NAME_KEY, &session.saved_author_name),
+                (Self::AUTHOR_EMAIL_KEY, &session.saved_author_email),
+            ] {
+                match saved_value {
+                    Some(value) => self.set_value(scope, key, value)?,
+                    None => self.unset_value(scope, key)?,
+                }
+            }
+        }
 
-            Some(Self::EXIT_CODE_CONFIG_INVALID_KEY) => Ok(vec![]),
-            _ => Self::git_config_error(&output),
+        let section = Self::COAUTHORS_MOB_SECTION.to_owned();
+
+        let output = self.command_runner.execute(
+            "git",
+            &[
+                "config",
+                scope.git_config_option(),
+                "--remove-section",
+                &section,
+            ],
+        )?;
+
+        match output.status_code {
+            Some(Self::EXIT_CODE_SUCCESS) => Ok(()),
+            _ => Self::git_config_error(scope, &output),
+        }
+    }
+
+    /// Starts a new session if there is none or it has expired, returning whether the session has a driver
+    fn start_session(&self, scope: MobSessionScope, now: DateTime<Local>) -> Result<bool> {
+        let session = self.get_session(scope)?;
+
+        if session.expired_at(now).is_some() {
+            self.remove_session(scope, &session)?;
+            self.set_started_at(scope, now)?;
+            return Ok(false);
+        } else if session.started_at.is_none() {
+            self.set_started_at(scope, now)?;
+        }
+
+        Ok(session.driver.is_some())
+    }
+
+    fn get_value(&self, scope: MobSessionScope, key: &str) -> Result<Option<String>> {
+        let output = self
+            .command_runner
+            .execute("git", &["config", scope.git_config_option(), "--get", key])?;
+
+        match output.status_code {
+            Some(Self::EXIT_CODE_SUCCESS) => Ok(Some(
+                String::from_utf8(output.stdout)?.trim_end().to_owned(),
+            )),
+            Some(Self::EXIT_CODE_CONFIG_INVALID_KEY) => Ok(None),
+            _ => Self::git_config_error(scope, &output),
         }
     }
-    fn add_coauthor(&self, coauthor: &str) -> Result<()> {
-        let full_key = format!("{}.{}", Self::COAUTHORS_MOB_SECTION, Self::COAUTHOR_MOB_KEY);
 
+    fn set_value(&self, scope: MobSessionScope, key: &str, value: &str) -> Result<()> {
         let output = self
             .command_runner
-            .execute("git", &["config", "--global", "--add", &full_key, coauthor])?;
+            .execute("git", &["config", scope.git_config_option(), key, value])?;
+
+        match output.status_code {
+            Some(Self::EXIT_CODE_SUCCESS) => Ok(()),
+            _ => Self::git_config_error(scope, &output),
+        }
+    }
+
+    fn unset_value(&self, scope: MobSessionScope, key: &str) -> Result<()> {
+        let output = self.command_runner.execute(
+            "git",
+            &["config", scope.git_config_option(), "--unset", key],
+        )?;
+
+        match output.status_code {
+            Some(Self::EXIT_CODE_SUCCESS | Self::EXIT_CODE_CONFIG_KEY_NOT_SET) => Ok(()),
+            _ => Self::git_config_error(scope, &output),
+        }
+    }
+
+    fn set_started_at(&self, scope: MobSessionScope, started_at: DateTime<Local>) -> Result<()> {
+        let full_key = format!("{}.{}", Self::COAUTHORS_MOB_SECTION, Self::STARTED_KEY);
+
+        let output = self.command_runner.execute(
+            "git",
+            &[
+                "config",
+                scope.git_config_option(),
+                &full_key,
+                &started_at.timestamp().to_string(),
+            ],
+        )?;
+
+        match output.status_code {
+            Some(Self::EXIT_CODE_SUCCESS) => Ok(()),
+            _ => Self::git_config_error(scope, &output),
+        }
+    }
+}
+
+impl<Cmd: CommandRunner> MobSessionRepo for GitConfigMobRepo<Cmd> {
+    fn list_coauthors(&self, scope: MobSessionScope) -> Result<Vec<String>> {
+        let session = self.get_session(scope)?;
+
+        if session.expired_at(Local::now()).is_some() {
+            return Ok(vec![]);
+        }
+
+        Ok(session.coauthors)
+    }
+    fn list_active_coauthors(&self) -> Result<(MobSessionScope, Vec<String>)> {
+        let local_session = self.get_session(MobSessionScope::Local)?;
+        if local_session.expired_at(Local::now()).is_none()
+            && (!local_session.coauthors.is_empty() || local_session.driver.is_some())
+        {
+            return Ok((MobSessionScope::Local, local_session.coauthors));
+        }
+
+        let global_coauthors = self.list_coauthors(MobSessionScope::Global)?;
+        Ok((MobSessionScope::Global, global_coauthors))
+    }
+    fn add_coauthor(&self, scope: MobSessionScope, coauthor: &str) -> Result<()> {
+        self.start_session(scope, Local::now())?;
+
+        let full_key = format!("{}.{}", Self::COAUTHORS_MOB_SECTION, Self::COAUTHOR_MOB_KEY);
+
+        let output = self.command_runner.execute(
+            "git",
+            &[
+                "config",
+                scope.git_config_option(),
+                "--add",
+                &full_key,
+                coauthor,
+            ],
+        )?;
 
         match output.status_code {
             Some(Self::EXIT_CODE_SUCCESS) => Ok(()),
-            _ => Self::git_config_error(&output),
+            _ => Self::git_config_error(scope, &output),
+        }
+    }
+    fn remove_coauthor(&self, scope: MobSessionScope, coauthor: &str) -> Result<()> {
+        let full_key = format!("{}.{}", Self::COAUTHORS_MOB_SECTION, Self::COAUTHOR_MOB_KEY);
+
+        let output = self.command_runner.execute(
+            "git",
+            &[
+                "config",
+                scope.git_config_option(),
+                "--fixed-value",
+                "--unset-all",
+                &full_key,
+                coauthor,
+            ],
+        )?;
+
+        match output.status_code {
+            Some(Self::EXIT_CODE_SUCCESS | Self::EXIT_CODE_CONFIG_KEY_NOT_SET) => Ok(()),
+            _ => Self::git_config_error(scope, &output),
+        }
+    }
+    fn get_driver(&self, scope: MobSessionScope) -> Result<Option<String>> {
+        let session = self.get_session(scope)?;
+
+        if session.expired_at(Local::now()).is_some() {
+            return Ok(None);
+        }
+
+        Ok(session.driver)
+    }
+    fn set_driver(&self, scope: MobSessionScope, driver: &TeamMember) -> Result<()> {
+        let has_driver = self.start_session(scope, Local::now())?;
+
+        // The user's own identity is saved before the first driver replaces it
+        if !has_driver {
+            for (key, saved_key) in [
+                (Self::AUTHOR_NAME_KEY, Self::SAVED_AUTHOR_NAME_KEY),
+                (Self::AUTHOR_EMAIL_KEY, Self::SAVED_AUTHOR_EMAIL_KEY),
+            ] {
+                if let Some(value) = self.get_value(scope, key)? {
+                    let full_key = format!("{}.{saved_key}", Self::COAUTHORS_MOB_SECTION);
+                    self.set_value(scope, &full_key, &value)?;
+                }
+            }
+        }
+
+        let full_key = format!("{}.{}", Self::COAUTHORS_MOB_SECTION, Self::DRIVER_KEY);
+        self.set_value(scope, &full_key, &driver.to_string())?;
+        self.set_value(scope, Self::AUTHOR_NAME_KEY, &driver.name)?;
+        self.set_value(scope, Self::AUTHOR_EMAIL_KEY, &driver.email)
+    }
+    fn list_rotation(&self, scope: MobSessionScope) -> Result<Vec<String>> {
+        let session = self.get_session(scope)?;
+
+        if session.expired_at(Local::now()).is_some() {
+            return Ok(vec![]);
+        }
+
+        Ok(session.rotation)
+    }
+    fn set_rotation(&self, scope: MobSessionScope, keys: &[String]) -> Result<()> {
+        self.start_session(scope, Local::now())?;
+
+        let full_key = format!("{}.{}", Self::COAUTHORS_MOB_SECTION, Self::ROTATION_KEY);
+
+        let output = self.command_runner.execute(
+            "git",
+            &[
+                "config",
+                scope.git_config_option(),
+                "--unset-all",
+                &full_key,
+            ],
+        )?;
+        match output.status_code {
+            Some(Self::EXIT_CODE_SUCCESS | Self::EXIT_CODE_CONFIG_KEY_NOT_SET) => {}
+            _ => return Self::git_config_error(scope, &output),
+        }
+
+        for key in keys {
+            let output = self.command_runner.execute(
+                "git",
+                &["config", scope.git_config_option(), "--add", &full_key, key],
+            )?;
+            if output.status_code != Some(Self::EXIT_CODE_SUCCESS) {
+                return Self::git_config_error(scope, &output);
+            }
+        }
+
+        Ok(())
+    }
+    fn is_pingpong(&self, scope: MobSessionScope) -> Result<bool> {
+        let session = self.get_session(scope)?;
+
+        Ok(session.pingpong && session.expired_at(Local::now()).is_none())
+    }
+    fn set_pingpong(&self, scope: MobSessionScope, enabled: bool) -> Result<()> {
+        let full_key = format!("{}.{}", Self::COAUTHORS_MOB_SECTION, Self::PINGPONG_KEY);
+
+        if enabled {
+            self.start_session(scope, Local::now())?;
+            self.set_value(scope, &full_key, "true")
+        } else {
+            self.unset_value(scope, &full_key)
         }
     }
-    fn clear(&self) -> Result<()> {
-        if self.list_coauthors()?.is_empty() {
+    fn clear(&self, scope: MobSessionScope) -> Result<()> {
+        let session = self.get_session(scope)?;
+        if session.coauthors.is_empty() && session.started_at.is_none() {
             return Ok(());
         }
 
-        let section = Self::COAUTHORS_MOB_SECTION.to_owned();
+        self.remove_session(scope, &session)
+    }
+    fn started_at(&self, scope: MobSessionScope) -> Result<Option<DateTime<Local>>> {
+        let session = self.get_session(scope)?;
+        if session.expired_at(Local::now()).is_some() {
+            return Ok(None);
+        }
+
+        Ok(session.started_at)
+    }
+    fn expired_at(&self, scope: MobSessionScope) -> Result<Option<DateTime<Local>>> {
+        Ok(self.get_session(scope)?.expired_at(Local::now()))
+    }
+    fn remove_expired(&self, scope: MobSessionScope) -> Result<Option<ExpiredSession>> {
+        let session = self.get_session(scope)?;
+        let Some(expired_at) = session.expired_at(Local::now()) else {
+            return Ok(None);
+        };
+
+        self.remove_session(scope, &session)?;
+
+        Ok(Some(ExpiredSession {
+            coauthors: session.coauthors,
+            driver: session.driver,
+            started_at: session.started_at,
+            expired_at,
+        }))
+    }
+    fn set_expire_after(&self, scope: MobSessionScope, expiry: SessionExpiry) -> Result<()> {
+        let full_key = format!(
+            "{}.{}",
+            Self::COAUTHORS_MOB_SETTINGS_SECTION,
+            Self::EXPIRE_AFTER_KEY
+        );
 
+        // A local session which never expires overrides the global setting
+        let output = match expiry {
+            SessionExpiry::Never if scope == MobSessionScope::Global => {
+                self.command_runner.execute(
+                    "git",
+                    &["config", scope.git_config_option(), "--unset", &full_key],
+                )?
+            }
+            _ => self.command_runner.execute(
+                "git",
+                &[
+                    "config",
+                    scope.git_config_option(),
+                    &full_key,
+                    &expiry.to_string(),
+                ],
+            )?,
+        };
+
+        match output.status_code {
+            Some(Self::EXIT_CODE_SUCCESS) => Ok(()),
+            Some(Self::EXIT_CODE_CONFIG_KEY_NOT_SET) if expiry == SessionExpiry::Never => Ok(()),
+            _ => Self::git_config_error(scope, &output),
+        }
+    }
+    fn get_author_email(&self) -> Result<Option<String>> {
         let output = self
             .command_runner
-            .execute("git", &["config", "--global", "--remove-section", &section])?;
+            .execute("git", &["var", "GIT_AUTHOR_IDENT"])?;
 
         match output.status_code {
-            Some(Self::EXIT_CODE_SUCCESS) => Ok(()),
-            _ => Self::git_config_error(&output),
+            // e.g. Leo Messi <leo.messi@example.com> 1760000000 +0100
+            Some(Self::EXIT_CODE_SUCCESS) => Ok(String::from_utf8(output.stdout)?
+                .split_once('<')
+                .and_then(|(_, x)| x.split_once('>'))
+                .map(|(email, _)| email.to_owned())),
+            Some(_) => Ok(None),
+            None => Err("Git var command terminated by signal".into()),
         }
     }
 }
@@ -80,20 +687,39 @@ impl<Cmd: CommandRunner> MobSessionRepo for GitConfigMobRepo<Cmd> {
 #[cfg(test)]
 mod tests {
     use crate::helpers::MockCommandRunner;
+    use chrono::TimeZone;
 
     use super::*;
 
-    fn create_mock_command_runner(
+    const GET_REGEXP_GLOBAL_ARGS: [&str; 4] = [
+        "config",
+        "--global",
+        "--get-regexp",
+        "^(coauthors-mob|coauthors-mob-settings)\\.",
+    ];
+    const GET_REGEXP_LOCAL_ARGS: [&str; 4] = [
+        "config",
+        "--local",
+        "--get-regexp",
+        "^(coauthors-mob|coauthors-mob-settings)\\.",
+    ];
+    const GET_GLOBAL_EXPIRE_AFTER_ARGS: [&str; 4] = [
+        "config",
+        "--global",
+        "--get",
+        "coauthors-mob-settings.expireAfter",
+    ];
+
+    fn expect_command(
+        mock_cmd_runner: &mut MockCommandRunner,
         program: &str,
         args: &[&str],
         stdout: Vec<u8>,
-        stderr: Vec<u8>,
         status_code: Option<i32>,
-    ) -> MockCommandRunner {
+    ) {
         let cloned_program = program.to_string();
         let cloned_args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
 
-        let mut mock_cmd_runner = MockCommandRunner::new();
         mock_cmd_runner
             .expect_execute()
             .once()
@@ -101,24 +727,61 @@ mod tests {
             .returning(move |_, _| {
                 Ok(CmdOutput {
                     stdout: stdout.clone(),
-                    stderr: stderr.clone(),
+                    stderr: vec![],
                     status_code,
                 })
             });
+    }
+
+    fn create_mock_command_runner(
+        program: &str,
+        args: &[&str],
+        stdout: Vec<u8>,
+        status_code: Option<i32>,
+    ) -> MockCommandRunner {
+        let mut mock_cmd_runner = MockCommandRunner::new();
+        expect_command(&mut mock_cmd_runner, program, args, stdout, status_code);
+        mock_cmd_runner
+    }
+
+    fn expect_set_started(mock_cmd_runner: &mut MockCommandRunner, scope_option: &'static str) {
         mock_cmd_runner
+            .expect_execute()
+            .once()
+            .withf(move |program, args| {
+                program == "git"
+                    && args.len() == 4
+                    && args[..3] == ["config", scope_option, "coauthors-mob.started"]
+                    && args[3].parse::<i64>().is_ok()
+            })
+            .returning(|_, _| {
+                Ok(CmdOutput {
+                    stdout: vec![],
+                    stderr: vec![],
+                    status_code: Some(0),
+                })
+            });
+    }
+
+    fn session_stdout(started_at: i64, expire_after: &str) -> Vec<u8> {
+        format!(
+            "coauthors-mob.entry Leo Messi <leo.messi@example.com>\n\
+             coauthors-mob.started {started_at}\n\
+             coauthors-mob-settings.expireafter {expire_after}\n"
+        )
+        .into_bytes()
     }
 
     #[test]
     fn test_list_coauthors() -> Result<()> {
-        let args = &["config", "--global", "--get-all", "coauthors-mob.entry"];
-        let stdout =
-            b"Leo Messi <leo.messi@example.com>\nEmi Martinez <emi.martinez@example.com>\n".into();
-        let stderr = vec![];
-        let status_code = Some(0);
-        let command_runner = create_mock_command_runner("git", args, stdout, stderr, status_code);
+        let stdout = b"coauthors-mob.entry Leo Messi <leo.messi@example.com>\n\
+                       coauthors-mob.entry Emi Martinez <emi.martinez@example.com>\n"
+            .into();
+        let command_runner =
+            create_mock_command_runner("git", &GET_REGEXP_GLOBAL_ARGS, stdout, Some(0));
         let mob_repo = GitConfigMobRepo { command_runner };
 
-        let result = mob_repo.list_coauthors()?;
+        let result = mob_repo.list_coauthors(MobSessionScope::Global)?;
 
         assert_eq!(
             result,
@@ -133,14 +796,41 @@ mod tests {
 
     #[test]
     fn test_list_coauthors_when_mob_session_empty() -> Result<()> {
-        let args = &["config", "--global", "--get-all", "coauthors-mob.entry"];
-        let stdout = vec![];
-        let stderr = vec![];
-        let status_code = Some(1);
-        let command_runner = create_mock_command_runner("git", args, stdout, stderr, status_code);
+        let command_runner =
+            create_mock_command_runner("git", &GET_REGEXP_GLOBAL_ARGS, vec![], Some(1));
+        let mob_repo = GitConfigMobRepo { command_runner };
+
+        let result = mob_repo.list_coauthors(MobSessionScope::Global)?;
+
+        assert_eq!(result, Vec::<String>::new());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_coauthors_when_mob_session_not_expired() -> Result<()> {
+        let started_at = (Local::now() - TimeDelta::hours(1)).timestamp();
+        let stdout = session_stdout(started_at, "8h");
+        let command_runner =
+            create_mock_command_runner("git", &GET_REGEXP_GLOBAL_ARGS, stdout, Some(0));
+        let mob_repo = GitConfigMobRepo { command_runner };
+
+        let result = mob_repo.list_coauthors(MobSessionScope::Global)?;
+
+        assert_eq!(result, vec!["Leo Messi <leo.messi@example.com>"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_coauthors_when_mob_session_expired() -> Result<()> {
+        let started_at = (Local::now() - TimeDelta::hours(9)).timestamp();
+        let stdout = session_stdout(started_at, "8h");
+        let command_runner =
+            create_mock_command_runner("git", &GET_REGEXP_GLOBAL_ARGS, stdout, Some(0));
         let mob_repo = GitConfigMobRepo { command_runner };
 
-        let result = mob_repo.list_coauthors()?;
+        let result = mob_repo.list_coauthors(MobSessionScope::Global)?;
 
         assert_eq!(result, Vec::<String>::new());
 
@@ -149,14 +839,11 @@ mod tests {
 
     #[test]
     fn test_list_coauthors_when_unexpected_error() -> Result<()> {
-        let args = &["config", "--global", "--get-all", "coauthors-mob.entry"];
-        let stdout = vec![];
-        let stderr = b"uh-oh!".into();
-        let status_code = Some(129);
-        let command_runner = create_mock_command_runner("git", args, stdout, stderr, status_code);
+        let command_runner =
+            create_mock_command_runner("git", &GET_REGEXP_GLOBAL_ARGS, vec![], Some(129));
         let mob_repo = GitConfigMobRepo { command_runner };
 
-        let result = mob_repo.list_coauthors();
+        let result = mob_repo.list_coauthors(MobSessionScope::Global);
 
         assert!(
             result
@@ -168,14 +855,11 @@ mod tests {
 
     #[test]
     fn test_list_coauthors_when_terminated_by_signal() -> Result<()> {
-        let args = &["config", "--global", "--get-all", "coauthors-mob.entry"];
-        let stdout = vec![];
-        let stderr = vec![];
-        let status_code = None;
-        let command_runner = create_mock_command_runner("git", args, stdout, stderr, status_code);
+        let command_runner =
+            create_mock_command_runner("git", &GET_REGEXP_GLOBAL_ARGS, vec![], None);
         let mob_repo = GitConfigMobRepo { command_runner };
 
-        let result = mob_repo.list_coauthors();
+        let result = mob_repo.list_coauthors(MobSessionScope::Global);
 
         assert!(result.is_err_and(|x| x.to_string() == "Git config command terminated by signal"));
 
@@ -183,120 +867,303 @@ mod tests {
     }
 
     #[test]
-    fn test_add_coauthor() -> Result<()> {
-        let coauthor = "Leo Messi <leo.messi@example.com>";
-        let args = &[
-            "config",
-            "--global",
-            "--add",
-            "coauthors-mob.entry",
-            coauthor,
-        ];
-        let stdout = vec![];
-        let stderr = vec![];
-        let status_code = Some(0);
-        let command_runner = create_mock_command_runner("git", args, stdout, stderr, status_code);
+    fn test_list_coauthors_given_local_scope() -> Result<()> {
+        let stdout = b"coauthors-mob.entry Leo Messi <leo.messi@example.com>\n".into();
+        let command_runner =
+            create_mock_command_runner("git", &GET_REGEXP_LOCAL_ARGS, stdout, Some(0));
         let mob_repo = GitConfigMobRepo { command_runner };
 
-        mob_repo.add_coauthor(coauthor)?;
+        let result = mob_repo.list_coauthors(MobSessionScope::Local)?;
+
+        assert_eq!(result, vec!["Leo Messi <leo.messi@example.com>"]);
 
         Ok(())
     }
 
     #[test]
-    fn test_add_coauthor_when_unexpected_error() -> Result<()> {
-        let coauthor = "Leo Messi <leo.messi@example.com>";
-        let args = &[
-            "config",
-            "--global",
-            "--add",
-            "coauthors-mob.entry",
-            coauthor,
-        ];
-        let stdout = vec![];
-        let stderr = b"uh-oh!".into();
-        let status_code = Some(129);
-        let command_runner = create_mock_command_runner("git", args, stdout, stderr, status_code);
+    fn test_list_coauthors_given_local_scope_when_not_in_git_repo() -> Result<()> {
+        let command_runner =
+            create_mock_command_runner("git", &GET_REGEXP_LOCAL_ARGS, vec![], Some(128));
         let mob_repo = GitConfigMobRepo { command_runner };
 
-        let result = mob_repo.add_coauthor(coauthor);
+        let result = mob_repo.list_coauthors(MobSessionScope::Local)?;
 
-        assert!(
-            result
-                .is_err_and(|x| x.to_string() == "Git config command exited with status code: 129")
+        assert_eq!(result, Vec::<String>::new());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_active_coauthors_given_local_session() -> Result<()> {
+        let stdout = b"coauthors-mob.entry Leo Messi <leo.messi@example.com>\n".into();
+        let command_runner =
+            create_mock_command_runner("git", &GET_REGEXP_LOCAL_ARGS, stdout, Some(0));
+        let mob_repo = GitConfigMobRepo { command_runner };
+
+        let result = mob_repo.list_active_coauthors()?;
+
+        assert_eq!(
+            result,
+            (
+                MobSessionScope::Local,
+                vec!["Leo Messi <leo.messi@example.com>".to_owned()]
+            )
         );
 
         Ok(())
     }
 
     #[test]
-    fn test_add_coauthor_when_terminated_by_signal() -> Result<()> {
-        let coauthor = "Leo Messi <leo.messi@example.com>";
-        let args = &[
-            "config",
-            "--global",
-            "--add",
-            "coauthors-mob.entry",
-            coauthor,
-        ];
-        let stdout = vec![];
-        let stderr = vec![];
-        let status_code = None;
-        let command_runner = create_mock_command_runner("git", args, stdout, stderr, status_code);
+    fn test_list_active_coauthors_given_no_local_session() -> Result<()> {
+        let mut command_runner = MockCommandRunner::new();
+        expect_command(
+            &mut command_runner,
+            "git",
+            &GET_REGEXP_LOCAL_ARGS,
+            vec![],
+            Some(1),
+        );
+        expect_command(
+            &mut command_runner,
+            "git",
+            &GET_REGEXP_GLOBAL_ARGS,
+            b"coauthors-mob.entry Emi Martinez <emi.martinez@example.com>\n".into(),
+            Some(0),
+        );
         let mob_repo = GitConfigMobRepo { command_runner };
 
-        let result = mob_repo.add_coauthor(coauthor);
+        let result = mob_repo.list_active_coauthors()?;
 
-        assert!(result.is_err_and(|x| x.to_string() == "Git config command terminated by signal"));
+        assert_eq!(
+            result,
+            (
+                MobSessionScope::Global,
+                vec!["Emi Martinez <emi.martinez@example.com>".to_owned()]
+            )
+        );
 
         Ok(())
     }
 
     #[test]
-    fn test_clear() -> Result<()> {
+    fn test_add_coauthor() -> Result<()> {
+        let coauthor = "Emi Martinez <emi.martinez@example.com>";
+        let started_at = (Local::now() - TimeDelta::hours(1)).timestamp();
         let mut command_runner = MockCommandRunner::new();
+        expect_command(
+            &mut command_runner,
+            "git",
+            &GET_REGEXP_GLOBAL_ARGS,
+            session_stdout(started_at, "8h"),
+            Some(0),
+        );
+        expect_command(
+            &mut command_runner,
+            "git",
+            &[
+                "config",
+                "--global",
+                "--add",
+                "coauthors-mob.entry",
+                coauthor,
+            ],
+            vec![],
+            Some(0),
+        );
+        let mob_repo = GitConfigMobRepo { command_runner };
+
+        mob_repo.add_coauthor(MobSessionScope::Global, coauthor)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_coauthor_when_mob_session_not_started() -> Result<()> {
+        let coauthor = "Leo Messi <leo.messi@example.com>";
+        let mut command_runner = MockCommandRunner::new();
+        expect_command(
+            &mut command_runner,
+            "git",
+            &GET_REGEXP_GLOBAL_ARGS,
+            vec![],
+            Some(1),
+        );
+        expect_set_started(&mut command_runner, "--global");
+        expect_command(
+            &mut command_runner,
+            "git",
+            &[
+                "config",
+                "--global",
+                "--add",
+                "coauthors-mob.entry",
+                coauthor,
+            ],
+            vec![],
+            Some(0),
+        );
+        let mob_repo = GitConfigMobRepo { command_runner };
+
+        mob_repo.add_coauthor(MobSessionScope::Global, coauthor)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_coauthor_when_mob_session_expired() -> Result<()> {
+        let coauthor = "Emi Martinez <emi.martinez@example.com>";
+        let started_at = (Local::now() - TimeDelta::days(2)).timestamp();
+        let mut command_runner = MockCommandRunner::new();
+        expect_command(
+            &mut command_runner,
+            "git",
+            &GET_REGEXP_LOCAL_ARGS,
+            session_stdout(started_at, "eod"),
+            Some(0),
+        );
+        expect_command(
+            &mut command_runner,
+            "git",
+            &["config", "--local", "--remove-section", "coauthors-mob"],
+            vec![],
+            Some(0),
+        );
+        expect_set_started(&mut command_runner, "--local");
+        expect_command(
+            &mut command_runner,
+            "git",
+            &[
+                "config",
+                "--local",
+                "--add",
+                "coauthors-mob.entry",
+                coauthor,
+            ],
+            vec![],
+            Some(0),
+        );
+        let mob_repo = GitConfigMobRepo { command_runner };
+
+        mob_repo.add_coauthor(MobSessionScope::Local, coauthor)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_coauthor_when_unexpected_error() -> Result<()> {
+        let coauthor = "Leo Messi <leo.messi@example.com>";
+        let started_at = Local::now().timestamp();
+        let mut command_runner = MockCommandRunner::new();
+        expect_command(
+            &mut command_runner,
+            "git",
+            &GET_REGEXP_GLOBAL_ARGS,
+            session_stdout(started_at, "8h"),
+            Some(0),
+        );
+        expect_command(
+            &mut command_runner,
+            "git",
+            &[
+                "config",
+                "--global",
+                "--add",
+                "coauthors-mob.entry",
+                coauthor,
+            ],
+            vec![],
+            Some(129),
+        );
+        let mob_repo = GitConfigMobRepo { command_runner };
+
+        let result = mob_repo.add_coauthor(MobSessionScope::Global, coauthor);
+
+        assert!(
+            result
+                .is_err_and(|x| x.to_string() == "Git config command exited with status code: 129")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_coauthor_when_terminated_by_signal() -> Result<()> {
+        let coauthor = "Leo Messi <leo.messi@example.com>";
+        let command_runner =
+            create_mock_command_runner("git", &GET_REGEXP_GLOBAL_ARGS, vec![], None);
+        let mob_repo = GitConfigMobRepo { command_runner };
+
+        let result = mob_repo.add_coauthor(MobSessionScope::Global, coauthor);
+
+        assert!(result.is_err_and(|x| x.to_string() == "Git config command terminated by signal"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_coauthor_given_local_scope_when_not_in_git_repo() -> Result<()> {
+        let coauthor = "Leo Messi <leo.messi@example.com>";
+        let mut command_runner = MockCommandRunner::new();
+        expect_command(
+            &mut command_runner,
+            "git",
+            &GET_REGEXP_LOCAL_ARGS,
+            vec![],
+            Some(128),
+        );
         command_runner
             .expect_execute()
             .once()
-            .withf(|program, args| program == "git" && args == ["config", "--global", "--get-all", "coauthors-mob.entry"])
-            .returning(|_, _| {
-                Ok(CmdOutput {
-                    stdout: b"Leo Messi <leo.messi@example.com>\nEmi Martinez <emi.martinez@example.com>\n".into(),
-                    stderr: vec![],
-                    status_code: Some(0),
-                })
-            });
-        command_runner
-            .expect_execute()
-            .once()
-            .withf(|program, args| {
-                program == "git"
-                    && args == ["config", "--global", "--remove-section", "coauthors-mob"]
-            })
+            .withf(|program, args| program == "git" && args[2] == "coauthors-mob.started")
             .returning(|_, _| {
                 Ok(CmdOutput {
                     stdout: vec![],
-                    stderr: vec![],
-                    status_code: Some(0),
+                    stderr: b"fatal: --local can only be used inside a git repository".into(),
+                    status_code: Some(128),
                 })
             });
         let mob_repo = GitConfigMobRepo { command_runner };
 
-        mob_repo.clear()?;
+        let result = mob_repo.add_coauthor(MobSessionScope::Local, coauthor);
+
+        assert!(result.is_err_and(
+            |x| x.to_string() == "Local mob session can only be used inside a git repository"
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_clear() -> Result<()> {
+        let mut command_runner = MockCommandRunner::new();
+        expect_command(
+            &mut command_runner,
+            "git",
+            &GET_REGEXP_GLOBAL_ARGS,
+            session_stdout(Local::now().timestamp(), "8h"),
+            Some(0),
+        );
+        expect_command(
+            &mut command_runner,
+            "git",
+            &["config", "--global", "--remove-section", "coauthors-mob"],
+            vec![],
+            Some(0),
+        );
+        let mob_repo = GitConfigMobRepo { command_runner };
+
+        mob_repo.clear(MobSessionScope::Global)?;
 
         Ok(())
     }
 
     #[test]
     fn test_clear_when_mob_session_empty() -> Result<()> {
-        let args = &["config", "--global", "--get-all", "coauthors-mob.entry"];
-        let stdout = vec![];
-        let stderr = vec![];
-        let status_code = Some(1);
-        let command_runner = create_mock_command_runner("git", args, stdout, stderr, status_code);
+        let command_runner =
+            create_mock_command_runner("git", &GET_REGEXP_GLOBAL_ARGS, vec![], Some(1));
         let mob_repo = GitConfigMobRepo { command_runner };
 
-        mob_repo.clear()?;
+        mob_repo.clear(MobSessionScope::Global)?;
 
         Ok(())
     }
@@ -304,34 +1171,23 @@ mod tests {
     #[test]
     fn test_clear_when_unexpected_error() -> Result<()> {
         let mut command_runner = MockCommandRunner::new();
-        command_runner
-            .expect_execute()
-            .once()
-            .withf( |program, args| program == "git" && args == ["config", "--global", "--get-all", "coauthors-mob.entry"])
-            .returning( |_, _| {
-                Ok(CmdOutput {
-                    stdout: b"Leo Messi <leo.messi@example.com>\nEmi Martinez <emi.martinez@example.com>\n".into(),
-                    stderr: vec![],
-                    status_code: Some(0),
-                })
-            });
-        command_runner
-            .expect_execute()
-            .once()
-            .withf(|program, args| {
-                program == "git"
-                    && args == ["config", "--global", "--remove-section", "coauthors-mob"]
-            })
-            .returning(|_, _| {
-                Ok(CmdOutput {
-                    stdout: vec![],
-                    stderr: b"uh-oh!".into(),
-                    status_code: Some(129),
-                })
-            });
+        expect_command(
+            &mut command_runner,
+            "git",
+            &GET_REGEXP_GLOBAL_ARGS,
+            session_stdout(Local::now().timestamp(), "8h"),
+            Some(0),
+        );
+        expect_command(
+            &mut command_runner,
+            "git",
+            &["config", "--global", "--remove-section", "coauthors-mob"],
+            vec![],
+            Some(129),
+        );
         let mob_repo = GitConfigMobRepo { command_runner };
 
-        let result = mob_repo.clear();
+        let result = mob_repo.clear(MobSessionScope::Global);
 
         assert!(
             result
@@ -344,36 +1200,712 @@ mod tests {
     #[test]
     fn test_clear_when_terminated_by_signal() -> Result<()> {
         let mut command_runner = MockCommandRunner::new();
-        command_runner
-            .expect_execute()
-            .once()
-            .withf( |program, args| program == "git" && args == ["config", "--global", "--get-all", "coauthors-mob.entry"])
-            .returning( |_, _| {
-                Ok(CmdOutput {
-                    stdout: b"Leo Messi <leo.messi@example.com>\nEmi Martinez <emi.martinez@example.com>\n".into(),
-                    stderr: vec![],
-                    status_code: Some(0),
-                })
-            });
-        command_runner
-            .expect_execute()
-            .once()
-            .withf(|program, args| {
-                program == "git"
-                    && args == ["config", "--global", "--remove-section", "coauthors-mob"]
-            })
-            .returning(|_, _| {
-                Ok(CmdOutput {
-                    stdout: vec![],
-                    stderr: vec![],
-                    status_code: None,
-                })
-            });
+        expect_command(
+            &mut command_runner,
+            "git",
+            &GET_REGEXP_GLOBAL_ARGS,
+            session_stdout(Local::now().timestamp(), "8h"),
+            Some(0),
+        );
+        expect_command(
+            &mut command_runner,
+            "git",
+            &["config", "--global", "--remove-section", "coauthors-mob"],
+            vec![],
+            None,
+        );
         let mob_repo = GitConfigMobRepo { command_runner };
-        let result = mob_repo.clear();
+
+        let result = mob_repo.clear(MobSessionScope::Global);
 
         assert!(result.is_err_and(|x| x.to_string() == "Git config command terminated by signal"));
 
         Ok(())
     }
+
+    #[test]
+    fn test_clear_given_driver() -> Result<()> {
+        let mut command_runner = MockCommandRunner::new();
+        expect_command(
+            &mut command_runner,
+            "git",
+            &GET_REGEXP_LOCAL_ARGS,
+            b"coauthors-mob.driver Leo Messi <leo.messi@example.com>\n\
+              coauthors-mob.started 1750000000\n"
+                .into(),
+            Some(0),
+        );
+        expect_command(
+            &mut command_runner,
+            "git",
+            &GET_GLOBAL_EXPIRE_AFTER_ARGS,
+            vec![],
+            Some(1),
+        );
+        for key in ["author.name", "author.email"] {
+            expect_command(
+                &mut command_runner,
+                "git",
+                &["config", "--local", "--unset", key],
+                vec![],
+                Some(0),
+            );
+        }
+        expect_command(
+            &mut command_runner,
+            "git",
+            &["config", "--local", "--remove-section", "coauthors-mob"],
+            vec![],
+            Some(0),
+        );
+        let mob_repo = GitConfigMobRepo { command_runner };
+
+        mob_repo.clear(MobSessionScope::Local)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove_coauthor() -> Result<()> {
+        let coauthor = "Leo Messi <leo.messi@example.com>";
+        let args = &[
+            "config",
+            "--global",
+            "--fixed-value",
+            "--unset-all",
+            "coauthors-mob.entry",
+            coauthor,
+        ];
+        let command_runner = create_mock_command_runner("git", args, vec![], Some(5));
+        let mob_repo = GitConfigMobRepo { command_runner };
+
+        mob_repo.remove_coauthor(MobSessionScope::Global, coauthor)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_driver() -> Result<()> {
+        let started_at = Local::now().timestamp();
+        let stdout = format!(
+            "coauthors-mob.entry Emi Martinez <emi.martinez@example.com>\n\
+             coauthors-mob.driver Leo Messi <leo.messi@example.com>\n\
+             coauthors-mob.started {started_at}\n"
+        )
+        .into_bytes();
+        let command_runner =
+            create_mock_command_runner("git", &GET_REGEXP_GLOBAL_ARGS, stdout, Some(0));
+        let mob_repo = GitConfigMobRepo { command_runner };
+
+        let result = mob_repo.get_driver(MobSessionScope::Global)?;
+
+        assert_eq!(result.as_deref(), Some("Leo Messi <leo.messi@example.com>"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_started_at() -> Result<()> {
+        let started_at = Local::now().timestamp();
+        let command_runner = create_mock_command_runner(
+            "git",
+            &GET_REGEXP_GLOBAL_ARGS,
+            session_stdout(started_at, "8h"),
+            Some(0),
+        );
+        let mob_repo = GitConfigMobRepo { command_runner };
+
+        let result = mob_repo.started_at(MobSessionScope::Global)?;
+
+        assert_eq!(result.map(|x| x.timestamp()), Some(started_at));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_started_at_when_mob_session_expired() -> Result<()> {
+        let started_at = (Local::now() - TimeDelta::hours(9)).timestamp();
+        let command_runner = create_mock_command_runner(
+            "git",
+            &GET_REGEXP_GLOBAL_ARGS,
+            session_stdout(started_at, "8h"),
+            Some(0),
+        );
+        let mob_repo = GitConfigMobRepo { command_runner };
+
+        let result = mob_repo.started_at(MobSessionScope::Global)?;
+
+        assert_eq!(result, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_driver() -> Result<()> {
+        let driver = TeamMember::new("lm", "Leo Messi", "leo.messi@example.com")?;
+        let mut command_runner = MockCommandRunner::new();
+        expect_command(
+            &mut command_runner,
+            "git",
+            &GET_REGEXP_LOCAL_ARGS,
+            vec![],
+            Some(1),
+        );
+        expect_set_started(&mut command_runner, "--local");
+        // the user's own identity is saved
+        expect_command(
+            &mut command_runner,
+            "git",
+            &["config", "--local", "--get", "author.name"],
+            b"Cata Diaz\n".into(),
+            Some(0),
+        );
+        expect_command(
+            &mut command_runner,
+            "git",
+            &["config", "--local", "--get", "author.email"],
+            vec![],
+            Some(1),
+        );
+        for (key, value) in [
+            ("coauthors-mob.savedAuthorName", "Cata Diaz"),
+            ("coauthors-mob.driver", "Leo Messi <leo.messi@example.com>"),
+            ("author.name", "Leo Messi"),
+            ("author.email", "leo.messi@example.com"),
+        ] {
+            expect_command(
+                &mut command_runner,
+                "git",
+                &["config", "--local", key, value],
+                vec![],
+                Some(0),
+            );
+        }
+        let mob_repo = GitConfigMobRepo { command_runner };
+
+        mob_repo.set_driver(MobSessionScope::Local, &driver)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_driver_given_driver() -> Result<()> {
+        let driver = TeamMember::new("em", "Emi Martinez", "emi.martinez@example.com")?;
+        let stdout = format!(
+            "coauthors-mob.driver Leo Messi <leo.messi@example.com>\n\
+             coauthors-mob.savedauthorname Cata Diaz\n\
+             coauthors-mob.started {}\n",
+            Local::now().timestamp()
+        )
+        .into_bytes();
+        let mut command_runner = MockCommandRunner::new();
+        expect_command(
+            &mut command_runner,
+            "git",
+            &GET_REGEXP_LOCAL_ARGS,
+            stdout,
+            Some(0),
+        );
+        expect_command(
+            &mut command_runner,
+            "git",
+            &GET_GLOBAL_EXPIRE_AFTER_ARGS,
+            vec![],
+            Some(1),
+        );
+        // the previous driver's identity is not saved
+        for (key, value) in [
+            (
+                "coauthors-mob.driver",
+                "Emi Martinez <emi.martinez@example.com>",
+            ),
+            ("author.name", "Emi Martinez"),
+            ("author.email", "emi.martinez@example.com"),
+        ] {
+            expect_command(
+                &mut command_runner,
+                "git",
+                &["config", "--local", key, value],
+                vec![],
+                Some(0),
+            );
+        }
+        let mob_repo = GitConfigMobRepo { command_runner };
+
+        mob_repo.set_driver(MobSessionScope::Local, &driver)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_clear_given_driver_and_saved_author() -> Result<()> {
+        let mut command_runner = MockCommandRunner::new();
+        expect_command(
+            &mut command_runner,
+            "git",
+            &GET_REGEXP_GLOBAL_ARGS,
+            b"coauthors-mob.driver Leo Messi <leo.messi@example.com>\n\
+              coauthors-mob.savedauthorname Cata Diaz\n\
+              coauthors-mob.savedauthoremail cata.diaz@example.com\n\
+              coauthors-mob.started 1750000000\n"
+                .into(),
+            Some(0),
+        );
+        // the user's own identity is restored
+        for (key, value) in [
+            ("author.name", "Cata Diaz"),
+            ("author.email", "cata.diaz@example.com"),
+        ] {
+            expect_command(
+                &mut command_runner,
+                "git",
+                &["config", "--global", key, value],
+                vec![],
+                Some(0),
+            );
+        }
+        expect_command(
+            &mut command_runner,
+            "git",
+            &["config", "--global", "--remove-section", "coauthors-mob"],
+            vec![],
+            Some(0),
+        );
+        let mob_repo = GitConfigMobRepo { command_runner };
+
+        mob_repo.clear(MobSessionScope::Global)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove_expired() -> Result<()> {
+        let started_at = (Local::now() - TimeDelta::hours(9)).timestamp();
+        let stdout = format!(
+            "coauthors-mob.entry Emi Martinez <emi.martinez@example.com>\n\
+             coauthors-mob.driver Leo Messi <leo.messi@example.com>\n\
+             coauthors-mob.started {started_at}\n\
+             coauthors-mob-settings.expireafter 8h\n"
+        )
+        .into_bytes();
+        let mut command_runner = MockCommandRunner::new();
+        expect_command(
+            &mut command_runner,
+            "git",
+            &GET_REGEXP_GLOBAL_ARGS,
+            stdout,
+            Some(0),
+        );
+        for key in ["author.name", "author.email"] {
+            expect_command(
+                &mut command_runner,
+                "git",
+                &["config", "--global", "--unset", key],
+                vec![],
+                Some(0),
+            );
+        }
+        expect_command(
+            &mut command_runner,
+            "git",
+            &["config", "--global", "--remove-section", "coauthors-mob"],
+            vec![],
+            Some(0),
+        );
+        let mob_repo = GitConfigMobRepo { command_runner };
+
+        let result = mob_repo.remove_expired(MobSessionScope::Global)?;
+
+        let started_at = DateTime::from_timestamp(started_at, 0).map(|x| x.with_timezone(&Local));
+        assert_eq!(
+            result,
+            Some(ExpiredSession {
+                coauthors: vec!["Emi Martinez <emi.martinez@example.com>".to_owned()],
+                driver: Some("Leo Messi <leo.messi@example.com>".to_owned()),
+                started_at,
+                expired_at: started_at.unwrap() + TimeDelta::hours(8),
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove_expired_when_not_expired() -> Result<()> {
+        let started_at = Local::now().timestamp();
+        let command_runner = create_mock_command_runner(
+            "git",
+            &GET_REGEXP_GLOBAL_ARGS,
+            session_stdout(started_at, "8h"),
+            Some(0),
+        );
+        let mob_repo = GitConfigMobRepo { command_runner };
+
+        assert_eq!(mob_repo.remove_expired(MobSessionScope::Global)?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_active_coauthors_given_local_session_with_only_driver() -> Result<()> {
+        let stdout = format!(
+            "coauthors-mob.driver Leo Messi <leo.messi@example.com>\n\
+             coauthors-mob.started {}\n",
+            Local::now().timestamp()
+        )
+        .into_bytes();
+        let mut command_runner = MockCommandRunner::new();
+        expect_command(
+            &mut command_runner,
+            "git",
+            &GET_REGEXP_LOCAL_ARGS,
+            stdout,
+            Some(0),
+        );
+        expect_command(
+            &mut command_runner,
+            "git",
+            &GET_GLOBAL_EXPIRE_AFTER_ARGS,
+            vec![],
+            Some(1),
+        );
+        let mob_repo = GitConfigMobRepo { command_runner };
+
+        let result = mob_repo.list_active_coauthors()?;
+
+        assert_eq!(result, (MobSessionScope::Local, vec![]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_rotation() -> Result<()> {
+        let stdout = format!(
+            "coauthors-mob.rotation lm\n\
+             coauthors-mob.rotation em\n\
+             coauthors-mob.started {}\n",
+            Local::now().timestamp()
+        )
+        .into_bytes();
+        let command_runner =
+            create_mock_command_runner("git", &GET_REGEXP_GLOBAL_ARGS, stdout, Some(0));
+        let mob_repo = GitConfigMobRepo { command_runner };
+
+        let result = mob_repo.list_rotation(MobSessionScope::Global)?;
+
+        assert_eq!(result, vec!["lm", "em"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_rotation() -> Result<()> {
+        let mut command_runner = MockCommandRunner::new();
+        expect_command(
+            &mut command_runner,
+            "git",
+            &GET_REGEXP_GLOBAL_ARGS,
+            session_stdout(Local::now().timestamp(), "8h"),
+            Some(0),
+        );
+        expect_command(
+            &mut command_runner,
+            "git",
+            &[
+                "config",
+                "--global",
+                "--unset-all",
+                "coauthors-mob.rotation",
+            ],
+            vec![],
+            Some(5),
+        );
+        for key in ["lm", "em"] {
+            expect_command(
+                &mut command_runner,
+                "git",
+                &["config", "--global", "--add", "coauthors-mob.rotation", key],
+                vec![],
+                Some(0),
+            );
+        }
+        let mob_repo = GitConfigMobRepo { command_runner };
+
+        mob_repo.set_rotation(MobSessionScope::Global, &["lm".to_owned(), "em".to_owned()])?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_pingpong() -> Result<()> {
+        let stdout = format!(
+            "coauthors-mob.pingpong true\n\
+             coauthors-mob.started {}\n",
+            Local::now().timestamp()
+        )
+        .into_bytes();
+        let mut command_runner = MockCommandRunner::new();
+        expect_command(
+            &mut command_runner,
+            "git",
+            &GET_REGEXP_LOCAL_ARGS,
+            stdout,
+            Some(0),
+        );
+        expect_command(
+            &mut command_runner,
+            "git",
+            &GET_GLOBAL_EXPIRE_AFTER_ARGS,
+            vec![],
+            Some(1),
+        );
+        let mob_repo = GitConfigMobRepo { command_runner };
+
+        assert!(mob_repo.is_pingpong(MobSessionScope::Local)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_pingpong() -> Result<()> {
+        let mut command_runner = MockCommandRunner::new();
+        expect_command(
+            &mut command_runner,
+            "git",
+            &GET_REGEXP_GLOBAL_ARGS,
+            session_stdout(Local::now().timestamp(), "8h"),
+            Some(0),
+        );
+        expect_command(
+            &mut command_runner,
+            "git",
+            &["config", "--global", "coauthors-mob.pingpong", "true"],
+            vec![],
+            Some(0),
+        );
+        let mob_repo = GitConfigMobRepo { command_runner };
+
+        mob_repo.set_pingpong(MobSessionScope::Global, true)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_expired_at() -> Result<()> {
+        let started_at = Local::now() - TimeDelta::hours(3);
+        let stdout = session_stdout(started_at.timestamp(), "2h");
+        let command_runner =
+            create_mock_command_runner("git", &GET_REGEXP_GLOBAL_ARGS, stdout, Some(0));
+        let mob_repo = GitConfigMobRepo { command_runner };
+
+        let result = mob_repo.expired_at(MobSessionScope::Global)?;
+
+        assert_eq!(
+            result.map(|x| x.timestamp()),
+            Some((started_at + TimeDelta::hours(2)).timestamp())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_expired_at_when_mob_session_not_expired() -> Result<()> {
+        let started_at = Local::now() - TimeDelta::hours(3);
+        let stdout = session_stdout(started_at.timestamp(), "never");
+        let command_runner =
+            create_mock_command_runner("git", &GET_REGEXP_GLOBAL_ARGS, stdout, Some(0));
+        let mob_repo = GitConfigMobRepo { command_runner };
+
+        let result = mob_repo.expired_at(MobSessionScope::Global)?;
+
+        assert_eq!(result, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_expired_at_given_local_session_with_global_expiry() -> Result<()> {
+        let started_at = (Local::now() - TimeDelta::hours(9)).timestamp();
+        let stdout = format!(
+            "coauthors-mob.entry Leo Messi <leo.messi@example.com>\n\
+             coauthors-mob.started {started_at}\n"
+        )
+        .into_bytes();
+        let mut command_runner = MockCommandRunner::new();
+        expect_command(
+            &mut command_runner,
+            "git",
+            &GET_REGEXP_LOCAL_ARGS,
+            stdout,
+            Some(0),
+        );
+        expect_command(
+            &mut command_runner,
+            "git",
+            &GET_GLOBAL_EXPIRE_AFTER_ARGS,
+            b"8h\n".into(),
+            Some(0),
+        );
+        let mob_repo = GitConfigMobRepo { command_runner };
+
+        let result = mob_repo.expired_at(MobSessionScope::Local)?;
+
+        assert_eq!(
+            result.map(|x| x.timestamp()),
+            Some(started_at + 8 * 60 * 60)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_coauthors_given_invalid_expiry() -> Result<()> {
+        let started_at = (Local::now() - TimeDelta::days(2)).timestamp();
+        let stdout = session_stdout(started_at, "8 hours");
+        let command_runner =
+            create_mock_command_runner("git", &GET_REGEXP_GLOBAL_ARGS, stdout, Some(0));
+        let mob_repo = GitConfigMobRepo { command_runner };
+
+        let result = mob_repo.list_coauthors(MobSessionScope::Global)?;
+
+        assert_eq!(result, vec!["Leo Messi <leo.messi@example.com>"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_expire_after() -> Result<()> {
+        let args = &[
+            "config",
+            "--global",
+            "coauthors-mob-settings.expireAfter",
+            "8h",
+        ];
+        let command_runner = create_mock_command_runner("git", args, vec![], Some(0));
+        let mob_repo = GitConfigMobRepo { command_runner };
+
+        mob_repo.set_expire_after(
+            MobSessionScope::Global,
+            SessionExpiry::After(TimeDelta::hours(8)),
+        )?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_expire_after_given_never() -> Result<()> {
+        let args = &[
+            "config",
+            "--global",
+            "--unset",
+            "coauthors-mob-settings.expireAfter",
+        ];
+        let command_runner = create_mock_command_runner("git", args, vec![], Some(5));
+        let mob_repo = GitConfigMobRepo { command_runner };
+
+        mob_repo.set_expire_after(MobSessionScope::Global, SessionExpiry::Never)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_expire_after_given_never_and_local_scope() -> Result<()> {
+        let args = &[
+            "config",
+            "--local",
+            "coauthors-mob-settings.expireAfter",
+            "never",
+        ];
+        let command_runner = create_mock_command_runner("git", args, vec![], Some(0));
+        let mob_repo = GitConfigMobRepo { command_runner };
+
+        mob_repo.set_expire_after(MobSessionScope::Local, SessionExpiry::Never)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_author_email() -> Result<()> {
+        let command_runner = create_mock_command_runner(
+            "git",
+            &["var", "GIT_AUTHOR_IDENT"],
+            b"Leo Messi <leo.messi@example.com> 1760000000 +0100\n".into(),
+            Some(0),
+        );
+        let mob_repo = GitConfigMobRepo { command_runner };
+
+        assert_eq!(
+            mob_repo.get_author_email()?,
+            Some("leo.messi@example.com".to_owned())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_author_email_when_author_identity_unknown() -> Result<()> {
+        let command_runner =
+            create_mock_command_runner("git", &["var", "GIT_AUTHOR_IDENT"], vec![], Some(128));
+        let mob_repo = GitConfigMobRepo { command_runner };
+
+        assert_eq!(mob_repo.get_author_email()?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_session_expiry_from_str() {
+        assert_eq!(
+            "30m".parse(),
+            Ok(SessionExpiry::After(TimeDelta::minutes(30)))
+        );
+        assert_eq!("8h".parse(), Ok(SessionExpiry::After(TimeDelta::hours(8))));
+        assert_eq!("2d".parse(), Ok(SessionExpiry::After(TimeDelta::days(2))));
+        assert_eq!("EOD".parse(), Ok(SessionExpiry::EndOfDay));
+        assert_eq!("end of day".parse(), Ok(SessionExpiry::EndOfDay));
+        assert_eq!("never".parse(), Ok(SessionExpiry::Never));
+        assert!("0h".parse::<SessionExpiry>().is_err());
+        assert!("8 hours".parse::<SessionExpiry>().is_err());
+        assert!("h".parse::<SessionExpiry>().is_err());
+        assert_eq!(
+            "999999999d".parse::<SessionExpiry>(),
+            Err(
+                "Invalid session expiry: 999999999d. Expected a duration of at most 36500d"
+                    .to_owned()
+            )
+        );
+    }
+
+    #[test]
+    fn test_session_expiry_display() {
+        assert_eq!(
+            SessionExpiry::After(TimeDelta::minutes(90)).to_string(),
+            "90m"
+        );
+        assert_eq!(SessionExpiry::After(TimeDelta::hours(8)).to_string(), "8h");
+        assert_eq!(SessionExpiry::After(TimeDelta::hours(48)).to_string(), "2d");
+        assert_eq!(SessionExpiry::EndOfDay.to_string(), "eod");
+        assert_eq!(SessionExpiry::Never.to_string(), "never");
+    }
+
+    #[test]
+    fn test_session_expiry_expires_at_when_out_of_range() {
+        let started_at = Local.with_ymd_and_hms(2025, 6, 1, 9, 30, 0).unwrap();
+
+        let result = SessionExpiry::After(TimeDelta::MAX).expires_at(started_at);
+
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_session_expiry_expires_at_end_of_day() {
+        let started_at = Local.with_ymd_and_hms(2025, 6, 1, 9, 30, 0).unwrap();
+
+        let result = SessionExpiry::EndOfDay.expires_at(started_at);
+
+        assert_eq!(
+            result,
+            Some(Local.with_ymd_and_hms(2025, 6, 2, 0, 0, 0).unwrap())
+        );
+    }
 }
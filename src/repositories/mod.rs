@@ -1,10 +1,36 @@
+pub mod coauthors_file_team_member_repo;
+pub mod commit_log_repo;
+pub mod history_repo;
+pub mod layered_team_member_repo;
 pub mod mob_session_repo;
+pub mod preset_repo;
+pub mod roster_file_team_member_repo;
+pub mod synced_team_member_repo;
+pub mod team_group_repo;
 pub mod team_member_repo;
 
-pub use mob_session_repo::{GitConfigMobRepo, MobSessionRepo};
-pub use team_member_repo::{GitConfigTeamMemberRepo, TeamMemberRepo};
+pub use coauthors_file_team_member_repo::CoauthorsFileTeamMemberRepo;
+pub use commit_log_repo::{Commit, CommitLogRepo, Contributor, GitCommitLogRepo};
+pub use history_repo::{FileHistoryRepo, HistoryEntry, HistoryEvent, HistoryRepo, SessionEnd};
+pub use layered_team_member_repo::LayeredTeamMemberRepo;
+pub use mob_session_repo::{
+    ExpiredSession, GitConfigMobRepo, MobSessionRepo, MobSessionScope, SessionExpiry,
+};
+pub use preset_repo::{GitConfigPresetRepo, Preset, PresetRepo};
+pub use roster_file_team_member_repo::RosterFileTeamMemberRepo;
+pub use synced_team_member_repo::SyncedTeamMemberRepo;
+pub use team_group_repo::{GitConfigTeamGroupRepo, TeamGroup, TeamGroupRepo};
+pub use team_member_repo::{GitConfigTeamMemberRepo, TeamMember, TeamMemberRepo, TeamMemberSource};
 
+#[cfg(test)]
+pub use commit_log_repo::MockCommitLogRepo;
+#[cfg(test)]
+pub use history_repo::MockHistoryRepo;
 #[cfg(test)]
 pub use mob_session_repo::MockMobSessionRepo;
 #[cfg(test)]
+pub use preset_repo::MockPresetRepo;
+#[cfg(test)]
+pub use team_group_repo::MockTeamGroupRepo;
+#[cfg(test)]
 pub use team_member_repo::MockTeamMemberRepo;
@@ -0,0 +1,468 @@
+use crate::Result;
+use crate::helpers::{CmdOutput, CommandRunner};
+use crate::validation;
+
+/// Co-author(s) and driver of a mob/pair programming session saved under a name
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Preset {
+    pub name: String,
+    pub coauthors: Vec<String>,
+    /// Driver (i.e. the commit author) of the session, formatted as "Name <email>"
+    pub driver: Option<String>,
+}
+
+impl Preset {
+    pub fn new(name: &str, coauthors: &[String]) -> Result<Self> {
+        if !validation::is_identifier(name) {
+            return Err(format!(
+                "Invalid preset name: {name}. It must start with a letter and only contain letters, digits and hyphens"
+            )
+            .into());
+        }
+
+        Ok(Self {
+            name: name.to_owned(),
+            coauthors: coauthors.to_vec(),
+            driver: None,
+        })
+    }
+}
+
+#[cfg(test)]
+use mockall::automock;
+#[cfg_attr(test, automock)]
+pub trait PresetRepo {
+    fn list(&self) -> Result<Vec<Preset>>;
+    fn get(&self, name: &str) -> Result<Option<Preset>>;
+    /// Saves the preset, replacing any existing preset with the same name
+    fn save(&self, preset: &Preset) -> Result<()>;
+    fn remove(&self, name: &str) -> Result<()>;
+    /// Gets the name of the preset used by the current repository when committing with no session
+    fn get_default(&self) -> Result<Option<String>>;
+    fn set_default(&self, name: &str) -> Result<()>;
+    fn unset_default(&self) -> Result<()>;
+}
+
+/// Preset repository which stores presets in the user's global git config and the default preset
+/// of a repository in its local git config
+pub struct GitConfigPresetRepo<Cmd> {
+    pub command_runner: Cmd,
+}
+
+impl<Cmd: CommandRunner> GitConfigPresetRepo<Cmd> {
+    const PRESET_SECTION: &'static str = "coauthors-preset";
+    const COAUTHOR_KEY: &'static str = "entry";
+    const DRIVER_KEY: &'static str = "driver";
+    const DEFAULT_PRESET_KEY: &'static str = "coauthors-mob-settings.defaultPreset";
+
+    const EXIT_CODE_SUCCESS: i32 = 0;
+    const EXIT_CODE_CONFIG_INVALID_KEY: i32 = 1;
+    const EXIT_CODE_CONFIG_KEY_NOT_SET: i32 = 5;
+    const EXIT_CODE_NOT_IN_GIT_REPO: i32 = 128;
+
+    fn coauthor_key(name: &str) -> String {
+        format!("{}.{name}.{}", Self::PRESET_SECTION, Self::COAUTHOR_KEY)
+    }
+
+    fn driver_key(name: &str) -> String {
+        format!("{}.{name}.{}", Self::PRESET_SECTION, Self::DRIVER_KEY)
+    }
+
+    /// Lists the presets whose name matches the regex, which are empty if none are found
+    fn list_matching(&self, name_regex: &str) -> Result<Vec<Preset>> {
+        let section = Self::PRESET_SECTION;
+        let search_regex = format!(
+            "^{section}\\.{name_regex}\\.({}|{})$",
+            Self::COAUTHOR_KEY,
+            Self::DRIVER_KEY
+        );
+
+        let output = self.command_runner.execute(
+            "git",
+            &["config", "--global", "--get-regexp", &search_regex],
+        )?;
+
+        let stdout = match output.status_code {
+            Some(Self::EXIT_CODE_SUCCESS) => String::from_utf8(output.stdout)?,
+            Some(Self::EXIT_CODE_CONFIG_INVALID_KEY) => return Ok(vec![]),
+            _ => return Self::git_config_error(&output),
+        };
+
+        let mut presets: Vec<Preset> = Vec::new();
+        for line in stdout.lines() {
+            let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+            let Some((name, field)) = key
+                .strip_prefix(&format!("{section}."))
+                .and_then(|x| x.rsplit_once('.'))
+            else {
+                continue;
+            };
+
+            let index = match presets.iter().position(|x| x.name == name) {
+                Some(index) => index,
+                None => {
+                    presets.push(Preset {
+                        name: name.to_owned(),
+                        coauthors: vec![],
+                        driver: None,
+                    });
+                    presets.len() - 1
+                }
+            };
+            let preset = &mut presets[index];
+            if field == Self::DRIVER_KEY {
+                preset.driver = Some(value.to_owned());
+            } else {
+                preset.coauthors.push(value.to_owned());
+            }
+        }
+
+        Ok(presets)
+    }
+
+    fn unset_all(&self, key: &str) -> Result<()> {
+        let output = self
+            .command_runner
+            .execute("git", &["config", "--global", "--unset-all", key])?;
+
+        match output.status_code {
+            Some(Self::EXIT_CODE_SUCCESS | Self::EXIT_CODE_CONFIG_KEY_NOT_SET) => Ok(()),
+            _ => Self::git_config_error(&output),
+        }
+    }
+
+    fn git_config_error<T>(output: &CmdOutput) -> Result<T> {
+        match output.status_code {
+            Some(code) => Err(format!("Git config command exited with status code: {code}").into()),
+            None => Err("Git config command terminated by signal".into()),
+        }
+    }
+
+    fn local_git_config_error<T>(output: &CmdOutput) -> Result<T> {
+        match output.status_code {
+            Some(Self::EXIT_CODE_NOT_IN_GIT_REPO) => {
+                Err("Default preset can only be used inside a git repository".into())
+            }
+            _ => Self::git_config_error(output),
+        }
+    }
+}
+
+impl<Cmd: CommandRunner> PresetRepo for GitConfigPresetRepo<Cmd> {
+    fn list(&self) -> Result<Vec<Preset>> {
+        self.list_matching(".*")
+    }
+
+    fn get(&self, name: &str) -> Result<Option<Preset>> {
+        // presets are saved with valid names only, which contain no special characters of regexes
+        if !validation::is_identifier(name) {
+            return Ok(None);
+        }
+
+        Ok(self
+            .list_matching(name)?
+            .into_iter()
+            .find(|x| x.name == name))
+    }
+
+    fn save(&self, preset: &Preset) -> Result<()> {
+        self.remove(&preset.name)?;
+
+        let key = Self::coauthor_key(&preset.name);
+        let values = preset.coauthors.iter().map(|x| (key.clone(), x)).chain(
+            preset
+                .driver
+                .iter()
+                .map(|x| (Self::driver_key(&preset.name), x)),
+        );
+        for (key, value) in values {
+            let output = self
+                .command_runner
+                .execute("git", &["config", "--global", "--add", &key, value])?;
+
+            match output.status_code {
+                Some(Self::EXIT_CODE_SUCCESS) => {}
+                Some(Self::EXIT_CODE_CONFIG_INVALID_KEY) => {
+                    return Err(format!("Invalid preset name: {}", preset.name).into());
+                }
+                _ => return Self::git_config_error(&output),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn remove(&self, name: &str) -> Result<()> {
+        self.unset_all(&Self::coauthor_key(name))?;
+        self.unset_all(&Self::driver_key(name))
+    }
+
+    fn get_default(&self) -> Result<Option<String>> {
+        let output = self.command_runner.execute(
+            "git",
+            &["config", "--local", "--get", Self::DEFAULT_PRESET_KEY],
+        )?;
+
+        match output.status_code {
+            Some(Self::EXIT_CODE_SUCCESS) => {
+                Ok(Some(String::from_utf8(output.stdout)?.trim().to_owned()))
+            }
+            // There is no default preset outside a git repository
+            Some(Self::EXIT_CODE_CONFIG_INVALID_KEY | Self::EXIT_CODE_NOT_IN_GIT_REPO) => Ok(None),
+            _ => Self::git_config_error(&output),
+        }
+    }
+
+    fn set_default(&self, name: &str) -> Result<()> {
+        let output = self.command_runner.execute(
+            "git",
+            &["config", "--local", Self::DEFAULT_PRESET_KEY, name],
+        )?;
+
+        match output.status_code {
+            Some(Self::EXIT_CODE_SUCCESS) => Ok(()),
+            _ => Self::local_git_config_error(&output),
+        }
+    }
+
+    fn unset_default(&self) -> Result<()> {
+        let output = self.command_runner.execute(
+            "git",
+            &["config", "--local", "--unset", Self::DEFAULT_PRESET_KEY],
+        )?;
+
+        match output.status_code {
+            Some(Self::EXIT_CODE_SUCCESS | Self::EXIT_CODE_CONFIG_KEY_NOT_SET) => Ok(()),
+            _ => Self::local_git_config_error(&output),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::MockCommandRunner;
+
+    fn expect_command(
+        command_runner: &mut MockCommandRunner,
+        args: &[&str],
+        stdout: Vec<u8>,
+        status_code: Option<i32>,
+    ) {
+        let cloned_args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+        command_runner
+            .expect_execute()
+            .once()
+            .withf(move |program, args| program == "git" && args == cloned_args)
+            .returning(move |_, _| {
+                Ok(CmdOutput {
+                    stdout: stdout.clone(),
+                    stderr: vec![],
+                    status_code,
+                })
+            });
+    }
+
+    #[test]
+    fn test_list() -> Result<()> {
+        let mut command_runner = MockCommandRunner::new();
+        expect_command(
+            &mut command_runner,
+            &[
+                "config",
+                "--global",
+                "--get-regexp",
+                "^coauthors-preset\\..*\\.(entry|driver)$",
+            ],
+            b"coauthors-preset.payments-mob.entry Leo Messi <leo.messi@example.com>\n\
+coauthors-preset.payments-mob.entry Diego Maradona <diego.maradona@example.com>\n\
+coauthors-preset.payments-mob.driver Emi Martinez <emi.martinez@example.com>\n\
+coauthors-preset.search.entry Emi Martinez <emi.martinez@example.com>\n"
+                .into(),
+            Some(0),
+        );
+        let preset_repo = GitConfigPresetRepo { command_runner };
+
+        let result = preset_repo.list()?;
+
+        assert_eq!(
+            result,
+            vec![
+                Preset {
+                    name: "payments-mob".to_owned(),
+                    coauthors: vec![
+                        "Leo Messi <leo.messi@example.com>".to_owned(),
+                        "Diego Maradona <diego.maradona@example.com>".to_owned(),
+                    ],
+                    driver: Some("Emi Martinez <emi.martinez@example.com>".to_owned()),
+                },
+                Preset {
+                    name: "search".to_owned(),
+                    coauthors: vec!["Emi Martinez <emi.martinez@example.com>".to_owned()],
+                    driver: None,
+                },
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_when_preset_not_found() -> Result<()> {
+        let mut command_runner = MockCommandRunner::new();
+        expect_command(
+            &mut command_runner,
+            &[
+                "config",
+                "--global",
+                "--get-regexp",
+                "^coauthors-preset\\.payments-mob\\.(entry|driver)$",
+            ],
+            vec![],
+            Some(1),
+        );
+        let preset_repo = GitConfigPresetRepo { command_runner };
+
+        assert_eq!(preset_repo.get("payments-mob")?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get() -> Result<()> {
+        let mut command_runner = MockCommandRunner::new();
+        expect_command(
+            &mut command_runner,
+            &[
+                "config",
+                "--global",
+                "--get-regexp",
+                "^coauthors-preset\\.payments-mob\\.(entry|driver)$",
+            ],
+            b"coauthors-preset.payments-mob.driver Emi Martinez <emi.martinez@example.com>\n\
+coauthors-preset.payments-mob.entry Leo Messi <leo.messi@example.com>\n"
+                .into(),
+            Some(0),
+        );
+        let preset_repo = GitConfigPresetRepo { command_runner };
+
+        assert_eq!(
+            preset_repo.get("payments-mob")?,
+            Some(Preset {
+                name: "payments-mob".to_owned(),
+                coauthors: vec!["Leo Messi <leo.messi@example.com>".to_owned()],
+                driver: Some("Emi Martinez <emi.martinez@example.com>".to_owned()),
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_save() -> Result<()> {
+        let key = "coauthors-preset.payments-mob.entry";
+        let driver_key = "coauthors-preset.payments-mob.driver";
+        let mut command_runner = MockCommandRunner::new();
+        expect_command(
+            &mut command_runner,
+            &["config", "--global", "--unset-all", key],
+            vec![],
+            Some(5),
+        );
+        expect_command(
+            &mut command_runner,
+            &["config", "--global", "--unset-all", driver_key],
+            vec![],
+            Some(5),
+        );
+        for (key, value) in [
+            (key, "Leo Messi <leo.messi@example.com>"),
+            (key, "Diego Maradona <diego.maradona@example.com>"),
+            (driver_key, "Emi Martinez <emi.martinez@example.com>"),
+        ] {
+            expect_command(
+                &mut command_runner,
+                &["config", "--global", "--add", key, value],
+                vec![],
+                Some(0),
+            );
+        }
+        let preset_repo = GitConfigPresetRepo { command_runner };
+
+        preset_repo.save(&Preset {
+            name: "payments-mob".to_owned(),
+            coauthors: vec![
+                "Leo Messi <leo.messi@example.com>".to_owned(),
+                "Diego Maradona <diego.maradona@example.com>".to_owned(),
+            ],
+            driver: Some("Emi Martinez <emi.martinez@example.com>".to_owned()),
+        })
+    }
+
+    #[test]
+    fn test_get_default() -> Result<()> {
+        let mut command_runner = MockCommandRunner::new();
+        expect_command(
+            &mut command_runner,
+            &[
+                "config",
+                "--local",
+                "--get",
+                "coauthors-mob-settings.defaultPreset",
+            ],
+            b"payments-mob\n".into(),
+            Some(0),
+        );
+        let preset_repo = GitConfigPresetRepo { command_runner };
+
+        assert_eq!(preset_repo.get_default()?, Some("payments-mob".to_owned()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_default_when_not_in_git_repo() -> Result<()> {
+        let mut command_runner = MockCommandRunner::new();
+        expect_command(
+            &mut command_runner,
+            &[
+                "config",
+                "--local",
+                "--get",
+                "coauthors-mob-settings.defaultPreset",
+            ],
+            vec![],
+            Some(128),
+        );
+        let preset_repo = GitConfigPresetRepo { command_runner };
+
+        assert_eq!(preset_repo.get_default()?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_default_when_not_in_git_repo() -> Result<()> {
+        let mut command_runner = MockCommandRunner::new();
+        expect_command(
+            &mut command_runner,
+            &[
+                "config",
+                "--local",
+                "coauthors-mob-settings.defaultPreset",
+                "payments-mob",
+            ],
+            vec![],
+            Some(128),
+        );
+        let preset_repo = GitConfigPresetRepo { command_runner };
+
+        let result = preset_repo.set_default("payments-mob");
+
+        assert!(result.is_err_and(
+            |x| x.to_string() == "Default preset can only be used inside a git repository"
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,580 @@
+use crate::Result;
+use crate::helpers::{CommandRunner, get_repo_dir};
+use crate::repositories::{TeamMember, TeamMemberRepo};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use toml_edit::{Array, DocumentMut, InlineTable, Item, TableLike, Value};
+
+/// Team member repository backed by a roster file, either committed at the root of a git repository or provisioned system-wide
+///
+/// Example roster file:
+///
+/// ```toml
+/// hidden-team-members = ["jf"]
+///
+/// [team-members]
+/// lm = { name = "Leo Messi", email = "leo.messi@example.com" }
+/// em = { name = "Emi Martinez", email = "emi.martinez@example.com" }
+///
+/// [team-member-aliases]
+/// leo = "lm"
+/// ```
+pub struct RosterFileTeamMemberRepo {
+    pub path: PathBuf,
+}
+
+impl RosterFileTeamMemberRepo {
+    pub const FILE_NAME: &'static str = ".git-mob.toml";
+    const TEAM_MEMBERS_TABLE: &'static str = "team-members";
+    const HIDDEN_TEAM_MEMBERS_ARRAY: &'static str = "hidden-team-members";
+    const ALIASES_TABLE: &'static str = "team-member-aliases";
+
+    /// Environment variable which overrides the location of the system-wide roster file
+    pub const SYSTEM_FILE_ENV_VAR: &'static str = "GIT_MOB_SYSTEM_ROSTER";
+
+    /// Finds the roster file at the root of the current git repository if it exists
+    pub fn find(command_runner: &impl CommandRunner) -> Result<Option<Self>> {
+        let Some(repo_dir) = get_repo_dir(command_runner)? else {
+            return Ok(None);
+        };
+        let path = repo_dir.join(Self::FILE_NAME);
+
+        Ok(path.is_file().then_some(Self { path }))
+    }
+
+    /// Finds the system-wide roster file provisioned for all users of the machine if it exists
+    pub fn find_system() -> Option<Self> {
+        let path = match env::var_os(Self::SYSTEM_FILE_ENV_VAR) {
+            Some(path) => PathBuf::from(path),
+            None if cfg!(windows) => PathBuf::from(env::var_os("PROGRAMDATA")?)
+                .join("git-mob")
+                .join("roster.toml"),
+            None => PathBuf::from("/etc/git-mob/roster.toml"),
+        };
+
+        path.is_file().then_some(Self { path })
+    }
+
+    fn read(&self) -> Result<DocumentMut> {
+        fs::read_to_string(&self.path)?
+            .parse::<DocumentMut>()
+            .map_err(|err| format!("Failed to parse {}: {err}", self.path.display()).into())
+    }
+
+    fn write(&self, document: &DocumentMut) -> Result<()> {
+        fs::write(&self.path, document.to_string())?;
+        Ok(())
+    }
+
+    fn team_members<'a>(&self, document: &'a DocumentMut) -> Result<Option<&'a dyn TableLike>> {
+        match document.get(Self::TEAM_MEMBERS_TABLE) {
+            None => Ok(None),
+            Some(item) => match item.as_table_like() {
+                Some(table) => Ok(Some(table)),
+                None => Err(self.invalid_error(Self::TEAM_MEMBERS_TABLE)),
+            },
+        }
+    }
+
+    fn parse_team_member(&self, key: &str, item: &Item) -> Result<TeamMember> {
+        let field = |name| item.get(name).and_then(Item::as_str);
+
+        match (field("name"), field("email")) {
+            (Some(name), Some(email)) => {
+                TeamMember::new(key, name, email).map_err(|_| self.invalid_error(key))
+            }
+            _ => Err(self.invalid_error(key)),
+        }
+    }
+
+    fn hidden_team_members<'a>(&self, document: &'a DocumentMut) -> Result<Option<&'a Array>> {
+        match document.get(Self::HIDDEN_TEAM_MEMBERS_ARRAY) {
+            None => Ok(None),
+            Some(item) => match item.as_array() {
+                Some(array) => Ok(Some(array)),
+                None => Err(self.invalid_hidden_error()),
+            },
+        }
+    }
+
+    fn invalid_hidden_error(&self) -> Box<dyn std::error::Error> {
+        format!(
+            "Invalid {} in {}. Expected an array of keys",
+            Self::HIDDEN_TEAM_MEMBERS_ARRAY,
+            self.path.display()
+        )
+        .into()
+    }
+
+    fn invalid_aliases_error(&self) -> Box<dyn std::error::Error> {
+        format!(
+            "Invalid {} in {}. Expected a table of aliases and keys",
+            Self::ALIASES_TABLE,
+            self.path.display()
+        )
+        .into()
+    }
+
+    fn invalid_error(&self, key: &str) -> Box<dyn std::error::Error> {
+        format!(
+            "Invalid team member with key {key} in {}",
+            self.path.display()
+        )
+        .into()
+    }
+}
+
+impl TeamMemberRepo for RosterFileTeamMemberRepo {
+    fn list(&self) -> Result<Vec<TeamMember>> {
+        let document = self.read()?;
+
+        match self.team_members(&document)? {
+            // An invalid team member (e.g. edited by hand) is skipped so that the others can still be used
+            Some(table) => Ok(table
+                .iter()
+                .filter_map(|(key, item)| {
+                    self.parse_team_member(key, item)
+                        .inspect_err(|_| {
+                            eprintln!(
+                                "Warning: Skipped invalid team member with key {key}. Fix or remove it in {}",
+                                self.path.display()
+                            )
+                        })
+                        .ok()
+                })
+                .collect()),
+            None => Ok(vec![]),
+        }
+    }
+
+    fn get(&self, key: &str) -> Result<Option<TeamMember>> {
+        let document = self.read()?;
+
+        match self.team_members(&document)?.and_then(|x| x.get(key)) {
+            Some(item) => Ok(Some(self.parse_team_member(key, item)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn remove(&self, key: &str) -> Result<()> {
+        let mut document = self.read()?;
+
+        if let Some(table) = document
+            .get_mut(Self::TEAM_MEMBERS_TABLE)
+            .and_then(Item::as_table_like_mut)
+        {
+            table.remove(key);
+        }
+
+        self.write(&document)
+    }
+
+    fn add(&self, team_member: &TeamMember) -> Result<()> {
+        let mut document = self.read()?;
+
+        let table = document
+            .entry(Self::TEAM_MEMBERS_TABLE)
+            .or_insert(toml_edit::table())
+            .as_table_like_mut()
+            .ok_or_else(|| self.invalid_error(Self::TEAM_MEMBERS_TABLE))?;
+
+        let mut entry = InlineTable::new();
+        entry.insert("name", team_member.name.as_str().into());
+        entry.insert("email", team_member.email.as_str().into());
+        table.insert(&team_member.key, Item::Value(Value::InlineTable(entry)));
+
+        self.write(&document)
+    }
+
+    fn list_hidden(&self) -> Result<Vec<String>> {
+        let document = self.read()?;
+
+        match self.hidden_team_members(&document)? {
+            Some(array) => array
+                .iter()
+                .map(|x| {
+                    x.as_str()
+                        .map(|x| x.to_owned())
+                        .ok_or_else(|| self.invalid_hidden_error())
+                })
+                .collect(),
+            None => Ok(vec![]),
+        }
+    }
+
+    fn hide(&self, key: &str) -> Result<()> {
+        let mut document = self.read()?;
+
+        let array = document
+            .entry(Self::HIDDEN_TEAM_MEMBERS_ARRAY)
+            .or_insert(toml_edit::value(Array::new()))
+            .as_array_mut()
+            .ok_or_else(|| self.invalid_hidden_error())?;
+
+        if !array.iter().any(|x| x.as_str() == Some(key)) {
+            array.push(key);
+        }
+
+        self.write(&document)
+    }
+
+    fn list_aliases(&self) -> Result<Vec<(String, String)>> {
+        let document = self.read()?;
+
+        let Some(item) = document.get(Self::ALIASES_TABLE) else {
+            return Ok(vec![]);
+        };
+        item.as_table_like()
+            .ok_or_else(|| self.invalid_aliases_error())?
+            .iter()
+            .map(|(alias, key)| {
+                key.as_str()
+                    .map(|key| (alias.to_owned(), key.to_owned()))
+                    .ok_or_else(|| self.invalid_aliases_error())
+            })
+            .collect()
+    }
+
+    fn add_alias(&self, alias: &str, key: &str) -> Result<()> {
+        let mut document = self.read()?;
+
+        document
+            .entry(Self::ALIASES_TABLE)
+            .or_insert(toml_edit::table())
+            .as_table_like_mut()
+            .ok_or_else(|| self.invalid_aliases_error())?
+            .insert(alias, toml_edit::value(key));
+
+        self.write(&document)
+    }
+
+    fn remove_alias(&self, alias: &str) -> Result<()> {
+        let mut document = self.read()?;
+
+        if let Some(table) = document
+            .get_mut(Self::ALIASES_TABLE)
+            .and_then(Item::as_table_like_mut)
+        {
+            table.remove(alias);
+        }
+
+        self.write(&document)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{CmdOutput, MockCommandRunner};
+    use tempfile::{TempDir, tempdir};
+
+    const ROSTER: &str = r#"# Shared roster of the team
+[team-members]
+lm = { name = "Leo Messi", email = "leo.messi@example.com" }
+
+[team-members.em]
+name = "Emi Martinez"
+email = "emi.martinez@example.com"
+"#;
+
+    fn create_roster_file(content: &str) -> Result<(TempDir, RosterFileTeamMemberRepo)> {
+        let dir = tempdir()?;
+        let path = dir.path().join(RosterFileTeamMemberRepo::FILE_NAME);
+        fs::write(&path, content)?;
+        Ok((dir, RosterFileTeamMemberRepo { path }))
+    }
+
+    fn create_mock_command_runner(stdout: Vec<u8>, status_code: Option<i32>) -> MockCommandRunner {
+        let mut mock_cmd_runner = MockCommandRunner::new();
+        mock_cmd_runner
+            .expect_execute()
+            .once()
+            .withf(|program, args| program == "git" && args == ["rev-parse", "--show-toplevel"])
+            .returning(move |_, _| {
+                Ok(CmdOutput {
+                    stdout: stdout.clone(),
+                    stderr: vec![],
+                    status_code,
+                })
+            });
+        mock_cmd_runner
+    }
+
+    #[test]
+    fn test_find() -> Result<()> {
+        let (dir, roster_repo) = create_roster_file(ROSTER)?;
+        let stdout = format!("{}\n", dir.path().display()).into_bytes();
+        let command_runner = create_mock_command_runner(stdout, Some(0));
+
+        let result = RosterFileTeamMemberRepo::find(&command_runner)?;
+
+        assert_eq!(result.map(|x| x.path), Some(roster_repo.path));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_when_roster_file_missing() -> Result<()> {
+        let dir = tempdir()?;
+        let stdout = format!("{}\n", dir.path().display()).into_bytes();
+        let command_runner = create_mock_command_runner(stdout, Some(0));
+
+        let result = RosterFileTeamMemberRepo::find(&command_runner)?;
+
+        assert!(result.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_when_not_inside_git_repository() -> Result<()> {
+        let command_runner = create_mock_command_runner(vec![], Some(128));
+
+        let result = RosterFileTeamMemberRepo::find(&command_runner)?;
+
+        assert!(result.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list() -> Result<()> {
+        let (_dir, roster_repo) = create_roster_file(ROSTER)?;
+
+        let result = roster_repo.list()?;
+
+        assert_eq!(
+            result,
+            vec![
+                TeamMember::new("lm", "Leo Messi", "leo.messi@example.com")?,
+                TeamMember::new("em", "Emi Martinez", "emi.martinez@example.com")?,
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_when_no_team_members() -> Result<()> {
+        let (_dir, roster_repo) = create_roster_file("")?;
+
+        let result = roster_repo.list()?;
+
+        assert_eq!(result, vec![]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_when_invalid_team_member() -> Result<()> {
+        let (_dir, roster_repo) = create_roster_file(
+            "[team-members]\nlm = { name = \"Leo Messi\" }\nem = { name = \"Emi Martinez\", email = \"emi.martinez@example.com\" }\n",
+        )?;
+
+        let result = roster_repo.list()?;
+
+        assert_eq!(
+            result,
+            vec![TeamMember::new(
+                "em",
+                "Emi Martinez",
+                "emi.martinez@example.com"
+            )?]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_when_invalid_team_member() -> Result<()> {
+        let (_dir, roster_repo) =
+            create_roster_file("[team-members]\nlm = { name = \"Leo Messi\" }\n")?;
+
+        let result = roster_repo.get("lm");
+
+        assert!(result.is_err_and(|x| x.to_string()
+            == format!(
+                "Invalid team member with key lm in {}",
+                roster_repo.path.display()
+            )));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_when_invalid_toml() -> Result<()> {
+        let (_dir, roster_repo) = create_roster_file("[team-members\n")?;
+
+        let result = roster_repo.list();
+
+        assert!(result.is_err_and(|x| {
+            x.to_string()
+                .starts_with(&format!("Failed to parse {}", roster_repo.path.display()))
+        }));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get() -> Result<()> {
+        let (_dir, roster_repo) = create_roster_file(ROSTER)?;
+
+        assert_eq!(
+            roster_repo.get("em")?,
+            Some(TeamMember::new(
+                "em",
+                "Emi Martinez",
+                "emi.martinez@example.com"
+            )?)
+        );
+        assert_eq!(roster_repo.get("rdp")?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove() -> Result<()> {
+        let (_dir, roster_repo) = create_roster_file(ROSTER)?;
+
+        roster_repo.remove("lm")?;
+
+        assert_eq!(
+            fs::read_to_string(&roster_repo.path)?,
+            r#"# Shared roster of the team
+[team-members]
+
+[team-members.em]
+name = "Emi Martinez"
+email = "emi.martinez@example.com"
+"#
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_add() -> Result<()> {
+        let (_dir, roster_repo) = create_roster_file(
+            "[team-members]\nlm = { name = \"Leo Messi\", email = \"leo.messi@example.com\" }\n",
+        )?;
+
+        roster_repo.add(&TeamMember::new(
+            "em",
+            "Emi Martinez",
+            "emi.martinez@example.com",
+        )?)?;
+
+        assert_eq!(
+            fs::read_to_string(&roster_repo.path)?,
+            r#"[team-members]
+lm = { name = "Leo Messi", email = "leo.messi@example.com" }
+em = { name = "Emi Martinez", email = "emi.martinez@example.com" }
+"#
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_when_no_team_members() -> Result<()> {
+        let (_dir, roster_repo) = create_roster_file("")?;
+
+        roster_repo.add(&TeamMember::new(
+            "lm",
+            "Leo Messi",
+            "leo.messi@example.com",
+        )?)?;
+
+        assert_eq!(
+            roster_repo.list()?,
+            vec![TeamMember::new("lm", "Leo Messi", "leo.messi@example.com")?]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_hidden() -> Result<()> {
+        let (_dir, roster_repo) = create_roster_file("hidden-team-members = [\"lm\", \"em\"]\n")?;
+
+        let result = roster_repo.list_hidden()?;
+
+        assert_eq!(result, vec!["lm", "em"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_hidden_when_invalid() -> Result<()> {
+        let (_dir, roster_repo) = create_roster_file("hidden-team-members = \"lm\"\n")?;
+
+        let result = roster_repo.list_hidden();
+
+        assert!(result.is_err_and(|x| x.to_string()
+            == format!(
+                "Invalid hidden-team-members in {}. Expected an array of keys",
+                roster_repo.path.display()
+            )));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_hide() -> Result<()> {
+        let (_dir, roster_repo) = create_roster_file(ROSTER)?;
+
+        roster_repo.hide("jf")?;
+        roster_repo.hide("jf")?;
+
+        assert_eq!(roster_repo.list_hidden()?, vec!["jf"]);
+        assert_eq!(roster_repo.list()?.len(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_and_remove_alias() -> Result<()> {
+        let (_dir, roster_repo) = create_roster_file(
+            "[team-members]\nlm = { name = \"Leo Messi\", email = \"leo.messi@example.com\" }\n",
+        )?;
+
+        roster_repo.add_alias("leo", "lm")?;
+        roster_repo.add_alias("messi", "lm")?;
+
+        assert_eq!(
+            fs::read_to_string(&roster_repo.path)?,
+            r#"[team-members]
+lm = { name = "Leo Messi", email = "leo.messi@example.com" }
+
+[team-member-aliases]
+leo = "lm"
+messi = "lm"
+"#
+        );
+
+        roster_repo.remove_alias("leo")?;
+
+        assert_eq!(
+            roster_repo.list_aliases()?,
+            vec![("messi".to_owned(), "lm".to_owned())]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_aliases_when_invalid() -> Result<()> {
+        let (_dir, roster_repo) = create_roster_file("[team-member-aliases]\nleo = 1\n")?;
+
+        let result = roster_repo.list_aliases();
+
+        assert!(result.is_err_and(|x| x.to_string()
+            == format!(
+                "Invalid team-member-aliases in {}. Expected a table of aliases and keys",
+                roster_repo.path.display()
+            )));
+
+        Ok(())
+    }
+}
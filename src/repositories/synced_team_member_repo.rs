@@ -0,0 +1,153 @@
+use crate::Result;
+use crate::repositories::{TeamMember, TeamMemberRepo};
+
+/// Team member repository which keeps a mirror repository in sync with the primary one
+///
+/// Changes are written to both, but team members are only read from the primary one as the
+/// sync is one way. Team members which are only in the mirror are read through a further layer
+/// of the roster instead.
+pub struct SyncedTeamMemberRepo<Primary, Mirror> {
+    pub primary: Primary,
+    pub mirror: Mirror,
+}
+
+impl<Primary: TeamMemberRepo, Mirror: TeamMemberRepo> TeamMemberRepo
+    for SyncedTeamMemberRepo<Primary, Mirror>
+{
+    fn list(&self) -> Result<Vec<TeamMember>> {
+        self.primary.list()
+    }
+
+    fn get(&self, key: &str) -> Result<Option<TeamMember>> {
+        self.primary.get(key)
+    }
+
+    fn remove(&self, key: &str) -> Result<()> {
+        if self.primary.get(key)?.is_some() {
+            self.primary.remove(key)?;
+        }
+        if self.mirror.get(key)?.is_some() {
+            self.mirror.remove(key)?;
+        }
+
+        Ok(())
+    }
+
+    fn add(&self, team_member: &TeamMember) -> Result<()> {
+        self.primary.add(team_member)?;
+        self.mirror.add(team_member)
+    }
+
+    fn list_hidden(&self) -> Result<Vec<String>> {
+        self.primary.list_hidden()
+    }
+
+    fn hide(&self, key: &str) -> Result<()> {
+        self.primary.hide(key)
+    }
+
+    fn list_aliases(&self) -> Result<Vec<(String, String)>> {
+        self.primary.list_aliases()
+    }
+
+    fn add_alias(&self, alias: &str, key: &str) -> Result<()> {
+        self.primary.add_alias(alias, key)
+    }
+
+    fn remove_alias(&self, alias: &str) -> Result<()> {
+        self.primary.remove_alias(alias)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::MockTeamMemberRepo;
+    use mockall::predicate;
+
+    fn create_mock_repo(team_members: Vec<TeamMember>) -> MockTeamMemberRepo {
+        let mut mock_team_member_repo = MockTeamMemberRepo::new();
+        let cloned_team_members = team_members.clone();
+        mock_team_member_repo
+            .expect_list()
+            .returning(move || Ok(cloned_team_members.clone()));
+        mock_team_member_repo
+            .expect_get()
+            .returning(move |key| Ok(team_members.iter().find(|x| x.key == key).cloned()));
+        mock_team_member_repo
+    }
+
+    #[test]
+    fn test_list() -> Result<()> {
+        let synced_repo = SyncedTeamMemberRepo {
+            primary: create_mock_repo(vec![TeamMember::new(
+                "lm",
+                "Lionel Messi",
+                "lionel.messi@example.com",
+            )?]),
+            mirror: create_mock_repo(vec![
+                TeamMember::new("lm", "Leo Messi", "leo.messi@example.com")?,
+                TeamMember::new("em", "Emi Martinez", "emi.martinez@example.com")?,
+            ]),
+        };
+
+        // team members only in the mirror are not read through the synced repo
+        assert_eq!(
+            synced_repo.list()?,
+            vec![TeamMember::new(
+                "lm",
+                "Lionel Messi",
+                "lionel.messi@example.com"
+            )?]
+        );
+        assert_eq!(synced_repo.get("em")?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_add() -> Result<()> {
+        let team_member = TeamMember::new("lm", "Leo Messi", "leo.messi@example.com")?;
+
+        let mut primary = MockTeamMemberRepo::new();
+        primary
+            .expect_add()
+            .with(predicate::eq(team_member.clone()))
+            .once()
+            .returning(|_| Ok(()));
+        let mut mirror = MockTeamMemberRepo::new();
+        mirror
+            .expect_add()
+            .with(predicate::eq(team_member.clone()))
+            .once()
+            .returning(|_| Ok(()));
+
+        let synced_repo = SyncedTeamMemberRepo { primary, mirror };
+
+        synced_repo.add(&team_member)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove() -> Result<()> {
+        let mut primary = create_mock_repo(vec![]);
+        primary.expect_remove().never();
+        let mut mirror = create_mock_repo(vec![TeamMember::new(
+            "lm",
+            "Leo Messi",
+            "leo.messi@example.com",
+        )?]);
+        mirror
+            .expect_remove()
+            .with(predicate::eq("lm"))
+            .once()
+            .returning(|_| Ok(()));
+
+        let synced_repo = SyncedTeamMemberRepo { primary, mirror };
+
+        synced_repo.remove("lm")?;
+
+        Ok(())
+    }
+}
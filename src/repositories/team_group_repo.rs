@@ -0,0 +1,275 @@
+use crate::Result;
+use crate::helpers::{CmdOutput, CommandRunner};
+use crate::validation;
+use std::fmt;
+
+/// Named group of team members which can be used as `@name` instead of their keys
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TeamGroup {
+    pub name: String,
+    pub keys: Vec<String>,
+}
+
+impl TeamGroup {
+    pub fn new(name: &str, keys: &[String]) -> Result<Self> {
+        if !validation::is_identifier(name) {
+            return Err(format!(
+                "Invalid team group name: {name}. It must start with a letter and only contain letters, digits and hyphens"
+            )
+            .into());
+        }
+        if keys.is_empty() {
+            return Err(format!("Team group {name} must have at least one team member").into());
+        }
+
+        Ok(Self {
+            name: name.to_owned(),
+            keys: keys.to_vec(),
+        })
+    }
+}
+
+impl fmt::Display for TeamGroup {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "@{} ({})", self.name, self.keys.join(", "))
+    }
+}
+
+#[cfg(test)]
+use mockall::automock;
+#[cfg_attr(test, automock)]
+pub trait TeamGroupRepo {
+    fn list(&self) -> Result<Vec<TeamGroup>>;
+    fn get(&self, name: &str) -> Result<Option<TeamGroup>>;
+    fn add(&self, team_group: &TeamGroup) -> Result<()>;
+    fn remove(&self, name: &str) -> Result<()>;
+}
+
+/// Team group repository which stores groups in the user's global git config next to the team members
+pub struct GitConfigTeamGroupRepo<Cmd> {
+    pub command_runner: Cmd,
+}
+
+impl<Cmd: CommandRunner> GitConfigTeamGroupRepo<Cmd> {
+    const GROUP_SECTION: &'static str = "coauthors-group";
+
+    const EXIT_CODE_SUCCESS: i32 = 0;
+    const EXIT_CODE_CONFIG_INVALID_KEY: i32 = 1;
+
+    fn parse(name: &str, value: &str) -> TeamGroup {
+        TeamGroup {
+            name: name.to_owned(),
+            keys: value.split_whitespace().map(|x| x.to_owned()).collect(),
+        }
+    }
+
+    fn git_config_error<T>(output: &CmdOutput) -> Result<T> {
+        match output.status_code {
+            Some(code) => Err(format!("Git config command exited with status code: {code}").into()),
+            None => Err("Git config command terminated by signal".into()),
+        }
+    }
+}
+
+impl<Cmd: CommandRunner> TeamGroupRepo for GitConfigTeamGroupRepo<Cmd> {
+    fn list(&self) -> Result<Vec<TeamGroup>> {
+        let section = Self::GROUP_SECTION;
+        let search_regex = format!("^{section}\\.");
+
+        let output = self.command_runner.execute(
+            "git",
+            &["config", "--global", "--get-regexp", &search_regex],
+        )?;
+
+        match output.status_code {
+            Some(Self::EXIT_CODE_SUCCESS) => Ok(String::from_utf8(output.stdout)?
+                .lines()
+                .map(|x| {
+                    let entry = x.strip_prefix(&format!("{section}.")).unwrap_or(x);
+                    let (name, value) = entry.split_once(' ').unwrap_or((entry, ""));
+                    Self::parse(name, value)
+                })
+                .collect()),
+            Some(Self::EXIT_CODE_CONFIG_INVALID_KEY) => Ok(vec![]),
+            _ => Self::git_config_error(&output),
+        }
+    }
+
+    fn get(&self, name: &str) -> Result<Option<TeamGroup>> {
+        let full_key = format!("{}.{name}", Self::GROUP_SECTION);
+
+        let output = self
+            .command_runner
+            .execute("git", &["config", "--global", &full_key])?;
+
+        match output.status_code {
+            Some(Self::EXIT_CODE_SUCCESS) => {
+                Ok(Some(Self::parse(name, &String::from_utf8(output.stdout)?)))
+            }
+            Some(Self::EXIT_CODE_CONFIG_INVALID_KEY) => Ok(None),
+            _ => Self::git_config_error(&output),
+        }
+    }
+
+    fn add(&self, team_group: &TeamGroup) -> Result<()> {
+        let name = &team_group.name;
+        let full_key = format!("{}.{name}", Self::GROUP_SECTION);
+
+        let output = self.command_runner.execute(
+            "git",
+            &["config", "--global", &full_key, &team_group.keys.join(" ")],
+        )?;
+
+        match output.status_code {
+            Some(Self::EXIT_CODE_SUCCESS) => Ok(()),
+            Some(Self::EXIT_CODE_CONFIG_INVALID_KEY) => {
+                Err(format!("Invalid team group name: {name}").into())
+            }
+            _ => Self::git_config_error(&output),
+        }
+    }
+
+    fn remove(&self, name: &str) -> Result<()> {
+        let full_key = format!("{}.{name}", Self::GROUP_SECTION);
+
+        let output = self
+            .command_runner
+            .execute("git", &["config", "--global", "--unset-all", &full_key])?;
+
+        match output.status_code {
+            Some(Self::EXIT_CODE_SUCCESS) => Ok(()),
+            _ => Self::git_config_error(&output),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::MockCommandRunner;
+
+    fn create_mock_command_runner(
+        args: &[&str],
+        stdout: Vec<u8>,
+        status_code: Option<i32>,
+    ) -> MockCommandRunner {
+        let cloned_args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+
+        let mut mock_cmd_runner = MockCommandRunner::new();
+        mock_cmd_runner
+            .expect_execute()
+            .once()
+            .withf(move |program, args| program == "git" && args == cloned_args)
+            .returning(move |_, _| {
+                Ok(CmdOutput {
+                    stdout: stdout.clone(),
+                    stderr: vec![],
+                    status_code,
+                })
+            });
+        mock_cmd_runner
+    }
+
+    #[test]
+    fn test_new_given_invalid_name() {
+        let result = TeamGroup::new("front end", &["lm".to_owned()]);
+
+        assert!(result.is_err_and(|x| x.to_string().starts_with("Invalid team group name")));
+    }
+
+    #[test]
+    fn test_new_given_no_keys() {
+        let result = TeamGroup::new("frontend", &[]);
+
+        assert!(result.is_err_and(
+            |x| x.to_string() == "Team group frontend must have at least one team member"
+        ));
+    }
+
+    #[test]
+    fn test_list() -> Result<()> {
+        let args = &["config", "--global", "--get-regexp", "^coauthors-group\\."];
+        let stdout = b"coauthors-group.frontend lm em sa\ncoauthors-group.backend jh\n".into();
+        let team_group_repo = GitConfigTeamGroupRepo {
+            command_runner: create_mock_command_runner(args, stdout, Some(0)),
+        };
+
+        let result = team_group_repo.list()?;
+
+        assert_eq!(
+            result,
+            vec![
+                TeamGroup::new("frontend", &["lm".into(), "em".into(), "sa".into()])?,
+                TeamGroup::new("backend", &["jh".into()])?,
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_when_no_groups() -> Result<()> {
+        let args = &["config", "--global", "--get-regexp", "^coauthors-group\\."];
+        let team_group_repo = GitConfigTeamGroupRepo {
+            command_runner: create_mock_command_runner(args, vec![], Some(1)),
+        };
+
+        assert!(team_group_repo.list()?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get() -> Result<()> {
+        let args = &["config", "--global", "coauthors-group.frontend"];
+        let team_group_repo = GitConfigTeamGroupRepo {
+            command_runner: create_mock_command_runner(args, b"lm em\n".into(), Some(0)),
+        };
+
+        let result = team_group_repo.get("frontend")?;
+
+        assert_eq!(
+            result,
+            Some(TeamGroup::new("frontend", &["lm".into(), "em".into()])?)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_when_group_not_found() -> Result<()> {
+        let args = &["config", "--global", "coauthors-group.frontend"];
+        let team_group_repo = GitConfigTeamGroupRepo {
+            command_runner: create_mock_command_runner(args, vec![], Some(1)),
+        };
+
+        assert_eq!(team_group_repo.get("frontend")?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_add() -> Result<()> {
+        let args = &["config", "--global", "coauthors-group.frontend", "lm em"];
+        let team_group_repo = GitConfigTeamGroupRepo {
+            command_runner: create_mock_command_runner(args, vec![], Some(0)),
+        };
+
+        team_group_repo.add(&TeamGroup::new("frontend", &["lm".into(), "em".into()])?)
+    }
+
+    #[test]
+    fn test_remove() -> Result<()> {
+        let args = &[
+            "config",
+            "--global",
+            "--unset-all",
+            "coauthors-group.frontend",
+        ];
+        let team_group_repo = GitConfigTeamGroupRepo {
+            command_runner: create_mock_command_runner(args, vec![], Some(0)),
+        };
+
+        team_group_repo.remove("frontend")
+    }
+}
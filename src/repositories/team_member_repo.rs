@@ -1,14 +1,121 @@
 use crate::Result;
 use crate::helpers::{CmdOutput, CommandRunner};
+use crate::validation;
+use std::fmt;
+
+/// Team member who can be added as a co-author to the mob/pair programming session
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TeamMember {
+    pub key: String,
+    pub name: String,
+    pub email: String,
+    /// Layer of the roster which the team member is resolved from, if known
+    pub source: Option<TeamMemberSource>,
+}
+
+impl TeamMember {
+    /// Creates team member with normalized key, name and email, rejecting values which would
+    /// corrupt the roster or Co-authored-by trailers
+    pub fn new(key: &str, name: &str, email: &str) -> Result<Self> {
+        Ok(TeamMember {
+            key: validation::normalize_key(key)?,
+            name: validation::normalize_name(name)?,
+            email: validation::normalize_email(email)?,
+            source: None,
+        })
+    }
+
+    /// Rejects team member whose key, name or email is valid but unusual (e.g. email without a domain name)
+    pub fn check_usual(&self) -> Result<()> {
+        validation::check_usual_key(&self.key)?;
+        validation::check_usual_name(&self.name)?;
+        validation::check_usual_email(&self.email)
+    }
+
+    /// Parses team member from value formatted as "Name <email>"
+    pub fn parse(key: &str, value: &str) -> Result<Self> {
+        let invalid_error = || format!("Invalid team member with key {key}: {value}");
+
+        let (name, email) = value
+            .trim()
+            .strip_suffix('>')
+            .and_then(|x| x.rsplit_once('<'))
+            .ok_or_else(invalid_error)?;
+
+        TeamMember::new(key, name, email)
+            .map_err(|err| format!("Invalid team member with key {key}: {err}").into())
+    }
+
+    pub fn with_source(self, source: TeamMemberSource) -> Self {
+        TeamMember {
+            source: Some(source),
+            ..self
+        }
+    }
+}
+
+/// Formats team member as "Name <email>" which is used in Co-authored-by trailers
+impl fmt::Display for TeamMember {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} <{}>", self.name, self.email)
+    }
+}
+
+/// Layer of the roster, ordered from the closest to the furthest
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TeamMemberSource {
+    /// Roster file committed in the current repository
+    Repo,
+    /// Global git config of the user
+    User,
+    /// `~/.git-coauthors` file of the JavaScript git-mob tool, which is only changed through the user layer
+    CoauthorsFile,
+    /// System-wide roster file
+    System,
+}
+
+impl fmt::Display for TeamMemberSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TeamMemberSource::Repo => write!(f, "repo"),
+            TeamMemberSource::User => write!(f, "user"),
+            TeamMemberSource::CoauthorsFile => write!(f, "git-coauthors"),
+            TeamMemberSource::System => write!(f, "system"),
+        }
+    }
+}
 
 #[cfg(test)]
 use mockall::{automock, predicate::*};
 #[cfg_attr(test, automock)]
 pub trait TeamMemberRepo {
-    fn list(&self, show_keys: bool) -> Result<Vec<String>>;
-    fn get(&self, key: &str) -> Result<Option<String>>;
+    fn list(&self) -> Result<Vec<TeamMember>>;
+    fn get(&self, key: &str) -> Result<Option<TeamMember>>;
     fn remove(&self, key: &str) -> Result<()>;
-    fn add(&self, key: &str, team_member: &str) -> Result<()>;
+    fn add(&self, team_member: &TeamMember) -> Result<()>;
+    /// Replaces the team member with the key by the given one, whose key may differ. The team
+    /// member is added before the old key is removed so that a failure loses nothing.
+    fn replace(&self, key: &str, team_member: &TeamMember) -> Result<()> {
+        self.add(team_member)?;
+        if team_member.key != key {
+            self.remove(key)?;
+        }
+
+        Ok(())
+    }
+    /// Lists keys of team members hidden from further layers of the roster
+    fn list_hidden(&self) -> Result<Vec<String>>;
+    fn hide(&self, key: &str) -> Result<()>;
+    /// Lists aliases with the keys of the team members they resolve to
+    fn list_aliases(&self) -> Result<Vec<(String, String)>>;
+    /// Adds alias resolving to the team member with the key, replacing any existing alias with the same name
+    fn add_alias(&self, alias: &str, key: &str) -> Result<()>;
+    fn remove_alias(&self, alias: &str) -> Result<()>;
+    /// Writes changes to the given layer of the roster, which fails if there is no such layer.
+    /// A repository which is a single layer always writes to itself.
+    fn write_to(&self, _source: TeamMemberSource) -> Result<()> {
+        Ok(())
+    }
 }
 
 pub struct GitConfigTeamMemberRepo<Cmd> {
@@ -18,9 +125,12 @@ pub struct GitConfigTeamMemberRepo<Cmd> {
 impl<Cmd: CommandRunner> GitConfigTeamMemberRepo<Cmd> {
     // Keeping it as coauthors instead of team-members for backwards compatibility
     const COAUTHORS_SECTION: &'static str = "coauthors";
+    const HIDDEN_COAUTHOR_KEY: &'static str = "coauthors-hidden.entry";
+    const ALIAS_SECTION: &'static str = "coauthors-alias";
 
     const EXIT_CODE_SUCCESS: i32 = 0;
     const EXIT_CODE_CONFIG_INVALID_KEY: i32 = 1;
+    const EXIT_CODE_CONFIG_KEY_NOT_SET: i32 = 5;
 
     fn git_config_error<T>(output: &CmdOutput) -> Result<T> {
         match output.status_code {
@@ -31,7 +141,7 @@ impl<Cmd: CommandRunner> GitConfigTeamMemberRepo<Cmd> {
 }
 
 impl<Cmd: CommandRunner> TeamMemberRepo for GitConfigTeamMemberRepo<Cmd> {
-    fn list(&self, show_keys: bool) -> Result<Vec<String>> {
+    fn list(&self) -> Result<Vec<TeamMember>> {
         let section = Self::COAUTHORS_SECTION;
         let search_regex = format!("^{section}\\.");
 
@@ -41,26 +151,26 @@ impl<Cmd: CommandRunner> TeamMemberRepo for GitConfigTeamMemberRepo<Cmd> {
         )?;
 
         match output.status_code {
-            Some(Self::EXIT_CODE_SUCCESS) => String::from_utf8(output.stdout)?
+            // An invalid team member (e.g. edited by hand) is skipped so that the others can still be used
+            Some(Self::EXIT_CODE_SUCCESS) => Ok(String::from_utf8(output.stdout)?
                 .lines()
-                .map(|x| {
-                    let delimiter = if show_keys {
-                        format!("{section}.")
-                    } else {
-                        " ".to_owned()
-                    };
-                    x.split_once(&delimiter)
-                        .ok_or(format!("Failed to split string: '{x}'").into())
-                        .map(|(_, team_member)| team_member.to_owned())
+                .filter_map(|x| {
+                    let entry = x.strip_prefix(&format!("{section}.")).unwrap_or(x);
+                    let (key, value) = entry.split_once(' ').unwrap_or((entry, ""));
+                    TeamMember::parse(key, value)
+                        .inspect_err(|err| {
+                            eprintln!("Warning: Skipped {err}. Fix or remove it with git config --global {section}.{key}")
+                        })
+                        .ok()
                 })
-                .collect(),
+                .collect()),
 
             Some(Self::EXIT_CODE_CONFIG_INVALID_KEY) => Ok(vec![]),
             _ => Self::git_config_error(&output),
         }
     }
 
-    fn get(&self, key: &str) -> Result<Option<String>> {
+    fn get(&self, key: &str) -> Result<Option<TeamMember>> {
         let full_key = format!("{}.{key}", Self::COAUTHORS_SECTION);
 
         let output = self
@@ -68,9 +178,10 @@ impl<Cmd: CommandRunner> TeamMemberRepo for GitConfigTeamMemberRepo<Cmd> {
             .execute("git", &["config", "--global", &full_key])?;
 
         match output.status_code {
-            Some(Self::EXIT_CODE_SUCCESS) => {
-                Ok(Some(String::from_utf8(output.stdout)?.trim().into()))
-            }
+            Some(Self::EXIT_CODE_SUCCESS) => Ok(Some(TeamMember::parse(
+                key,
+                &String::from_utf8(output.stdout)?,
+            )?)),
             Some(Self::EXIT_CODE_CONFIG_INVALID_KEY) => Ok(None),
             _ => Self::git_config_error(&output),
         }
@@ -89,16 +200,104 @@ impl<Cmd: CommandRunner> TeamMemberRepo for GitConfigTeamMemberRepo<Cmd> {
         }
     }
 
-    fn add(&self, key: &str, team_member: &str) -> Result<()> {
+    fn add(&self, team_member: &TeamMember) -> Result<()> {
+        let key = &team_member.key;
         let full_key = format!("{}.{key}", Self::COAUTHORS_SECTION);
 
+        let output = self.command_runner.execute(
+            "git",
+            &["config", "--global", &full_key, &team_member.to_string()],
+        )?;
+
+        match output.status_code {
+            Some(Self::EXIT_CODE_SUCCESS) => Ok(()),
+            Some(Self::EXIT_CODE_CONFIG_INVALID_KEY) => Err(format!("Invalid key: {key}").into()),
+            _ => Self::git_config_error(&output),
+        }
+    }
+
+    fn list_hidden(&self) -> Result<Vec<String>> {
+        let output = self.command_runner.execute(
+            "git",
+            &["config", "--global", "--get-all", Self::HIDDEN_COAUTHOR_KEY],
+        )?;
+
+        match output.status_code {
+            Some(Self::EXIT_CODE_SUCCESS) => Ok(String::from_utf8(output.stdout)?
+                .lines()
+                .map(|x| x.to_owned())
+                .collect()),
+            Some(Self::EXIT_CODE_CONFIG_INVALID_KEY) => Ok(vec![]),
+            _ => Self::git_config_error(&output),
+        }
+    }
+
+    fn hide(&self, key: &str) -> Result<()> {
+        let output = self.command_runner.execute(
+            "git",
+            &[
+                "config",
+                "--global",
+                "--add",
+                Self::HIDDEN_COAUTHOR_KEY,
+                key,
+            ],
+        )?;
+
+        match output.status_code {
+            Some(Self::EXIT_CODE_SUCCESS) => Ok(()),
+            _ => Self::git_config_error(&output),
+        }
+    }
+
+    fn list_aliases(&self) -> Result<Vec<(String, String)>> {
+        let section = Self::ALIAS_SECTION;
+        let search_regex = format!("^{section}\\.");
+
+        let output = self.command_runner.execute(
+            "git",
+            &["config", "--global", "--get-regexp", &search_regex],
+        )?;
+
+        match output.status_code {
+            Some(Self::EXIT_CODE_SUCCESS) => Ok(String::from_utf8(output.stdout)?
+                .lines()
+                .map(|x| {
+                    let entry = x.strip_prefix(&format!("{section}.")).unwrap_or(x);
+                    let (alias, key) = entry.split_once(' ').unwrap_or((entry, ""));
+                    (alias.to_owned(), key.trim().to_owned())
+                })
+                .collect()),
+            Some(Self::EXIT_CODE_CONFIG_INVALID_KEY) => Ok(vec![]),
+            _ => Self::git_config_error(&output),
+        }
+    }
+
+    fn add_alias(&self, alias: &str, key: &str) -> Result<()> {
+        let full_key = format!("{}.{alias}", Self::ALIAS_SECTION);
+
         let output = self
             .command_runner
-            .execute("git", &["config", "--global", &full_key, team_member])?;
+            .execute("git", &["config", "--global", &full_key, key])?;
 
         match output.status_code {
             Some(Self::EXIT_CODE_SUCCESS) => Ok(()),
-            Some(Self::EXIT_CODE_CONFIG_INVALID_KEY) => Err(format!("Invalid key: {key}").into()),
+            Some(Self::EXIT_CODE_CONFIG_INVALID_KEY) => {
+                Err(format!("Invalid alias: {alias}").into())
+            }
+            _ => Self::git_config_error(&output),
+        }
+    }
+
+    fn remove_alias(&self, alias: &str) -> Result<()> {
+        let full_key = format!("{}.{alias}", Self::ALIAS_SECTION);
+
+        let output = self
+            .command_runner
+            .execute("git", &["config", "--global", "--unset-all", &full_key])?;
+
+        match output.status_code {
+            Some(Self::EXIT_CODE_SUCCESS | Self::EXIT_CODE_CONFIG_KEY_NOT_SET) => Ok(()),
             _ => Self::git_config_error(&output),
         }
     }
@@ -137,7 +336,7 @@ mod tests {
     }
 
     #[test]
-    fn test_list_without_keys() -> Result<()> {
+    fn test_list() -> Result<()> {
         let args = &["config", "--global", "--get-regexp", "^coauthors\\."];
         let stdout = b"coauthors.lm Leo Messi <leo.messi@example.com>\ncoauthors.em Emi Martinez <emi.martinez@example.com>\n".into();
         let stderr = vec![];
@@ -145,13 +344,13 @@ mod tests {
         let command_runner = create_mock_command_runner("git", args, stdout, stderr, status_code);
         let team_member_repo = GitConfigTeamMemberRepo { command_runner };
 
-        let result = team_member_repo.list(false)?;
+        let result = team_member_repo.list()?;
 
         assert_eq!(
             result,
             vec![
-                "Leo Messi <leo.messi@example.com>",
-                "Emi Martinez <emi.martinez@example.com>"
+                TeamMember::new("lm", "Leo Messi", "leo.messi@example.com")?,
+                TeamMember::new("em", "Emi Martinez", "emi.martinez@example.com")?,
             ]
         );
 
@@ -159,22 +358,20 @@ mod tests {
     }
 
     #[test]
-    fn test_list_with_keys() -> Result<()> {
+    fn test_list_when_invalid_team_member() -> Result<()> {
         let args = &["config", "--global", "--get-regexp", "^coauthors\\."];
-        let stdout = b"coauthors.lm Leo Messi <leo.messi@example.com>\ncoauthors.em Emi Martinez <emi.martinez@example.com>\n".into();
+        let stdout =
+            b"coauthors.lm Leo Messi <leo.messi@example.com>\ncoauthors.em Emi Martinez\n".into();
         let stderr = vec![];
         let status_code = Some(0);
         let command_runner = create_mock_command_runner("git", args, stdout, stderr, status_code);
         let team_member_repo = GitConfigTeamMemberRepo { command_runner };
 
-        let result = team_member_repo.list(true)?;
+        let result = team_member_repo.list()?;
 
         assert_eq!(
             result,
-            vec![
-                "lm Leo Messi <leo.messi@example.com>",
-                "em Emi Martinez <emi.martinez@example.com>"
-            ]
+            vec![TeamMember::new("lm", "Leo Messi", "leo.messi@example.com")?]
         );
 
         Ok(())
@@ -189,9 +386,9 @@ mod tests {
         let command_runner = create_mock_command_runner("git", args, stdout, stderr, status_code);
         let team_member_repo = GitConfigTeamMemberRepo { command_runner };
 
-        let result = team_member_repo.list(true)?;
+        let result = team_member_repo.list()?;
 
-        assert_eq!(result, Vec::<String>::new());
+        assert_eq!(result, vec![]);
 
         Ok(())
     }
@@ -205,7 +402,7 @@ mod tests {
         let command_runner = create_mock_command_runner("git", args, stdout, stderr, status_code);
         let team_member_repo = GitConfigTeamMemberRepo { command_runner };
 
-        let result = team_member_repo.list(true);
+        let result = team_member_repo.list();
 
         assert!(
             result
@@ -224,7 +421,7 @@ mod tests {
         let command_runner = create_mock_command_runner("git", args, stdout, stderr, status_code);
         let team_member_repo = GitConfigTeamMemberRepo { command_runner };
 
-        let result = team_member_repo.list(true);
+        let result = team_member_repo.list();
 
         assert!(result.is_err_and(|x| x.to_string() == "Git config command terminated by signal"));
 
@@ -243,7 +440,10 @@ mod tests {
 
         let result = team_member_repo.get(key)?;
 
-        assert_eq!(result, Some("Leo Messi <leo.messi@example.com>".into()));
+        assert_eq!(
+            result,
+            Some(TeamMember::new("lm", "Leo Messi", "leo.messi@example.com")?)
+        );
 
         Ok(())
     }
@@ -371,12 +571,12 @@ mod tests {
     #[test]
     fn test_add() -> Result<()> {
         let key = "lm";
-        let team_member = "Leo Messi <leo.messi@example.com>";
+        let team_member = &TeamMember::new(key, "Leo Messi", "leo.messi@example.com")?;
         let args = &[
             "config",
             "--global",
             &format!("coauthors.{key}"),
-            team_member,
+            "Leo Messi <leo.messi@example.com>",
         ];
         let stdout = vec![];
         let stderr = vec![];
@@ -384,7 +584,7 @@ mod tests {
         let command_runner = create_mock_command_runner("git", args, stdout, stderr, status_code);
         let team_member_repo = GitConfigTeamMemberRepo { command_runner };
 
-        team_member_repo.add(key, team_member)?;
+        team_member_repo.add(team_member)?;
 
         Ok(())
     }
@@ -392,12 +592,12 @@ mod tests {
     #[test]
     fn test_add_when_invalid_key() -> Result<()> {
         let key = "l_m";
-        let team_member = "Leo Messi <leo.messi@example.com>";
+        let team_member = &TeamMember::new(key, "Leo Messi", "leo.messi@example.com")?;
         let args = &[
             "config",
             "--global",
             &format!("coauthors.{key}"),
-            team_member,
+            "Leo Messi <leo.messi@example.com>",
         ];
         let stdout = vec![];
         let stderr = format!("error: invalid key: coauthors.{key}").into_bytes();
@@ -405,7 +605,7 @@ mod tests {
         let command_runner = create_mock_command_runner("git", args, stdout, stderr, status_code);
         let team_member_repo = GitConfigTeamMemberRepo { command_runner };
 
-        let result = team_member_repo.add(key, team_member);
+        let result = team_member_repo.add(team_member);
 
         assert!(result.is_err_and(|x| x.to_string() == format!("Invalid key: {key}")));
 
@@ -415,12 +615,12 @@ mod tests {
     #[test]
     fn test_add_when_unexpected_error() -> Result<()> {
         let key = "lm";
-        let team_member = "Leo Messi <leo.messi@example.com>";
+        let team_member = &TeamMember::new(key, "Leo Messi", "leo.messi@example.com")?;
         let args = &[
             "config",
             "--global",
             &format!("coauthors.{key}"),
-            team_member,
+            "Leo Messi <leo.messi@example.com>",
         ];
         let stdout = vec![];
         let stderr = b"uh-oh!".into();
@@ -428,7 +628,7 @@ mod tests {
         let command_runner = create_mock_command_runner("git", args, stdout, stderr, status_code);
         let team_member_repo = GitConfigTeamMemberRepo { command_runner };
 
-        let result = team_member_repo.add(key, team_member);
+        let result = team_member_repo.add(team_member);
 
         assert!(
             result
@@ -441,22 +641,174 @@ mod tests {
     #[test]
     fn test_add_when_terminated_by_signal() -> Result<()> {
         let key = "lm";
-        let team_member = "Leo Messi <leo.messi@example.com>";
+        let team_member = &TeamMember::new(key, "Leo Messi", "leo.messi@example.com")?;
         let args = &[
             "config",
             "--global",
             &format!("coauthors.{key}"),
-            team_member,
+            "Leo Messi <leo.messi@example.com>",
         ];
         let stdout = vec![];
         let stderr = vec![];
         let status_code = None;
         let command_runner = create_mock_command_runner("git", args, stdout, stderr, status_code);
         let team_member_repo = GitConfigTeamMemberRepo { command_runner };
-        let result = team_member_repo.add(key, team_member);
+        let result = team_member_repo.add(team_member);
 
         assert!(result.is_err_and(|x| x.to_string() == "Git config command terminated by signal"));
 
         Ok(())
     }
+
+    #[test]
+    fn test_team_member_parse() -> Result<()> {
+        let result = TeamMember::parse("lm", "Leo Messi <leo.messi@example.com>\n")?;
+
+        assert_eq!(
+            result,
+            TeamMember {
+                key: "lm".to_owned(),
+                name: "Leo Messi".to_owned(),
+                email: "leo.messi@example.com".to_owned(),
+                source: None,
+            }
+        );
+        assert_eq!(result.to_string(), "Leo Messi <leo.messi@example.com>");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_team_member_parse_when_invalid() -> Result<()> {
+        for value in ["Leo Messi", "Leo Messi leo.messi@example.com"] {
+            let result = TeamMember::parse("lm", value);
+
+            assert!(result.is_err_and(
+                |x| x.to_string() == format!("Invalid team member with key lm: {value}")
+            ));
+        }
+
+        let result = TeamMember::parse("lm", "Leo Messi <>");
+        assert!(result.is_err_and(|x| x.to_string()
+            == "Invalid team member with key lm: Invalid email: it must not be empty"));
+
+        for value in [
+            "<leo.messi@example.com>",
+            "Leo <Messi> <leo.messi@example.com>",
+        ] {
+            let result = TeamMember::parse("lm", value);
+
+            assert!(result.is_err_and(|x| {
+                x.to_string()
+                    .starts_with("Invalid team member with key lm: Invalid name: ")
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_team_member_new_when_invalid() -> Result<()> {
+        let result = TeamMember::new("l m", "Leo Messi", "leo.messi@example.com");
+        assert!(result.is_err_and(|x| x.to_string()
+            == "Invalid key: l m. It must not contain whitespace or control characters"));
+
+        let result = TeamMember::new("lm", "Leo <Messi>", "leo.messi@example.com");
+        assert!(result.is_err_and(
+            |x| x.to_string() == "Invalid name: Leo <Messi>. It must not contain < or >"
+        ));
+
+        let result = TeamMember::new("lm", "Leo Messi", " ");
+        assert!(result.is_err_and(|x| x.to_string() == "Invalid email: it must not be empty"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_hidden() -> Result<()> {
+        let args = &["config", "--global", "--get-all", "coauthors-hidden.entry"];
+        let stdout = b"lm\nem\n".into();
+        let stderr = vec![];
+        let status_code = Some(0);
+        let command_runner = create_mock_command_runner("git", args, stdout, stderr, status_code);
+        let team_member_repo = GitConfigTeamMemberRepo { command_runner };
+
+        let result = team_member_repo.list_hidden()?;
+
+        assert_eq!(result, vec!["lm", "em"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_hidden_when_no_team_members_hidden() -> Result<()> {
+        let args = &["config", "--global", "--get-all", "coauthors-hidden.entry"];
+        let stdout = vec![];
+        let stderr = vec![];
+        let status_code = Some(1);
+        let command_runner = create_mock_command_runner("git", args, stdout, stderr, status_code);
+        let team_member_repo = GitConfigTeamMemberRepo { command_runner };
+
+        let result = team_member_repo.list_hidden()?;
+
+        assert_eq!(result, Vec::<String>::new());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_hide() -> Result<()> {
+        let args = &[
+            "config",
+            "--global",
+            "--add",
+            "coauthors-hidden.entry",
+            "lm",
+        ];
+        let stdout = vec![];
+        let stderr = vec![];
+        let status_code = Some(0);
+        let command_runner = create_mock_command_runner("git", args, stdout, stderr, status_code);
+        let team_member_repo = GitConfigTeamMemberRepo { command_runner };
+
+        team_member_repo.hide("lm")?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_aliases() -> Result<()> {
+        let args = &["config", "--global", "--get-regexp", "^coauthors-alias\\."];
+        let stdout = b"coauthors-alias.leo lm\ncoauthors-alias.messi lm\n".into();
+        let command_runner = create_mock_command_runner("git", args, stdout, vec![], Some(0));
+        let team_member_repo = GitConfigTeamMemberRepo { command_runner };
+
+        assert_eq!(
+            team_member_repo.list_aliases()?,
+            vec![
+                ("leo".to_owned(), "lm".to_owned()),
+                ("messi".to_owned(), "lm".to_owned()),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_alias() -> Result<()> {
+        let args = &["config", "--global", "coauthors-alias.leo", "lm"];
+        let command_runner = create_mock_command_runner("git", args, vec![], vec![], Some(0));
+        let team_member_repo = GitConfigTeamMemberRepo { command_runner };
+
+        team_member_repo.add_alias("leo", "lm")
+    }
+
+    #[test]
+    fn test_remove_alias_when_alias_not_found() -> Result<()> {
+        let args = &["config", "--global", "--unset-all", "coauthors-alias.leo"];
+        let command_runner = create_mock_command_runner("git", args, vec![], vec![], Some(5));
+        let team_member_repo = GitConfigTeamMemberRepo { command_runner };
+
+        team_member_repo.remove_alias("leo")
+    }
 }
@@ -0,0 +1,281 @@
+//! Validation of the keys, names and emails of co-authors
+//!
+//! Values are normalized and checked in two levels. Values which would corrupt the roster or
+//! the Co-authored-by trailers are always rejected. Values which are valid but unusual, and
+//! more likely to be mistakes which GitHub won't attribute, are only rejected by the checks
+//! which can be skipped with --allow-unusual.
+
+use crate::Result;
+use unicode_normalization::UnicodeNormalization;
+
+const ALLOW_UNUSUAL_HINT: &str = "Use --allow-unusual if it is intended";
+
+/// Special characters allowed in the local part of an email besides letters, digits and dots
+const LOCAL_PART_SPECIAL_CHARS: &str = "!#$%&'*+-/=?^_`{|}~";
+
+/// Checks if the value starts with a letter and only contains letters, digits and hyphens,
+/// which is what git config allows in variable names
+pub fn is_identifier(value: &str) -> bool {
+    value.starts_with(|c: char| c.is_ascii_alphabetic())
+        && value.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+/// Trims the key and rejects it if it is empty or contains whitespace or control characters
+pub fn normalize_key(key: &str) -> Result<String> {
+    let normalized: String = key.trim().nfc().collect();
+
+    if normalized.is_empty() {
+        return Err("Invalid key: it must not be empty".into());
+    }
+    if normalized.contains(|c: char| c.is_whitespace() || c.is_control()) {
+        return Err(format!(
+            "Invalid key: {}. It must not contain whitespace or control characters",
+            key.escape_debug()
+        )
+        .into());
+    }
+
+    Ok(normalized)
+}
+
+/// Trims the name and normalizes its Unicode characters (NFC), rejecting names which would break
+/// the "Name <email>" format
+pub fn normalize_name(name: &str) -> Result<String> {
+    let normalized: String = name.trim().nfc().collect();
+
+    if normalized.is_empty() {
+        return Err("Invalid name: it must not be empty".into());
+    }
+    if normalized.contains(['<', '>']) {
+        return Err(format!(
+            "Invalid name: {}. It must not contain < or >",
+            name.escape_debug()
+        )
+        .into());
+    }
+    if normalized.contains(char::is_control) {
+        return Err(format!(
+            "Invalid name: {}. It must not contain control characters such as line breaks",
+            name.escape_debug()
+        )
+        .into());
+    }
+
+    Ok(normalized)
+}
+
+/// Trims the email and normalizes its Unicode characters (NFC), rejecting emails which would break
+/// the "Name <email>" format
+pub fn normalize_email(email: &str) -> Result<String> {
+    let normalized: String = email.trim().nfc().collect();
+
+    if normalized.is_empty() {
+        return Err("Invalid email: it must not be empty".into());
+    }
+    if normalized.contains(['<', '>']) {
+        return Err(format!(
+            "Invalid email: {}. It must not contain < or >",
+            email.escape_debug()
+        )
+        .into());
+    }
+    if normalized.contains(|c: char| c.is_whitespace() || c.is_control()) {
+        return Err(format!(
+            "Invalid email: {}. It must not contain whitespace or control characters",
+            email.escape_debug()
+        )
+        .into());
+    }
+
+    Ok(normalized)
+}
+
+/// Rejects keys which cannot be stored in git config or used with --with
+pub fn check_usual_key(key: &str) -> Result<()> {
+    if !is_identifier(key) {
+        return Err(format!(
+            "Invalid key: {key}. It must start with a letter and only contain letters, digits and hyphens. {ALLOW_UNUSUAL_HINT}"
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+/// Rejects names which are wrapped in quotes or contain invisible formatting characters
+pub fn check_usual_name(name: &str) -> Result<()> {
+    if name.starts_with(['"', '\'']) && name.ends_with(['"', '\'']) {
+        return Err(format!(
+            "Invalid name: {name}. It must not be wrapped in quotes. {ALLOW_UNUSUAL_HINT}"
+        )
+        .into());
+    }
+    if let Some(c) = name.chars().find(|x| is_invisible_format_char(*x)) {
+        return Err(format!(
+            "Invalid name: {}. It contains the invisible formatting character U+{:04X}. {ALLOW_UNUSUAL_HINT}",
+            name.escape_debug(),
+            c as u32
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+/// Rejects emails which are not like local-part@example.com, with a local part made up of
+/// the unquoted characters allowed by RFC 5322 and a domain name made up of at least two labels
+pub fn check_usual_email(email: &str) -> Result<()> {
+    let Some((local_part, domain)) = email.split_once('@').filter(|(_, x)| !x.contains('@')) else {
+        return Err(format!(
+            "Invalid email: {email}. It must have a single @ between the local part and the domain. {ALLOW_UNUSUAL_HINT}"
+        )
+        .into());
+    };
+
+    let is_local_part_char =
+        |c: char| c.is_ascii_alphanumeric() || LOCAL_PART_SPECIAL_CHARS.contains(c);
+    if local_part.is_empty()
+        || local_part.len() > 64
+        || local_part.split('.').any(|x| x.is_empty())
+        || !local_part
+            .chars()
+            .all(|c| c == '.' || is_local_part_char(c))
+    {
+        return Err(format!(
+            "Invalid email: {email}. The local part before @ must only contain letters, digits and {LOCAL_PART_SPECIAL_CHARS} separated by single dots. {ALLOW_UNUSUAL_HINT}"
+        )
+        .into());
+    }
+
+    let is_label = |label: &str| {
+        (1..=63).contains(&label.len())
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+            && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    };
+    let labels: Vec<&str> = domain.split('.').collect();
+    if labels.len() < 2 || !labels.iter().all(|x| is_label(x)) {
+        return Err(format!(
+            "Invalid email: {email}. The domain after @ must be a domain name like example.com. {ALLOW_UNUSUAL_HINT}"
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+/// Checks if the character is invisible and changes how text is displayed, e.g. zero width
+/// spaces and bidirectional text overrides
+fn is_invisible_format_char(c: char) -> bool {
+    matches!(
+        c,
+        '\u{00AD}'
+            | '\u{061C}'
+            | '\u{180E}'
+            | '\u{200B}'..='\u{200F}'
+            | '\u{202A}'..='\u{202E}'
+            | '\u{2060}'..='\u{2064}'
+            | '\u{2066}'..='\u{206F}'
+            | '\u{FEFF}'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_name() -> Result<()> {
+        // "e" followed by a combining acute accent is composed into "é"
+        assert_eq!(
+            normalize_name(" Rene\u{0301} Houseman ")?,
+            "Ren\u{e9} Houseman"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_normalize_name_when_invalid() {
+        for (name, error) in [
+            (" ", "Invalid name: it must not be empty"),
+            (
+                "Leo <Messi>",
+                "Invalid name: Leo <Messi>. It must not contain < or >",
+            ),
+            (
+                "Leo\nMessi",
+                "Invalid name: Leo\\nMessi. It must not contain control characters such as line breaks",
+            ),
+        ] {
+            assert!(normalize_name(name).is_err_and(|x| x.to_string() == error));
+        }
+    }
+
+    #[test]
+    fn test_normalize_email_when_invalid() {
+        for (email, error) in [
+            ("", "Invalid email: it must not be empty"),
+            (
+                "<leo.messi@example.com>",
+                "Invalid email: <leo.messi@example.com>. It must not contain < or >",
+            ),
+            (
+                "leo messi@example.com",
+                "Invalid email: leo messi@example.com. It must not contain whitespace or control characters",
+            ),
+        ] {
+            assert!(normalize_email(email).is_err_and(|x| x.to_string() == error));
+        }
+    }
+
+    #[test]
+    fn test_normalize_key_when_invalid() {
+        assert!(normalize_key("l m").is_err_and(|x| x.to_string()
+            == "Invalid key: l m. It must not contain whitespace or control characters"));
+    }
+
+    #[test]
+    fn test_check_usual_key() {
+        assert!(check_usual_key("lm2").is_ok());
+        assert!(check_usual_key("leo_messi").is_err_and(|x| x.to_string()
+            == "Invalid key: leo_messi. It must start with a letter and only contain letters, digits and hyphens. Use --allow-unusual if it is intended"));
+    }
+
+    #[test]
+    fn test_check_usual_name() {
+        assert!(check_usual_name("Rodrigo De Paul").is_ok());
+        assert!(check_usual_name("Ángel Di María").is_ok());
+        assert!(check_usual_name("\"Leo\"").is_err_and(|x| x.to_string()
+            == "Invalid name: \"Leo\". It must not be wrapped in quotes. Use --allow-unusual if it is intended"));
+        assert!(check_usual_name("Leo\u{202E}issem").is_err_and(|x| x.to_string()
+            == "Invalid name: Leo\\u{202e}issem. It contains the invisible formatting character U+202E. Use --allow-unusual if it is intended"));
+    }
+
+    #[test]
+    fn test_check_usual_email() {
+        for email in [
+            "leo.messi@example.com",
+            "leo+git@mail.example.co.uk",
+            "12345+leo@users.noreply.github.com",
+        ] {
+            assert!(check_usual_email(email).is_ok(), "{email}");
+        }
+
+        for (email, reason) in [
+            ("not-an-email", "It must have a single @"),
+            ("leo@messi@example.com", "It must have a single @"),
+            ("leo..messi@example.com", "The local part before @"),
+            (".leo@example.com", "The local part before @"),
+            ("leó@example.com", "The local part before @"),
+            ("leo@localhost", "The domain after @"),
+            ("leo@-example.com", "The domain after @"),
+            ("leo@example..com", "The domain after @"),
+        ] {
+            assert!(
+                check_usual_email(email).is_err_and(|x| x.to_string().contains(reason)),
+                "{email}"
+            );
+        }
+    }
+}
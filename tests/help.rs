@@ -29,17 +29,29 @@ git mob --with lm
 Usage: git mob [COMMAND] [OPTIONS]
 
 Commands:
-  setup        Create global prepare-commit-msg githook which appends Co-authored-by trailers to commit message
-  team-member  Add/delete/list team member(s) from team member repository
-  help         Print this message or the help of the given subcommand(s)
+  setup         Create global prepare-commit-msg githook which appends Co-authored-by trailers to commit message
+  team-member   Add/delete/list team member(s) from team member repository
+  team          Add/delete/list team group(s) of team members
+  timer         Count down driver rotations of the mob/pair programming session
+  pingpong      Start ping-pong mode which swaps the driver and co-author after every commit
+  preset        Save/load/delete/list presets of mob/pair programming session co-author(s)
+  history       List history of the mob/pair programming sessions
+  stats         Show who paired with whom from the Co-authored-by trailers of commits in the current repository
+  suggest-pair  Suggest team members to pair with, starting with the least recently paired with
+  schedule      Generate a round-robin pair rotation schedule for team members
+  help          Print this message or the help of the given subcommand(s)
 
 Options:
   -w, --with [<COAUTHOR_KEY>...]
           Sets co-author(s) from team member(s) in the mob/pair programming session
           
-          This will clear any existing co-author(s) in current session
+          This will clear any existing co-author(s) in current session. A team group can be given as @name and team member(s) can be excluded with -key or -@name. A team member can also be given by a unique prefix of their key or by their name or email, and a co-author who is not a team member as "Name <email>"
           
           Usage example: git mob pair --with lm mj
+          
+          Usage example: git mob --with @frontend -lm
+          
+          Usage example: git mob --with lm "John Doe <john.doe@example.com>"
 
   -a, --add <COAUTHOR_NAME> <COAUTHOR_EMAIL>
           Adds co-author to the mob/pair programming session (usually non-team member)
@@ -51,16 +63,68 @@ Options:
           
           Usage example: git mob --clear
 
+      --driver <TEAM_MEMBER_KEY>
+          Sets driver of the mob/pair programming session from team member
+          
+          The driver becomes the commit author (via author.name and author.email git config in the same scope as the session) and is removed from the co-author(s). The previous driver becomes a co-author.
+          
+          Usage example: git mob --driver lm
+
   -l, --list
           Lists co-author(s) in the mob/pair programming session
           
           Usage example: git mob --list
 
+      --verbose
+          Shows the scope, driver and expiry of the mob/pair programming session with --list
+          
+          Without it, --list only prints the co-author(s) one per line, which githooks set up by older versions rely on
+          
+          Usage example: git mob --list --verbose
+
   -t, --trailers
           Lists Co-authored-by trailers in the mob/pair programming session
           
           Usage example: git mob --trailers
 
+      --format <FORMAT>
+          Sets format of the output of the listing commands
+          
+          Applies to --list, --trailers, team-member --list, history, stats and schedule. json prints a versioned document described in docs/output_schema.json. tsv, table, csv and markdown print one row per entry with a header row. Formats other than text can't be used with both --list and --trailers.
+          
+          Usage example: git mob --list --format json
+
+          Possible values:
+          - text:     Human readable lines, which may change between versions
+          - json:     Versioned JSON document described in docs/output_schema.json
+          - tsv:      Tab separated values with a header row
+          - table:    Aligned columns with a header row
+          - csv:      Comma separated values with a header row, quoted where needed
+          - markdown: Markdown table
+          
+          [default: text]
+
+      --local
+          Uses the mob/pair programming session of the current repository instead of the global one
+          
+          The local session takes precedence over the global one when committing in the repository
+          
+          Usage example: git mob --with lm --local
+
+      --expire-after <DURATION>
+          Sets how long mob/pair programming sessions last after they start e.g. 30m, 8h, 1d, eod (end of day) or never
+          
+          An expired session is ignored when committing. Local sessions expire after the global setting unless it is set with --local
+          
+          Usage example: git mob --expire-after eod
+
+      --allow-unusual
+          Allows unusual but valid name and email of the co-author added with --add or given as "Name <email>" to --with
+          
+          e.g. emails without a domain name or names wrapped in quotes
+          
+          Usage example: git mob --add "John Doe" john@localhost --allow-unusual
+
   -h, --help
           Print help (see a summary with '-h')
 
@@ -85,9 +149,17 @@ r#"A CLI tool which can help users automatically add co-author(s) to git commits
 Usage: git mob [COMMAND] [OPTIONS]
 
 Commands:
-  setup        Create global prepare-commit-msg githook which appends Co-authored-by trailers to commit message
-  team-member  Add/delete/list team member(s) from team member repository
-  help         Print this message or the help of the given subcommand(s)
+  setup         Create global prepare-commit-msg githook which appends Co-authored-by trailers to commit message
+  team-member   Add/delete/list team member(s) from team member repository
+  team          Add/delete/list team group(s) of team members
+  timer         Count down driver rotations of the mob/pair programming session
+  pingpong      Start ping-pong mode which swaps the driver and co-author after every commit
+  preset        Save/load/delete/list presets of mob/pair programming session co-author(s)
+  history       List history of the mob/pair programming sessions
+  stats         Show who paired with whom from the Co-authored-by trailers of commits in the current repository
+  suggest-pair  Suggest team members to pair with, starting with the least recently paired with
+  schedule      Generate a round-robin pair rotation schedule for team members
+  help          Print this message or the help of the given subcommand(s)
 
 Options:
   -w, --with [<COAUTHOR_KEY>...]
@@ -96,10 +168,22 @@ Options:
           Adds co-author to the mob/pair programming session (usually non-team member)
   -c, --clear
           Clears the mob/pair programming session. Going solo!
+      --driver <TEAM_MEMBER_KEY>
+          Sets driver of the mob/pair programming session from team member
   -l, --list
           Lists co-author(s) in the mob/pair programming session
+      --verbose
+          Shows the scope, driver and expiry of the mob/pair programming session with --list
   -t, --trailers
           Lists Co-authored-by trailers in the mob/pair programming session
+      --format <FORMAT>
+          Sets format of the output of the listing commands [default: text] [possible values: text, json, tsv, table, csv, markdown]
+      --local
+          Uses the mob/pair programming session of the current repository instead of the global one
+      --expire-after <DURATION>
+          Sets how long mob/pair programming sessions last after they start e.g. 30m, 8h, 1d, eod (end of day) or never
+      --allow-unusual
+          Allows unusual but valid name and email of the co-author added with --add or given as "Name <email>" to --with
   -h, --help
           Print help (see more with '--help')
   -V, --version
@@ -122,7 +206,11 @@ r#"Add/delete/list team member(s) from team member repository
 
 User must store team member(s) to team member repository by using keys before starting pair/mob programming session(s).
 
-Usage: git mob team-member [OPTIONS]
+Usage: git mob team-member [OPTIONS] [REVISION_RANGE]
+
+Arguments:
+  [REVISION_RANGE]
+          Only imports from commits in the given revision range
 
 Options:
   -a, --add <TEAM_MEMBER_KEY> <TEAM_MEMBER_NAME> <TEAM_MEMBER_EMAIL>
@@ -130,16 +218,142 @@ Options:
           
           Usage example: git mob team-member --add lm "Leo Messi" leo.messi@example.com
 
+      --force
+          Overwrites the team member if the key given to --add already exists
+
+  -e, --edit <TEAM_MEMBER_KEY>
+          Edits team member, prompting for the key, name and email if none of them is given
+          
+          Team groups and presets which reference the team member are updated too.
+          
+          Usage example: git mob team-member --edit lm --email leo@example.com
+
+      --key <TEAM_MEMBER_KEY>
+          Sets the new key of the edited team member
+
+      --name <TEAM_MEMBER_NAME>
+          Sets the new name of the edited team member
+
+      --email <TEAM_MEMBER_EMAIL>
+          Sets the new email of the edited team member
+
+      --alias <ALIAS> <TEAM_MEMBER_KEY>
+          Adds alias which can be used instead of the key of the team member
+          
+          Usage example: git mob team-member --alias leo lm
+
   -d, --delete <TEAM_MEMBER_KEY>
           Remove team member from team member repository
           
+          Their aliases are removed too. An alias can be given to only remove the alias.
+          
           Usage example: git mob team-member --delete lm
 
+      --hide <TEAM_MEMBER_KEY>
+          Hides team member which comes from a further layer of the roster (e.g. system roster)
+          
+          Usage example: git mob team-member --hide lm
+
+      --repo
+          Writes the changes to the roster file committed in the current repository instead of your global team members
+          
+          Usage example: git mob team-member --add lm "Leo Messi" leo.messi@example.com --repo
+
+      --system
+          Writes the changes to the system-wide roster file instead of your global team members
+          
+          Usage example: git mob team-member --hide lm --system
+
   -l, --list
           Lists team member(s) with keys(s) from team member repository
           
+          The layer of the roster (repo, user, git-coauthors or system) which each team member comes from is shown too, followed by their aliases
+          
           Usage example: git mob team-member --list
 
+      --import-from-log
+          Imports team member(s) from authors and co-authors of commits in the current repository
+          
+          Team members are deduplicated by email and given keys based on their initials. The team member(s) to import are confirmed with a multi-select prompt.
+          
+          Usage example: git mob team-member --import-from-log --since "6 months ago" main
+
+      --since <DATE>
+          Only imports from commits more recent than the given date
+
+      --export
+          Exports team member(s) from team member repository to stdout
+          
+          Usage example: git mob team-member --export --roster-format csv > roster.csv
+
+      --import [<FILE>]
+          Imports team member(s) from a file to team member repository
+          
+          The file can be omitted when importing from another tool with --from.
+          
+          Usage example: git mob team-member --import roster.json --on-conflict rename
+
+      --from <TOOL>
+          Imports from the author file or git config of another pair/mob programming tool
+          
+          Entries which cannot be converted (e.g. without an email address) are reported and skipped.
+          
+          Usage example: git mob team-member --import --from git-duet
+
+          Possible values:
+          - git-duet:     `~/.git-authors` YAML file (or the path in GIT_DUET_AUTHORS_FILE environment variable)
+          - git-together: `git-together.authors.*` git config
+          - git-pair:     `pair.*` git config
+
+      --roster-format <FORMAT>
+          Sets format of the exported or imported team member(s)
+          
+          Defaults to the extension of the imported file or json.
+          
+          Usage example: git mob team-member --export --roster-format git-coauthors
+
+          Possible values:
+          - json:          JSON array of team members
+          - csv:           Comma separated values with a header row
+          - git-coauthors: `~/.git-coauthors` file of the JavaScript git-mob tool
+
+      --format <FORMAT>
+          Sets format of the output of the listing commands
+          
+          Applies to --list, --trailers, team-member --list, history, stats and schedule. json prints a versioned document described in docs/output_schema.json. tsv, table, csv and markdown print one row per entry with a header row. Formats other than text can't be used with both --list and --trailers.
+          
+          Usage example: git mob --list --format json
+
+          Possible values:
+          - text:     Human readable lines, which may change between versions
+          - json:     Versioned JSON document described in docs/output_schema.json
+          - tsv:      Tab separated values with a header row
+          - table:    Aligned columns with a header row
+          - csv:      Comma separated values with a header row, quoted where needed
+          - markdown: Markdown table
+          
+          [default: text]
+
+      --on-conflict <STRATEGY>
+          Sets what to do with an imported team member whose key already exists
+
+          Possible values:
+          - skip:      Keep the existing team member
+          - overwrite: Replace the existing team member
+          - rename:    Add the imported team member with a new key
+          
+          [default: skip]
+
+      --dry-run
+          Shows changes the import would make without making them
+
+      --allow-unusual
+          Allows unusual but valid keys, names and emails of added, edited or imported team member(s)
+          
+          e.g. emails without a domain name, names wrapped in quotes or keys with underscores (in a roster file)
+          
+          Usage example: git mob team-member --add jd "John Doe" john@localhost --allow-unusual
+
   -h, --help
           Print help (see a summary with '-h')
 
@@ -161,15 +375,56 @@ fn test_team_member_help_summary(ctx: TestContextCli) -> Result<(), Box<dyn Erro
         .stdout(predicate::str::diff(
             r#"Add/delete/list team member(s) from team member repository
 
-Usage: git mob team-member [OPTIONS]
+Usage: git mob team-member [OPTIONS] [REVISION_RANGE]
+
+Arguments:
+  [REVISION_RANGE]  Only imports from commits in the given revision range
 
 Options:
   -a, --add <TEAM_MEMBER_KEY> <TEAM_MEMBER_NAME> <TEAM_MEMBER_EMAIL>
           Adds team member to team member repository
+      --force
+          Overwrites the team member if the key given to --add already exists
+  -e, --edit <TEAM_MEMBER_KEY>
+          Edits team member, prompting for the key, name and email if none of them is given
+      --key <TEAM_MEMBER_KEY>
+          Sets the new key of the edited team member
+      --name <TEAM_MEMBER_NAME>
+          Sets the new name of the edited team member
+      --email <TEAM_MEMBER_EMAIL>
+          Sets the new email of the edited team member
+      --alias <ALIAS> <TEAM_MEMBER_KEY>
+          Adds alias which can be used instead of the key of the team member
   -d, --delete <TEAM_MEMBER_KEY>
           Remove team member from team member repository
+      --hide <TEAM_MEMBER_KEY>
+          Hides team member which comes from a further layer of the roster (e.g. system roster)
+      --repo
+          Writes the changes to the roster file committed in the current repository instead of your global team members
+      --system
+          Writes the changes to the system-wide roster file instead of your global team members
   -l, --list
           Lists team member(s) with keys(s) from team member repository
+      --import-from-log
+          Imports team member(s) from authors and co-authors of commits in the current repository
+      --since <DATE>
+          Only imports from commits more recent than the given date
+      --export
+          Exports team member(s) from team member repository to stdout
+      --import [<FILE>]
+          Imports team member(s) from a file to team member repository
+      --from <TOOL>
+          Imports from the author file or git config of another pair/mob programming tool [possible values: git-duet, git-together, git-pair]
+      --roster-format <FORMAT>
+          Sets format of the exported or imported team member(s) [possible values: json, csv, git-coauthors]
+      --format <FORMAT>
+          Sets format of the output of the listing commands [default: text] [possible values: text, json, tsv, table, csv, markdown]
+      --on-conflict <STRATEGY>
+          Sets what to do with an imported team member whose key already exists [default: skip] [possible values: skip, overwrite, rename]
+      --dry-run
+          Shows changes the import would make without making them
+      --allow-unusual
+          Allows unusual but valid keys, names and emails of added, edited or imported team member(s)
   -h, --help
           Print help (see more with '--help')
   -V, --version
@@ -200,6 +455,23 @@ Options:
           
           Usage example: git mob setup --local
 
+      --format <FORMAT>
+          Sets format of the output of the listing commands
+          
+          Applies to --list, --trailers, team-member --list, history, stats and schedule. json prints a versioned document described in docs/output_schema.json. tsv, table, csv and markdown print one row per entry with a header row. Formats other than text can't be used with both --list and --trailers.
+          
+          Usage example: git mob --list --format json
+
+          Possible values:
+          - text:     Human readable lines, which may change between versions
+          - json:     Versioned JSON document described in docs/output_schema.json
+          - tsv:      Tab separated values with a header row
+          - table:    Aligned columns with a header row
+          - csv:      Comma separated values with a header row, quoted where needed
+          - markdown: Markdown table
+          
+          [default: text]
+
   -h, --help
           Print help (see a summary with '-h')
 
@@ -224,9 +496,10 @@ fn test_setup_help_summary(ctx: TestContextCli) -> Result<(), Box<dyn Error>> {
 Usage: git mob setup [OPTIONS]
 
 Options:
-      --local    Set up local prepare-commit-msg githook which invokes the global one
-  -h, --help     Print help (see more with '--help')
-  -V, --version  Print version
+      --local            Set up local prepare-commit-msg githook which invokes the global one
+      --format <FORMAT>  Sets format of the output of the listing commands [default: text] [possible values: text, json, tsv, table, csv, markdown]
+  -h, --help             Print help (see more with '--help')
+  -V, --version          Print version
 "#,
         ));
 
@@ -1,4 +1,9 @@
-use std::{env, ffi::OsString, path::PathBuf, process::Command};
+use std::{
+    env,
+    ffi::OsString,
+    path::{Path, PathBuf},
+    process::Command,
+};
 
 use assert_cmd::{assert::OutputAssertExt, cargo::cargo_bin};
 use once_cell::sync::Lazy;
@@ -18,8 +23,26 @@ static PATH_WITH_GIT_MOB: Lazy<OsString> = Lazy::new(|| {
     env::join_paths(split_paths).unwrap()
 });
 
+/// Isolates the rosters read from the user's home directory and the system from the tests
+fn isolate_rosters(command: &mut Command, home_dir: &Path) {
+    #[cfg(unix)]
+    {
+        command.env("HOME", home_dir);
+    }
+    #[cfg(windows)]
+    {
+        command.env("USERPROFILE", home_dir);
+    }
+
+    command
+        .env("GITMOB_COAUTHORS_PATH", home_dir.join(".git-coauthors"))
+        .env("GIT_MOB_SYSTEM_ROSTER", home_dir.join("system-roster.toml"));
+}
+
 pub(crate) struct TestContextCli {
     git_config_global: TempPath,
+    history_file: TempPath,
+    home_dir: TempDir,
 }
 
 impl TestContextCli {
@@ -28,6 +51,8 @@ impl TestContextCli {
         let mut command = Command::new("git");
         command.env("PATH", &*PATH_WITH_GIT_MOB);
         command.env("GIT_CONFIG_GLOBAL", &self.git_config_global);
+        command.env("GIT_MOB_HISTORY_FILE", &self.history_file);
+        isolate_rosters(&mut command, self.home_dir.path());
         command
     }
 }
@@ -36,6 +61,8 @@ impl TestContext for TestContextCli {
     fn setup() -> TestContextCli {
         TestContextCli {
             git_config_global: NamedTempFile::new().unwrap().into_temp_path(),
+            history_file: NamedTempFile::new().unwrap().into_temp_path(),
+            home_dir: tempdir().unwrap(),
         }
     }
 }
@@ -43,6 +70,7 @@ impl TestContext for TestContextCli {
 #[allow(dead_code)] // incorrectly detected as unused by clippy; used in tests
 pub(crate) struct TestContextRepo {
     git_config_global: TempPath,
+    history_file: TempPath,
     dir: TempDir,
     pub home_dir: TempDir,
 }
@@ -54,25 +82,24 @@ impl TestContextRepo {
         command
             .current_dir(self.dir.path())
             .env("PATH", &*PATH_WITH_GIT_MOB)
-            .env("GIT_CONFIG_GLOBAL", &self.git_config_global);
-
-        #[cfg(unix)]
-        {
-            command.env("HOME", self.home_dir.path());
-        }
-        #[cfg(windows)]
-        {
-            command.env("USERPROFILE", self.home_dir.path());
-        }
+            .env("GIT_CONFIG_GLOBAL", &self.git_config_global)
+            .env("GIT_MOB_HISTORY_FILE", &self.history_file);
+        isolate_rosters(&mut command, self.home_dir.path());
 
         command
     }
+
+    #[allow(dead_code)] // incorrectly detected as unused by clippy; used in tests
+    pub fn dir(&self) -> &Path {
+        self.dir.path()
+    }
 }
 
 impl TestContext for TestContextRepo {
     fn setup() -> TestContextRepo {
         let ctx = TestContextRepo {
             git_config_global: NamedTempFile::new().unwrap().into_temp_path(),
+            history_file: NamedTempFile::new().unwrap().into_temp_path(),
             dir: tempdir().unwrap(),
             home_dir: tempdir().unwrap(),
         };
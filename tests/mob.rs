@@ -1,11 +1,12 @@
 mod helpers;
 
 use assert_cmd::prelude::*;
-use helpers::test_contexts::TestContextCli;
+use helpers::test_contexts::{TestContextCli, TestContextRepo};
 use predicates::prelude::*;
 #[cfg(unix)]
 use rexpect::session::spawn_command;
 use std::error::Error;
+use std::process::Stdio;
 use test_context::test_context;
 
 #[test_context(TestContextCli, skip_teardown)]
@@ -50,6 +51,107 @@ fn test_mob_with_by_key_when_team_member_not_found(
     Ok(())
 }
 
+#[test_context(TestContextCli, skip_teardown)]
+#[test]
+fn test_mob_with_by_key_prefix_email_and_adhoc_coauthor(
+    ctx: TestContextCli,
+) -> Result<(), Box<dyn Error>> {
+    add_two_team_members(&ctx)?;
+
+    ctx.git()
+        .args([
+            "mob",
+            "--with",
+            "l",
+            "Emi.Martinez@example.com",
+            "John Doe <john.doe@example.com>",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "Leo Messi <leo.messi@example.com>\nEmi Martinez <emi.martinez@example.com>\nJohn Doe <john.doe@example.com>\n",
+        ));
+
+    // a typo fails with a suggestion and leaves the session as it was
+    ctx.git()
+        .args(["mob", "--with", "lx"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::diff(
+            "Error: \"No team member found with key: lx. Did you mean lm (Leo Messi <leo.messi@example.com>)?\"\n",
+        ));
+
+    ctx.git()
+        .args(["mob", "--list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "Leo Messi <leo.messi@example.com>\nEmi Martinez <emi.martinez@example.com>\nJohn Doe <john.doe@example.com>\n",
+        ));
+
+    Ok(())
+}
+
+#[test_context(TestContextCli, skip_teardown)]
+#[test]
+fn test_mob_list_and_trailers_given_format(ctx: TestContextCli) -> Result<(), Box<dyn Error>> {
+    add_two_team_members(&ctx)?;
+    ctx.git()
+        .args(["mob", "--with", "lm", "John Doe <john.doe@example.com>"])
+        .assert()
+        .success();
+
+    ctx.git()
+        .args(["mob", "--list", "--format", "json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            r#"{
+  "version": 1,
+  "members": [
+    {
+      "role": "coauthor",
+      "key": "lm",
+      "name": "Leo Messi",
+      "email": "leo.messi@example.com",
+      "scope": "global",
+      "source": "user"
+    },
+    {
+      "role": "coauthor",
+      "key": null,
+      "name": "John Doe",
+      "email": "john.doe@example.com",
+      "scope": "global",
+      "source": null
+    }
+  ]
+}
+"#,
+        ));
+
+    ctx.git()
+        .args(["mob", "--trailers", "--format", "table"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(concat!(
+            "KEY  NAME       EMAIL                  SCOPE   SOURCE  TRAILER\n",
+            "lm   Leo Messi  leo.messi@example.com  global  user    Co-authored-by: Leo Messi <leo.messi@example.com>\n",
+            "     John Doe   john.doe@example.com   global          Co-authored-by: John Doe <john.doe@example.com>\n",
+        )));
+
+    // --format only applies to the listings
+    ctx.git()
+        .args(["mob", "--format", "json"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--format only applies to --list, --trailers and the listing commands",
+        ));
+
+    Ok(())
+}
+
 #[test_context(TestContextCli, skip_teardown)]
 #[test]
 fn test_mob_with_multiselect_given_no_team_members_added(
@@ -305,6 +407,573 @@ fn test_list_mob_given_empty_mob_session(ctx: TestContextCli) -> Result<(), Box<
     Ok(())
 }
 
+#[test_context(TestContextRepo, skip_teardown)]
+#[test]
+fn test_local_mob_session(ctx: TestContextRepo) -> Result<(), Box<dyn Error>> {
+    // given a global mob session
+    ctx.git()
+        .args(["mob", "--add", "Leo Messi", "leo.messi@example.com"])
+        .assert()
+        .success();
+
+    // adding a co-author to the local mob session
+    ctx.git()
+        .args([
+            "mob",
+            "--add",
+            "Emi Martinez",
+            "emi.martinez@example.com",
+            "--local",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "Emi Martinez <emi.martinez@example.com>\n",
+        ));
+
+    // the local mob session takes precedence over the global one
+    ctx.git()
+        .args(["mob", "--list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "Emi Martinez <emi.martinez@example.com>\n",
+        ));
+
+    // clearing the local mob session falls back to the global one
+    ctx.git()
+        .args(["mob", "--clear", "--local"])
+        .assert()
+        .success();
+
+    ctx.git()
+        .args(["mob", "--list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("Leo Messi <leo.messi@example.com>\n"));
+
+    Ok(())
+}
+
+#[test_context(TestContextCli, skip_teardown)]
+#[test]
+fn test_mob_session_expiry(ctx: TestContextCli) -> Result<(), Box<dyn Error>> {
+    ctx.git()
+        .args(["mob", "--expire-after", "8h"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(""));
+
+    ctx.git()
+        .args(["mob", "--add", "Leo Messi", "leo.messi@example.com"])
+        .assert()
+        .success();
+
+    // the mob session has not expired yet
+    ctx.git()
+        .args(["mob", "--trailers"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "Co-authored-by: Leo Messi <leo.messi@example.com>\n",
+        ));
+
+    // pretending the mob session started a day ago
+    let started_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)?
+        .as_secs()
+        - 24 * 60 * 60;
+    ctx.git()
+        .args([
+            "config",
+            "--global",
+            "coauthors-mob.started",
+            &started_at.to_string(),
+        ])
+        .assert()
+        .success();
+
+    // the expired mob session is ignored
+    ctx.git()
+        .args(["mob", "--list", "--verbose"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::starts_with("Mob session (global) expired at ")
+                .and(predicate::str::contains("Leo Messi").not()),
+        );
+
+    ctx.git()
+        .args(["mob", "--trailers"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(""));
+
+    Ok(())
+}
+
+#[test_context(TestContextCli, skip_teardown)]
+#[test]
+fn test_mob_session_expiry_given_invalid_duration(
+    ctx: TestContextCli,
+) -> Result<(), Box<dyn Error>> {
+    ctx.git()
+        .args(["mob", "--expire-after", "8 hours"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Invalid session expiry: 8 hours. Expected a duration (e.g. 30m, 8h, 1d), eod (end of day) or never",
+        ));
+
+    Ok(())
+}
+
+#[test_context(TestContextCli, skip_teardown)]
+#[test]
+fn test_mob_session_expiry_given_out_of_range_duration(
+    ctx: TestContextCli,
+) -> Result<(), Box<dyn Error>> {
+    ctx.git()
+        .args(["mob", "--expire-after", "999999999d"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Invalid session expiry: 999999999d. Expected a duration of at most 36500d",
+        ));
+
+    Ok(())
+}
+
+#[test_context(TestContextCli, skip_teardown)]
+#[test]
+fn test_mob_timer(ctx: TestContextCli) -> Result<(), Box<dyn Error>> {
+    add_two_team_members(&ctx)?;
+
+    ctx.git()
+        .args(["mob", "--with", "em", "--driver", "lm"])
+        .assert()
+        .success();
+
+    // counting down a rotation and switching to the next driver
+    ctx.git()
+        .args(["mob", "timer", "1s"])
+        .stdin(Stdio::null())
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "Leo Messi <leo.messi@example.com> is driving. Press Enter to pause/resume or q and Enter to quit\n\
+             \rRotation 00:01         \rRotation 00:00         \n\
+             \x07Time's up! Emi Martinez <emi.martinez@example.com> drives next\n\
+             Driver: Emi Martinez <emi.martinez@example.com>\n\
+             Press Enter to start the next rotation or q and Enter to quit\n",
+        ));
+
+    ctx.git()
+        .args(["mob", "--list", "--verbose"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "Mob session (global):\nDriver: Emi Martinez <emi.martinez@example.com>\nNavigator: Leo Messi <leo.messi@example.com>\n",
+        ));
+
+    Ok(())
+}
+
+#[test_context(TestContextCli, skip_teardown)]
+#[test]
+fn test_mob_timer_given_invalid_duration(ctx: TestContextCli) -> Result<(), Box<dyn Error>> {
+    ctx.git()
+        .args(["mob", "timer", "10"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Invalid duration: 10. Expected e.g. 30s, 10m or 1h",
+        ));
+
+    Ok(())
+}
+
+#[test_context(TestContextCli, skip_teardown)]
+#[test]
+fn test_mob_history(ctx: TestContextCli) -> Result<(), Box<dyn Error>> {
+    add_two_team_members(&ctx)?;
+
+    // starting, adding to and clearing a mob session
+    ctx.git()
+        .args(["mob", "--with", "lm", "em"])
+        .assert()
+        .success();
+    ctx.git()
+        .args([
+            "mob",
+            "--add",
+            "Diego Maradona",
+            "diego.maradona@example.com",
+        ])
+        .assert()
+        .success();
+    ctx.git().args(["mob", "--clear"]).assert().success();
+
+    // verifying the history has an entry for each change
+    ctx.git()
+        .args(["mob", "history"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(
+            "^\\d{4}-\\d{2}-\\d{2} \\d{2}:\\d{2} start \\(global\\) .+: Leo Messi <leo.messi@example.com>, Emi Martinez <emi.martinez@example.com>\n\
+             \\d{4}-\\d{2}-\\d{2} \\d{2}:\\d{2} add \\(global\\) .+: Diego Maradona <diego.maradona@example.com>\n\
+             \\d{4}-\\d{2}-\\d{2} \\d{2}:\\d{2} cleared after 0m \\(global\\) .+: Leo Messi <leo.messi@example.com>, Emi Martinez <emi.martinez@example.com>, Diego Maradona <diego.maradona@example.com>\n$",
+        )?);
+
+    // verifying the history can be filtered by team member and printed as JSON
+    ctx.git()
+        .args([
+            "mob", "history", "--member", "em", "--since", "1d", "--format", "json",
+        ])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("\"event\": \"start\"")
+                .and(predicate::str::contains("\"event\": \"end\""))
+                .and(predicate::str::contains("\"ended\": \"cleared\""))
+                .and(predicate::str::contains("\"event\": \"add\"").not()),
+        );
+
+    // verifying the global --format can also be given before the command
+    ctx.git()
+        .args(["mob", "--format", "tsv", "history", "--member", "lm"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "timestamp\trepo\tscope\tevent\tended\tstarted_at\tmembers\n",
+        ));
+
+    Ok(())
+}
+
+#[test_context(TestContextCli, skip_teardown)]
+#[test]
+fn test_mob_history_given_invalid_since(ctx: TestContextCli) -> Result<(), Box<dyn Error>> {
+    ctx.git()
+        .args(["mob", "history", "--since", "last week"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Invalid date or duration: last week. Use a date like 2024-05-01 or a duration like 30m, 8h or 7d",
+        ));
+
+    Ok(())
+}
+
+#[test_context(TestContextRepo, skip_teardown)]
+#[test]
+fn test_mob_stats(ctx: TestContextRepo) -> Result<(), Box<dyn Error>> {
+    // given team members and commits co-authored by them
+    add_three_team_members(&ctx)?;
+    for trailers in [
+        "Co-authored-by: Leo Messi <leo.messi@example.com>",
+        "Co-authored-by: Leo Messi <leo.messi@example.com>\nCo-authored-by: Diego Maradona <diego.maradona@example.com>",
+        "",
+    ] {
+        ctx.git()
+            .args([
+                "commit",
+                "--allow-empty",
+                "--message",
+                &format!("commit\n\n{trailers}"),
+            ])
+            .assert()
+            .success();
+    }
+
+    // verifying the matrix counts commits for each pair and lists who has not paired
+    ctx.git()
+        .args(["mob", "stats"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "Commits paired on:\n\
+             \x20               cd  lm  em  Diego Maradona\n\
+             cd               -   2   0               1\n\
+             lm               2   -   0               1\n\
+             em               0   0   -               0\n\
+             Diego Maradona   1   1   0               -\n\
+             \n\
+             Not paired in the last 4 weeks:\n\
+             em\n",
+        ));
+
+    // verifying CSV output has a row for each pair of members
+    ctx.git()
+        .args(["mob", "stats", "--format", "csv"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::starts_with("member,paired_with,commits,last_paired_at\ncd,lm,2,")
+                .and(predicate::str::contains("\nem,cd,0,\n")),
+        );
+
+    Ok(())
+}
+
+#[test_context(TestContextRepo, skip_teardown)]
+#[test]
+fn test_mob_suggest_pair(ctx: TestContextRepo) -> Result<(), Box<dyn Error>> {
+    // given team members and a commit co-authored by one of them
+    add_three_team_members(&ctx)?;
+    ctx.git()
+        .args([
+            "commit",
+            "--allow-empty",
+            "--message",
+            "commit\n\nCo-authored-by: Leo Messi <leo.messi@example.com>",
+        ])
+        .assert()
+        .success();
+
+    // verifying team members never paired with come first
+    ctx.git()
+        .args(["mob", "suggest-pair"])
+        .stdin(Stdio::null())
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(
+            "^Suggested pairs for Cata Diaz <cata.diaz@example.com>, least recently paired first:\n\
+             em Emi Martinez <emi.martinez@example.com> \\(never paired\\)\n\
+             lm Leo Messi <leo.messi@example.com> \\(last paired \\d{4}-\\d{2}-\\d{2}\\)\n$",
+        )?);
+
+    // verifying suggestions can be for another team member
+    ctx.git()
+        .args(["mob", "suggest-pair", "--for", "em"])
+        .stdin(Stdio::null())
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "Suggested pairs for Emi Martinez <emi.martinez@example.com>, least recently paired first:\n\
+             cd Cata Diaz <cata.diaz@example.com> (never paired)\n\
+             lm Leo Messi <leo.messi@example.com> (never paired)\n",
+        ));
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test_context(TestContextRepo, skip_teardown)]
+#[test]
+fn test_mob_suggest_pair_when_select_suggestion(
+    ctx: TestContextRepo,
+) -> Result<(), Box<dyn Error>> {
+    add_three_team_members(&ctx)?;
+    ctx.git()
+        .args(["commit", "--allow-empty", "--message", "solo commit"])
+        .assert()
+        .success();
+
+    // running command to display suggested pairs prompt
+    let mut command = ctx.git();
+    command.args(["mob", "suggest-pair"]);
+
+    let mut session = spawn_command(command, Some(5000))?;
+    session.exp_string("Select who to pair with")?;
+
+    // pressing enter to accept the first suggestion
+    session.send_control('m')?;
+    session.process.wait()?;
+
+    // verifying mob session has the suggested team member
+    ctx.git()
+        .args(["mob", "--list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("Leo Messi <leo.messi@example.com>\n"));
+
+    Ok(())
+}
+
+#[test_context(TestContextCli, skip_teardown)]
+#[test]
+fn test_mob_schedule(ctx: TestContextCli) -> Result<(), Box<dyn Error>> {
+    add_two_team_members(&ctx)?;
+
+    ctx.git()
+        .args(["mob", "schedule", "--days", "2", "--seed", "42"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(
+            "^Day  Pair 1\n1    (lm, em|em, lm)\n2    (lm, em|em, lm)\n\nGenerated with --seed 42\n$",
+        )?);
+
+    ctx.git()
+        .args(["mob", "schedule", "--members", "lm,jk"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::diff(
+            "Error: \"No team member found with key: jk\"\n",
+        ));
+
+    ctx.git()
+        .args(["mob", "schedule", "--days", "367"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "invalid value '367' for '--days <DAYS>': 367 is not in 1..=366",
+        ));
+
+    Ok(())
+}
+
+#[test_context(TestContextRepo, skip_teardown)]
+#[test]
+fn test_mob_with_team_group(ctx: TestContextRepo) -> Result<(), Box<dyn Error>> {
+    add_three_team_members(&ctx)?;
+
+    ctx.git()
+        .args(["mob", "team", "--add", "frontend", "cd", "lm", "em"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("@frontend (cd, lm, em)\n"));
+
+    ctx.git()
+        .args(["mob", "team", "--list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("@frontend cd lm em\n"));
+
+    // mobbing with the team group without the current user
+    ctx.git()
+        .args(["mob", "--with", "@frontend", "-cd", "--local"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "Leo Messi <leo.messi@example.com>\nEmi Martinez <emi.martinez@example.com>\n",
+        ));
+
+    ctx.git()
+        .args(["mob", "--list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "Leo Messi <leo.messi@example.com>\nEmi Martinez <emi.martinez@example.com>\n",
+        ));
+
+    ctx.git()
+        .args(["mob", "team", "--delete", "frontend"])
+        .assert()
+        .success();
+
+    ctx.git()
+        .args(["mob", "--with", "@frontend"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::diff(
+            "Error: \"No team group found with name: frontend\"\n",
+        ));
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test_context(TestContextCli, skip_teardown)]
+#[test]
+fn test_mob_with_multiselect_when_select_team_group(
+    ctx: TestContextCli,
+) -> Result<(), Box<dyn Error>> {
+    add_two_team_members(&ctx)?;
+    ctx.git()
+        .args(["mob", "team", "--add", "pair", "lm", "em"])
+        .assert()
+        .success();
+
+    // running command to display mob session multiselect prompt
+    let mut command = ctx.git();
+    command.args(["mob", "--with"]);
+
+    let mut session = spawn_command(command, Some(5000))?;
+    session.exp_string("Select active co-author(s)")?;
+    session.exp_string("@pair (lm, em)")?;
+
+    // pressing space to select the team group listed first
+    session.send(" ")?;
+    session.flush()?;
+
+    // pressing enter to end prompt
+    session.send_control('m')?;
+    session.process.wait()?;
+
+    // verifying mob session has the team members of the selected team group
+    ctx.git()
+        .args(["mob", "--list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "Leo Messi <leo.messi@example.com>\nEmi Martinez <emi.martinez@example.com>\n",
+        ));
+
+    Ok(())
+}
+
+#[test_context(TestContextCli, skip_teardown)]
+#[test]
+fn test_mob_preset(ctx: TestContextCli) -> Result<(), Box<dyn Error>> {
+    add_two_team_members(&ctx)?;
+    ctx.git().args(["mob", "--with", "lm"]).assert().success();
+    ctx.git()
+        .args([
+            "mob",
+            "--add",
+            "Diego Maradona",
+            "diego.maradona@example.com",
+        ])
+        .assert()
+        .success();
+
+    ctx.git()
+        .args(["mob", "preset", "save", "payments-mob"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "Leo Messi <leo.messi@example.com>\nDiego Maradona <diego.maradona@example.com>\n",
+        ));
+
+    ctx.git()
+        .args(["mob", "preset", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "payments-mob: Leo Messi <leo.messi@example.com>, Diego Maradona <diego.maradona@example.com>\n",
+        ));
+
+    // restoring the preset after mobbing with someone else
+    ctx.git().args(["mob", "--with", "em"]).assert().success();
+    ctx.git()
+        .args(["mob", "preset", "load", "payments-mob"])
+        .assert()
+        .success();
+
+    ctx.git()
+        .args(["mob", "--list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "Leo Messi <leo.messi@example.com>\nDiego Maradona <diego.maradona@example.com>\n",
+        ));
+
+    ctx.git()
+        .args(["mob", "preset", "delete", "payments-mob"])
+        .assert()
+        .success();
+    ctx.git()
+        .args(["mob", "preset", "load", "payments-mob"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::diff(
+            "Error: \"No preset found with name: payments-mob\"\n",
+        ));
+
+    Ok(())
+}
+
 fn add_two_team_members(ctx: &TestContextCli) -> Result<(), Box<dyn Error>> {
     ctx.git()
         .args([
@@ -332,3 +1001,18 @@ fn add_two_team_members(ctx: &TestContextCli) -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
+
+fn add_three_team_members(ctx: &TestContextRepo) -> Result<(), Box<dyn Error>> {
+    for (key, name, email) in [
+        ("cd", "Cata Diaz", "cata.diaz@example.com"),
+        ("lm", "Leo Messi", "leo.messi@example.com"),
+        ("em", "Emi Martinez", "emi.martinez@example.com"),
+    ] {
+        ctx.git()
+            .args(["mob", "team-member", "--add", key, name, email])
+            .assert()
+            .success();
+    }
+
+    Ok(())
+}
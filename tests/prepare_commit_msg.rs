@@ -3,7 +3,7 @@ mod helpers;
 use assert_cmd::prelude::*;
 use helpers::test_contexts::TestContextRepo;
 use predicates::prelude::*;
-use std::error::Error;
+use std::{error::Error, fs};
 use tempfile::TempDir;
 use test_context::test_context;
 
@@ -161,3 +161,485 @@ fn test_prepare_commit_msg_given_local_hooks_directory(
 
     Ok(())
 }
+
+#[test_context(TestContextRepo, skip_teardown)]
+#[test]
+fn test_prepare_commit_msg_given_existing_trailers(
+    ctx: TestContextRepo,
+) -> Result<(), Box<dyn Error>> {
+    ctx.git()
+        .args(["mob", "setup", "--global"])
+        .assert()
+        .success();
+
+    ctx.git()
+        .args(["mob", "--add", "Leo Messi", "leo.messi@example.com"])
+        .assert()
+        .success();
+
+    // committing with a message which already has a trailer block including the co-author
+    ctx.git()
+        .args([
+            "commit",
+            "--allow-empty",
+            "--message",
+            "test: hello world!\n\n\
+             Signed-off-by: Cata Diaz <cata.diaz@example.com>\n\
+             Co-authored-by: Leo Messi <leo.messi@example.com>",
+        ])
+        .assert()
+        .success();
+
+    // the Co-authored-by trailer is not duplicated
+    ctx.git()
+        .args(["show", "--no-patch", "--format=%B"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "test: hello world!\n\n\
+            Signed-off-by: Cata Diaz <cata.diaz@example.com>\n\
+            Co-authored-by: Leo Messi <leo.messi@example.com>\n\n",
+        ));
+
+    // amending without a message does not add the trailers again
+    ctx.git()
+        .args(["commit", "--amend", "--no-edit", "--allow-empty"])
+        .assert()
+        .success()
+        .stderr(predicate::str::diff(""));
+
+    Ok(())
+}
+
+#[test_context(TestContextRepo, skip_teardown)]
+#[test]
+fn test_prepare_commit_msg_given_local_mob_session(
+    ctx: TestContextRepo,
+) -> Result<(), Box<dyn Error>> {
+    ctx.git()
+        .args(["mob", "setup", "--global"])
+        .assert()
+        .success();
+
+    // global and local mob sessions with different co-authors
+    ctx.git()
+        .args(["mob", "--add", "Leo Messi", "leo.messi@example.com"])
+        .assert()
+        .success();
+
+    ctx.git()
+        .args([
+            "mob",
+            "--add",
+            "Emi Martinez",
+            "emi.martinez@example.com",
+            "--local",
+        ])
+        .assert()
+        .success();
+
+    // only the co-author from the local mob session is added
+    ctx.git()
+        .args(["commit", "--allow-empty", "--message", "test: hello world!"])
+        .assert()
+        .success()
+        .stderr(predicate::str::diff(
+            "Co-authored-by: Emi Martinez <emi.martinez@example.com>\n\n",
+        ));
+
+    Ok(())
+}
+
+#[test_context(TestContextRepo, skip_teardown)]
+#[test]
+fn test_prepare_commit_msg_given_coauthor_as_author(
+    ctx: TestContextRepo,
+) -> Result<(), Box<dyn Error>> {
+    ctx.git()
+        .args(["mob", "setup", "--global"])
+        .assert()
+        .success();
+
+    ctx.git()
+        .args(["mob", "--add", "Leo Messi", "leo.messi@example.com"])
+        .assert()
+        .success();
+
+    ctx.git()
+        .args(["mob", "--add", "Emi Martinez", "emi.martinez@example.com"])
+        .assert()
+        .success();
+
+    // the author is not added as a co-author, even with a differently cased email
+    ctx.git()
+        .args([
+            "commit",
+            "--allow-empty",
+            "--message",
+            "test: hello world!",
+            "--author",
+            "Leo Messi <Leo.Messi@example.com>",
+        ])
+        .assert()
+        .success()
+        .stderr(predicate::str::diff(
+            "Co-authored-by: Emi Martinez <emi.martinez@example.com>\n\n",
+        ));
+
+    ctx.git()
+        .args(["show", "--no-patch", "--format=%an <%ae>%n%B"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "Leo Messi <Leo.Messi@example.com>\n\
+             test: hello world!\n\n\
+             Co-authored-by: Emi Martinez <emi.martinez@example.com>\n\n",
+        ));
+
+    Ok(())
+}
+
+#[test_context(TestContextRepo, skip_teardown)]
+#[test]
+fn test_prepare_commit_msg_given_driver(ctx: TestContextRepo) -> Result<(), Box<dyn Error>> {
+    ctx.git()
+        .args(["mob", "setup", "--global"])
+        .assert()
+        .success();
+    for (key, name, email) in [
+        ("lm", "Leo Messi", "leo.messi@example.com"),
+        ("em", "Emi Martinez", "emi.martinez@example.com"),
+    ] {
+        ctx.git()
+            .args(["mob", "team-member", "--add", key, name, email])
+            .assert()
+            .success();
+    }
+
+    ctx.git()
+        .args(["mob", "--with", "lm", "em", "--local"])
+        .assert()
+        .success();
+
+    // the driver is removed from the co-authors
+    ctx.git()
+        .args(["mob", "--driver", "lm", "--local"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "Driver: Leo Messi <leo.messi@example.com>\n",
+        ));
+
+    ctx.git()
+        .args(["mob", "--list", "--verbose"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "Mob session (local):\nDriver: Leo Messi <leo.messi@example.com>\nNavigator: Emi Martinez <emi.martinez@example.com>\n",
+        ));
+
+    // the driver is the author of the commit
+    ctx.git()
+        .args(["commit", "--allow-empty", "--message", "test: hello world!"])
+        .assert()
+        .success()
+        .stderr(predicate::str::diff(
+            "Co-authored-by: Emi Martinez <emi.martinez@example.com>\n\n",
+        ));
+
+    ctx.git()
+        .args(["log", "-1", "--format=%an <%ae>"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("Leo Messi <leo.messi@example.com>\n"));
+
+    // the previous driver becomes a co-author when switching drivers
+    ctx.git()
+        .args(["mob", "--driver", "em", "--local"])
+        .assert()
+        .success();
+
+    ctx.git()
+        .args(["mob", "--trailers"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "Co-authored-by: Leo Messi <leo.messi@example.com>\n",
+        ));
+
+    // clearing the session restores the original author
+    ctx.git()
+        .args(["mob", "--clear", "--local"])
+        .assert()
+        .success();
+
+    ctx.git()
+        .args(["commit", "--allow-empty", "--message", "test: solo"])
+        .assert()
+        .success();
+
+    ctx.git()
+        .args(["log", "-1", "--format=%an <%ae>"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("Cata Diaz <cata.diaz@example.com>\n"));
+
+    Ok(())
+}
+
+#[test_context(TestContextRepo, skip_teardown)]
+#[test]
+fn test_prepare_commit_msg_when_session_with_driver_expired(
+    ctx: TestContextRepo,
+) -> Result<(), Box<dyn Error>> {
+    ctx.git()
+        .args(["mob", "setup", "--global"])
+        .assert()
+        .success();
+    ctx.git()
+        .args(["config", "--local", "author.name", "Cata"])
+        .assert()
+        .success();
+    ctx.git()
+        .args([
+            "mob",
+            "team-member",
+            "--add",
+            "lm",
+            "Leo Messi",
+            "leo.messi@example.com",
+        ])
+        .assert()
+        .success();
+
+    ctx.git()
+        .args(["mob", "--driver", "lm", "--local", "--expire-after", "8h"])
+        .assert()
+        .success();
+
+    // pretending the mob session started a day ago
+    let started_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)?
+        .as_secs()
+        - 24 * 60 * 60;
+    ctx.git()
+        .args([
+            "config",
+            "--local",
+            "coauthors-mob.started",
+            &started_at.to_string(),
+        ])
+        .assert()
+        .success();
+
+    // the commit already has the driver as its author, so the user is told how to reset it
+    ctx.git()
+        .args(["commit", "--allow-empty", "--message", "test: hello world!"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Its driver Leo Messi <leo.messi@example.com> is still the author of this commit, \
+             which git commit --amend --reset-author changes to you",
+        ));
+
+    // the user's own author identity is restored
+    ctx.git()
+        .args(["config", "--local", "author.name"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("Cata\n"));
+
+    // the end of the expired mob session is recorded in the history
+    ctx.git()
+        .args(["mob", "history"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(
+            " expired after 24h \\(local\\) .+: Leo Messi <leo.messi@example.com>\n$",
+        )?);
+
+    ctx.git()
+        .args(["log", "-1", "--format=%an <%ae>"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("Leo Messi <leo.messi@example.com>\n"));
+
+    ctx.git()
+        .args([
+            "commit",
+            "--amend",
+            "--allow-empty",
+            "--reset-author",
+            "--no-edit",
+        ])
+        .assert()
+        .success();
+
+    ctx.git()
+        .args(["log", "-1", "--format=%an <%ae>"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("Cata <cata.diaz@example.com>\n"));
+
+    Ok(())
+}
+
+#[test_context(TestContextRepo, skip_teardown)]
+#[test]
+fn test_pingpong(ctx: TestContextRepo) -> Result<(), Box<dyn Error>> {
+    ctx.git().args(["mob", "setup"]).assert().success();
+    for (key, name, email) in [
+        ("lm", "Leo Messi", "leo.messi@example.com"),
+        ("em", "Emi Martinez", "emi.martinez@example.com"),
+    ] {
+        ctx.git()
+            .args(["mob", "team-member", "--add", key, name, email])
+            .assert()
+            .success();
+    }
+
+    ctx.git()
+        .args(["mob", "pingpong", "lm", "em", "--local"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "Ping-pong mode (local):\n\
+             Driver: Leo Messi <leo.messi@example.com>\n\
+             Navigator: Emi Martinez <emi.martinez@example.com>\n",
+        ));
+
+    // the start of the ping-pong mob session is recorded in the history
+    ctx.git()
+        .args(["mob", "history"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(
+            " start \\(local\\) .+: Leo Messi <leo.messi@example.com>, Emi Martinez <emi.martinez@example.com>\n$",
+        )?);
+
+    // attribution alternates after every commit
+    for (message, author, coauthor) in [
+        ("test: failing test", "Leo Messi", "Emi Martinez"),
+        ("feat: pass the test", "Emi Martinez", "Leo Messi"),
+        ("test: another failing test", "Leo Messi", "Emi Martinez"),
+    ] {
+        ctx.git()
+            .args(["commit", "--allow-empty", "--message", message])
+            .assert()
+            .success();
+
+        ctx.git()
+            .args([
+                "log",
+                "-1",
+                "--format=%an%n%(trailers:key=Co-authored-by,valueonly)",
+            ])
+            .assert()
+            .success()
+            .stdout(predicate::str::starts_with(format!(
+                "{author}\n{coauthor} <"
+            )));
+    }
+
+    Ok(())
+}
+
+#[test_context(TestContextRepo, skip_teardown)]
+#[test]
+fn test_prepare_commit_msg_given_default_preset(
+    ctx: TestContextRepo,
+) -> Result<(), Box<dyn Error>> {
+    ctx.git()
+        .args(["mob", "setup", "--global"])
+        .assert()
+        .success();
+
+    // saving a session with a non-team member as the default preset of the repository
+    ctx.git()
+        .args([
+            "mob",
+            "--add",
+            "Diego Maradona",
+            "diego.maradona@example.com",
+        ])
+        .assert()
+        .success();
+    ctx.git()
+        .args(["mob", "preset", "save", "payments-mob", "--default"])
+        .assert()
+        .success();
+
+    // committing with no session
+    ctx.git().args(["mob", "--clear"]).assert().success();
+    ctx.git()
+        .args(["commit", "--allow-empty", "--message", "test: hello world!"])
+        .assert()
+        .success()
+        .stderr(predicate::str::diff(
+            "Co-authored-by: Diego Maradona <diego.maradona@example.com>\n\n",
+        ));
+
+    // no longer using the default preset
+    ctx.git()
+        .args(["mob", "preset", "default", "--unset"])
+        .assert()
+        .success();
+    ctx.git()
+        .args(["commit", "--allow-empty", "--message", "test: solo"])
+        .assert()
+        .success()
+        .stderr(predicate::str::diff(""));
+
+    Ok(())
+}
+
+#[test_context(TestContextRepo, skip_teardown)]
+#[test]
+fn test_pingpong_given_foreign_post_commit_hook(
+    ctx: TestContextRepo,
+) -> Result<(), Box<dyn Error>> {
+    ctx.git().args(["mob", "setup"]).assert().success();
+    for (key, name, email) in [
+        ("lm", "Leo Messi", "leo.messi@example.com"),
+        ("em", "Emi Martinez", "emi.martinez@example.com"),
+    ] {
+        ctx.git()
+            .args(["mob", "team-member", "--add", key, name, email])
+            .assert()
+            .success();
+    }
+
+    // overriding the hooks directory with one which has a post-commit githook of another tool
+    let temp_dir = TempDir::new()?;
+    let hooks_dir = temp_dir.path().to_path_buf();
+    let post_commit_path = hooks_dir.join("post-commit");
+    let existing_hook_contents = "#!/bin/sh\nnpx lint-staged\n";
+    fs::write(&post_commit_path, existing_hook_contents)?;
+    ctx.git()
+        .args([
+            "config",
+            "--local",
+            "core.hooksPath",
+            &hooks_dir.to_string_lossy(),
+        ])
+        .assert()
+        .success();
+
+    ctx.git()
+        .args(["mob", "pingpong", "lm", "em", "--local"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::diff(format!(
+            "Error: \"Post-commit githook does not invoke git mob: {}. Add `git mob hook post-commit` to it to use ping-pong mode\"\n",
+            post_commit_path.to_string_lossy()
+        )));
+
+    // verifying the existing post-commit githook is left untouched
+    assert_eq!(
+        fs::read_to_string(&post_commit_path)?,
+        existing_hook_contents
+    );
+    assert!(!hooks_dir.join("post-commit.bak").exists());
+
+    Ok(())
+}
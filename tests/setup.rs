@@ -19,10 +19,12 @@ fn test_setup_given_hooks_dir_not_set(ctx: TestContextRepo) -> Result<(), Box<dy
         .stdout(predicate::str::diff(format!(
             r#"Set global githooks directory: {}
 Created new prepare-commit-msg githook: {}
+Created new post-commit githook: {}
 Setup complete
 "#,
             hooks_dir.to_string_lossy(),
-            hooks_dir.join("prepare-commit-msg").to_string_lossy()
+            hooks_dir.join("prepare-commit-msg").to_string_lossy(),
+            hooks_dir.join("post-commit").to_string_lossy()
         )));
 
     verify_prepare_commit_msg_global_hook(&ctx, &hooks_dir)
@@ -51,9 +53,11 @@ fn test_setup_given_hooks_dir_set_and_exists(ctx: TestContextRepo) -> Result<(),
         .success()
         .stdout(predicate::str::diff(format!(
             r#"Created new prepare-commit-msg githook: {}
+Created new post-commit githook: {}
 Setup complete
 "#,
-            hooks_dir.join("prepare-commit-msg").to_string_lossy()
+            hooks_dir.join("prepare-commit-msg").to_string_lossy(),
+            hooks_dir.join("post-commit").to_string_lossy()
         )));
 
     verify_prepare_commit_msg_global_hook(&ctx, &hooks_dir)
@@ -88,9 +92,11 @@ fn test_setup_given_hooks_dir_set_but_does_not_exist(
         .success()
         .stdout(predicate::str::diff(format!(
             r#"Created new prepare-commit-msg githook: {}
+Created new post-commit githook: {}
 Setup complete
 "#,
-            hooks_dir.join("prepare-commit-msg").to_string_lossy()
+            hooks_dir.join("prepare-commit-msg").to_string_lossy(),
+            hooks_dir.join("post-commit").to_string_lossy()
         )));
 
     verify_prepare_commit_msg_global_hook(&ctx, &hooks_dir)
@@ -121,11 +127,13 @@ fn test_setup_given_hooks_dir_set_and_starts_with_tilde(
         .success()
         .stdout(predicate::str::diff(format!(
             r#"Created new prepare-commit-msg githook: {}
+Created new post-commit githook: {}
 Setup complete
 "#,
             expanded_hooks_dir
                 .join("prepare-commit-msg")
-                .to_string_lossy()
+                .to_string_lossy(),
+            expanded_hooks_dir.join("post-commit").to_string_lossy()
         )));
 
     verify_prepare_commit_msg_global_hook(&ctx, hooks_dir)
@@ -163,10 +171,12 @@ fn test_setup_given_prepare_commit_msg_hook_already_exists(
         .stdout(predicate::str::diff(format!(
             r#"Backed up existing prepare-commit-msg githook: {}
 Created new prepare-commit-msg githook: {}
+Created new post-commit githook: {}
 Setup complete
 "#,
             backup_path.to_string_lossy(),
-            hook_path.to_string_lossy()
+            hook_path.to_string_lossy(),
+            hooks_dir.join("post-commit").to_string_lossy()
         )));
 
     // verifying existing prepare-commit-msg is backed up as prepare-commit-msg.bak
@@ -233,9 +243,11 @@ fn test_setup_local_given_hooks_dir_set_and_exists(
         .success()
         .stdout(predicate::str::diff(format!(
             r#"Created new prepare-commit-msg githook: {}
+Created new post-commit githook: {}
 Setup complete
 "#,
-            hooks_dir.join("prepare-commit-msg").to_string_lossy()
+            hooks_dir.join("prepare-commit-msg").to_string_lossy(),
+            hooks_dir.join("post-commit").to_string_lossy()
         )));
 
     verify_prepare_commit_msg_local_hook(&ctx, &hooks_dir)
@@ -270,9 +282,11 @@ fn test_setup_local_given_hooks_dir_set_but_does_not_exist(
         .success()
         .stdout(predicate::str::diff(format!(
             r#"Created new prepare-commit-msg githook: {}
+Created new post-commit githook: {}
 Setup complete
 "#,
-            hooks_dir.join("prepare-commit-msg").to_string_lossy()
+            hooks_dir.join("prepare-commit-msg").to_string_lossy(),
+            hooks_dir.join("post-commit").to_string_lossy()
         )));
 
     verify_prepare_commit_msg_local_hook(&ctx, &hooks_dir)
@@ -303,11 +317,13 @@ fn test_setup_local_given_hooks_dir_set_and_starts_with_tilde(
         .success()
         .stdout(predicate::str::diff(format!(
             r#"Created new prepare-commit-msg githook: {}
+Created new post-commit githook: {}
 Setup complete
 "#,
             expanded_hooks_dir
                 .join("prepare-commit-msg")
-                .to_string_lossy()
+                .to_string_lossy(),
+            expanded_hooks_dir.join("post-commit").to_string_lossy()
         )));
 
     verify_prepare_commit_msg_local_hook(&ctx, hooks_dir)
@@ -345,10 +361,12 @@ fn test_setup_local_given_prepare_commit_msg_hook_already_exists(
         .stdout(predicate::str::diff(format!(
             r#"Backed up existing prepare-commit-msg githook: {}
 Created new prepare-commit-msg githook: {}
+Created new post-commit githook: {}
 Setup complete
 "#,
             backup_path.to_string_lossy(),
-            hook_path.to_string_lossy()
+            hook_path.to_string_lossy(),
+            hooks_dir.join("post-commit").to_string_lossy()
         )));
 
     // verifying existing prepare-commit-msg is backed up as prepare-commit-msg.bak
@@ -361,6 +379,52 @@ Setup complete
     verify_prepare_commit_msg_local_hook(&ctx, &hooks_dir)
 }
 
+#[test_context(TestContextRepo, skip_teardown)]
+#[test]
+fn test_setup_local_given_post_commit_hook_already_exists(
+    ctx: TestContextRepo,
+) -> Result<(), Box<dyn Error>> {
+    let temp_dir = TempDir::new()?;
+    let hooks_dir = temp_dir.path().to_path_buf();
+
+    let post_commit_path = hooks_dir.join("post-commit");
+    let existing_hook_contents = "#!/bin/sh\nnpx lint-staged\n";
+    fs::write(&post_commit_path, existing_hook_contents.as_bytes())?;
+
+    // setting local hooks directory
+    ctx.git()
+        .args([
+            "config",
+            "--local",
+            "core.hooksPath",
+            &hooks_dir.to_string_lossy(),
+        ])
+        .assert()
+        .success();
+
+    ctx.git()
+        .args(["mob", "setup", "--local"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(format!(
+            r#"Created new prepare-commit-msg githook: {}
+Skipped existing post-commit githook: {}. Add `git mob hook post-commit` to it to use ping-pong mode
+Setup complete
+"#,
+            hooks_dir.join("prepare-commit-msg").to_string_lossy(),
+            post_commit_path.to_string_lossy()
+        )));
+
+    // verifying existing post-commit is left untouched
+    assert_eq!(
+        fs::read_to_string(&post_commit_path)?,
+        existing_hook_contents
+    );
+    assert!(!hooks_dir.join("post-commit.bak").exists());
+
+    verify_prepare_commit_msg_local_hook(&ctx, &hooks_dir)
+}
+
 fn verify_prepare_commit_msg_global_hook(
     ctx: &TestContextRepo,
     hooks_dir: &Path,
@@ -1,9 +1,11 @@
 mod helpers;
 
 use assert_cmd::prelude::*;
-use helpers::test_contexts::TestContextCli;
+use helpers::test_contexts::{TestContextCli, TestContextRepo};
 use predicates::prelude::*;
-use std::error::Error;
+#[cfg(unix)]
+use rexpect::session::spawn_command;
+use std::{error::Error, fs};
 use test_context::test_context;
 
 #[test_context(TestContextCli, skip_teardown)]
@@ -44,7 +46,7 @@ fn test_add_and_list_team_members(ctx: TestContextCli) -> Result<(), Box<dyn Err
         .assert()
         .success()
         .stdout(predicate::str::diff(
-            "lm Leo Messi <leo.messi@example.com>\nem Emi Martinez <emi.martinez@example.com>\n",
+            "lm Leo Messi <leo.messi@example.com> (user)\nem Emi Martinez <emi.martinez@example.com> (user)\n",
         ));
 
     Ok(())
@@ -65,7 +67,98 @@ fn test_add_member_with_invalid_key(ctx: TestContextCli) -> Result<(), Box<dyn E
         .assert()
         .failure()
         .stderr(predicate::str::diff(
-            "Error: \"Invalid key: invalid_key_with_underscore\"\n",
+            "Error: \"Invalid key: invalid_key_with_underscore. It must start with a letter and only contain letters, digits and hyphens. Use --allow-unusual if it is intended\"\n",
+        ));
+
+    Ok(())
+}
+
+#[test_context(TestContextCli, skip_teardown)]
+#[test]
+fn test_add_member_with_invalid_email(ctx: TestContextCli) -> Result<(), Box<dyn Error>> {
+    ctx.git()
+        .args([
+            "mob",
+            "team-member",
+            "--add",
+            "lm",
+            "Leo Messi",
+            "<leo.messi@example.com>",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::diff(
+            "Error: \"Invalid email: <leo.messi@example.com>. It must not contain < or >\"\n",
+        ));
+
+    Ok(())
+}
+
+#[test_context(TestContextCli, skip_teardown)]
+#[test]
+fn test_add_member_with_unusual_email(ctx: TestContextCli) -> Result<(), Box<dyn Error>> {
+    ctx.git()
+        .args(["mob", "team-member", "--add", "lm", "Leo Messi", "leo@localhost"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::diff(
+            "Error: \"Invalid email: leo@localhost. The domain after @ must be a domain name like example.com. Use --allow-unusual if it is intended\"\n",
+        ));
+
+    ctx.git()
+        .args([
+            "mob",
+            "team-member",
+            "--add",
+            "lm",
+            " Leo Messi ",
+            "leo@localhost",
+            "--allow-unusual",
+        ])
+        .assert()
+        .success();
+
+    ctx.git()
+        .args(["mob", "team-member", "--list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "lm Leo Messi <leo@localhost> (user)\n",
+        ));
+
+    Ok(())
+}
+
+#[test_context(TestContextCli, skip_teardown)]
+#[test]
+fn test_list_team_members_given_invalid_team_member(
+    ctx: TestContextCli,
+) -> Result<(), Box<dyn Error>> {
+    ctx.git()
+        .args(["config", "--global", "coauthors.lm", "Leo Messi"])
+        .assert()
+        .success();
+
+    ctx.git()
+        .args([
+            "config",
+            "--global",
+            "coauthors.em",
+            "Emi Martinez <emi.martinez@example.com>",
+        ])
+        .assert()
+        .success();
+
+    // the invalid team member is skipped so that the others can still be used
+    ctx.git()
+        .args(["mob", "team-member", "--list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "em Emi Martinez <emi.martinez@example.com> (user)\n",
+        ))
+        .stderr(predicate::str::diff(
+            "Warning: Skipped Invalid team member with key lm: Leo Messi. Fix or remove it with git config --global coauthors.lm\n",
         ));
 
     Ok(())
@@ -85,6 +178,58 @@ fn test_list_team_members_given_no_team_members_added(
     Ok(())
 }
 
+#[test_context(TestContextCli, skip_teardown)]
+#[test]
+fn test_list_team_members_given_json_format(ctx: TestContextCli) -> Result<(), Box<dyn Error>> {
+    ctx.git()
+        .args([
+            "mob",
+            "team-member",
+            "--add",
+            "lm",
+            "Leo Messi",
+            "leo.messi@example.com",
+        ])
+        .assert()
+        .success();
+    ctx.git()
+        .args(["mob", "team-member", "--alias", "leo", "lm"])
+        .assert()
+        .success();
+
+    ctx.git()
+        .args(["mob", "team-member", "--list", "--format", "json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            r#"{
+  "version": 1,
+  "team_members": [
+    {
+      "key": "lm",
+      "name": "Leo Messi",
+      "email": "leo.messi@example.com",
+      "source": "user",
+      "aliases": [
+        "leo"
+      ]
+    }
+  ]
+}
+"#,
+        ));
+
+    ctx.git()
+        .args(["mob", "team-member", "--list", "--format", "git-coauthors"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "invalid value 'git-coauthors' for '--format <FORMAT>'",
+        ));
+
+    Ok(())
+}
+
 #[test_context(TestContextCli, skip_teardown)]
 #[test]
 fn test_delete_team_members(ctx: TestContextCli) -> Result<(), Box<dyn Error>> {
@@ -125,7 +270,7 @@ fn test_delete_team_members(ctx: TestContextCli) -> Result<(), Box<dyn Error>> {
         .assert()
         .success()
         .stdout(predicate::str::diff(
-            "em Emi Martinez <emi.martinez@example.com>\n",
+            "em Emi Martinez <emi.martinez@example.com> (user)\n",
         ));
 
     Ok(())
@@ -144,3 +289,687 @@ fn test_delete_member_when_member_not_found(ctx: TestContextCli) -> Result<(), B
 
     Ok(())
 }
+
+#[test_context(TestContextRepo, skip_teardown)]
+#[test]
+fn test_team_members_given_roster_file(ctx: TestContextRepo) -> Result<(), Box<dyn Error>> {
+    let roster_file = ctx.dir().join(".git-mob.toml");
+    fs::write(
+        &roster_file,
+        "[team-members]\nlm = { name = \"Leo Messi\", email = \"leo.messi@example.com\" }\n",
+    )?;
+
+    // adding team member to the roster file
+    ctx.git()
+        .args([
+            "mob",
+            "team-member",
+            "--add",
+            "em",
+            "Emi Martinez",
+            "emi.martinez@example.com",
+            "--repo",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "Emi Martinez <emi.martinez@example.com>\n",
+        ));
+
+    // adding team member to the user's global team members by default
+    ctx.git()
+        .args([
+            "mob",
+            "team-member",
+            "--add",
+            "rdp",
+            "Rodrigo de Paul",
+            "rodrigo.depaul@example.com",
+        ])
+        .assert()
+        .success();
+
+    assert_eq!(
+        fs::read_to_string(&roster_file)?,
+        "[team-members]\nlm = { name = \"Leo Messi\", email = \"leo.messi@example.com\" }\nem = { name = \"Emi Martinez\", email = \"emi.martinez@example.com\" }\n"
+    );
+
+    ctx.git()
+        .args(["mob", "team-member", "--list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "lm Leo Messi <leo.messi@example.com> (repo)\nem Emi Martinez <emi.martinez@example.com> (repo)\nrdp Rodrigo de Paul <rodrigo.depaul@example.com> (user)\n",
+        ));
+
+    // the user's global team member can be deleted while the roster file exists
+    ctx.git()
+        .args(["mob", "team-member", "--delete", "rdp"])
+        .assert()
+        .success();
+
+    // keys are resolved from the roster file
+    ctx.git()
+        .args(["mob", "--with", "lm", "em"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "Leo Messi <leo.messi@example.com>\nEmi Martinez <emi.martinez@example.com>\n",
+        ));
+
+    Ok(())
+}
+
+#[test_context(TestContextRepo, skip_teardown)]
+#[test]
+fn test_team_members_given_layered_rosters(ctx: TestContextRepo) -> Result<(), Box<dyn Error>> {
+    fs::write(
+        ctx.dir().join(".git-mob.toml"),
+        "hidden-team-members = [\"jf\"]\n\n[team-members]\nlm = { name = \"Lionel Messi\", email = \"lionel.messi@example.com\" }\n",
+    )?;
+    let system_roster_file = ctx.home_dir.path().join("roster.toml");
+    fs::write(
+        &system_roster_file,
+        "[team-members]\nlm = { name = \"Leo Messi\", email = \"leo.messi@example.com\" }\njf = { name = \"Julian Fernandez\", email = \"julian.fernandez@example.com\" }\nad = { name = \"Angel Di Maria\", email = \"angel.dimaria@example.com\" }\n",
+    )?;
+    ctx.git()
+        .args([
+            "config",
+            "--global",
+            "coauthors.em",
+            "Emi Martinez <emi.martinez@example.com>",
+        ])
+        .assert()
+        .success();
+
+    // closer layers override and hide team members of further layers
+    ctx.git()
+        .args(["mob", "team-member", "--list"])
+        .env("GIT_MOB_SYSTEM_ROSTER", &system_roster_file)
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "lm Lionel Messi <lionel.messi@example.com> (repo)\nem Emi Martinez <emi.martinez@example.com> (user)\nad Angel Di Maria <angel.dimaria@example.com> (system)\n",
+        ));
+
+    ctx.git()
+        .args(["mob", "--with", "lm", "em", "ad"])
+        .env("GIT_MOB_SYSTEM_ROSTER", &system_roster_file)
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "Lionel Messi <lionel.messi@example.com>\nEmi Martinez <emi.martinez@example.com>\nAngel Di Maria <angel.dimaria@example.com>\n",
+        ));
+
+    ctx.git()
+        .args(["mob", "--with", "jf"])
+        .env("GIT_MOB_SYSTEM_ROSTER", &system_roster_file)
+        .assert()
+        .failure()
+        .stderr(predicate::str::diff(
+            "Error: \"No team member found with key: jf\"\n",
+        ));
+
+    // team member of a further layer can only be hidden
+    ctx.git()
+        .args(["mob", "team-member", "--delete", "ad"])
+        .env("GIT_MOB_SYSTEM_ROSTER", &system_roster_file)
+        .assert()
+        .failure()
+        .stderr(predicate::str::diff(
+            "Error: \"Team member with key ad comes from the system roster and can only be hidden\"\n",
+        ));
+
+    ctx.git()
+        .args(["mob", "team-member", "--hide", "ad"])
+        .env("GIT_MOB_SYSTEM_ROSTER", &system_roster_file)
+        .assert()
+        .success();
+
+    ctx.git()
+        .args(["mob", "team-member", "--list"])
+        .env("GIT_MOB_SYSTEM_ROSTER", &system_roster_file)
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "lm Lionel Messi <lionel.messi@example.com> (repo)\nem Emi Martinez <emi.martinez@example.com> (user)\n",
+        ));
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test_context(TestContextRepo, skip_teardown)]
+#[test]
+fn test_import_team_members_from_log(ctx: TestContextRepo) -> Result<(), Box<dyn Error>> {
+    // given commits authored and co-authored by team members
+    ctx.git()
+        .args([
+            "commit",
+            "--allow-empty",
+            "--author",
+            "Leo Messi <leo.messi@example.com>",
+            "--message",
+            "first commit\n\nCo-authored-by: Emi Martinez <emi.martinez@example.com>",
+        ])
+        .assert()
+        .success();
+    ctx.git()
+        .args([
+            "commit",
+            "--allow-empty",
+            "--message",
+            "second commit\n\nCo-authored-by: Leo Messi <leo.messi@example.com>",
+        ])
+        .assert()
+        .success();
+    ctx.git()
+        .args([
+            "config",
+            "--global",
+            "coauthors.cd",
+            "Cata Diaz <cata.diaz@example.com>",
+        ])
+        .assert()
+        .success();
+
+    // running command to display import multiselect prompt
+    let mut command = ctx.git();
+    command.args(["mob", "team-member", "--import-from-log"]);
+
+    let mut session = spawn_command(command, Some(5000))?;
+    session.exp_string("Select team member(s) to import")?;
+    session.exp_string("lm Leo Messi <leo.messi@example.com>")?;
+    session.exp_string("em Emi Martinez <emi.martinez@example.com>")?;
+
+    // pressing enter to import all proposed team members
+    session.send_control('m')?;
+    session.process.wait()?;
+
+    ctx.git()
+        .args(["mob", "team-member", "--list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "cd Cata Diaz <cata.diaz@example.com> (user)\nlm Leo Messi <leo.messi@example.com> (user)\nem Emi Martinez <emi.martinez@example.com> (user)\n",
+        ));
+
+    // running again finds no new team members
+    ctx.git()
+        .args(["mob", "team-member", "--import-from-log"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "No new team member(s) found in git log\n",
+        ));
+
+    Ok(())
+}
+
+#[test_context(TestContextRepo, skip_teardown)]
+#[test]
+fn test_export_and_import_team_members(ctx: TestContextRepo) -> Result<(), Box<dyn Error>> {
+    add_team_member(&ctx, "lm", "Leo Messi", "leo.messi@example.com");
+    add_team_member(&ctx, "em", "Emi Martinez", "emi.martinez@example.com");
+
+    // exporting team members
+    let output = ctx
+        .git()
+        .args(["mob", "team-member", "--export", "--roster-format", "csv"])
+        .output()?;
+    assert_eq!(
+        String::from_utf8(output.stdout)?,
+        "key,name,email\nlm,Leo Messi,leo.messi@example.com\nem,Emi Martinez,emi.martinez@example.com\n"
+    );
+
+    let roster_file = ctx.home_dir.path().join("roster.json");
+    fs::write(
+        &roster_file,
+        r#"[
+  { "key": "em", "name": "Emiliano Martinez", "email": "emi.martinez@example.com" },
+  { "key": "ad", "name": "Angel Di Maria", "email": "angel.dimaria@example.com" }
+]"#,
+    )?;
+
+    // dry run shows the changes without making them
+    ctx.git()
+        .args(["mob", "team-member", "--import"])
+        .arg(&roster_file)
+        .args(["--on-conflict", "overwrite", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "- em Emi Martinez <emi.martinez@example.com>\n+ em Emiliano Martinez <emi.martinez@example.com>\n+ ad Angel Di Maria <angel.dimaria@example.com>\n",
+        ));
+
+    ctx.git()
+        .args(["mob", "team-member", "--import"])
+        .arg(&roster_file)
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "! em Emiliano Martinez <emi.martinez@example.com> (skipped)\n+ ad Angel Di Maria <angel.dimaria@example.com>\n",
+        ));
+
+    ctx.git()
+        .args(["mob", "team-member", "--list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "lm Leo Messi <leo.messi@example.com> (user)\nem Emi Martinez <emi.martinez@example.com> (user)\nad Angel Di Maria <angel.dimaria@example.com> (user)\n",
+        ));
+
+    Ok(())
+}
+
+fn add_team_member(ctx: &TestContextRepo, key: &str, name: &str, email: &str) {
+    ctx.git()
+        .args(["mob", "team-member", "--add", key, name, email])
+        .assert()
+        .success();
+}
+
+#[test_context(TestContextRepo, skip_teardown)]
+#[test]
+fn test_team_members_given_git_coauthors_file(ctx: TestContextRepo) -> Result<(), Box<dyn Error>> {
+    let coauthors_file = ctx.home_dir.path().join(".git-coauthors");
+    fs::write(
+        &coauthors_file,
+        r#"{
+  "coauthors": {
+    "lm": {
+      "name": "Leo Messi",
+      "email": "leo.messi@example.com"
+    }
+  }
+}
+"#,
+    )?;
+
+    // ~/.git-coauthors is ignored unless syncing it is enabled
+    ctx.git().args(["mob", "--with", "lm"]).assert().failure();
+
+    ctx.git()
+        .args([
+            "config",
+            "--global",
+            "coauthors-mob-settings.syncCoauthorsFile",
+            "true",
+        ])
+        .assert()
+        .success();
+
+    // team members of ~/.git-coauthors can be used
+    ctx.git()
+        .args(["mob", "--with", "lm"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("Leo Messi <leo.messi@example.com>\n"));
+
+    // team members added are kept in sync with ~/.git-coauthors
+    add_team_member(&ctx, "em", "Emi Martinez", "emi.martinez@example.com");
+
+    assert_eq!(
+        fs::read_to_string(&coauthors_file)?,
+        r#"{
+  "coauthors": {
+    "lm": {
+      "name": "Leo Messi",
+      "email": "leo.messi@example.com"
+    },
+    "em": {
+      "name": "Emi Martinez",
+      "email": "emi.martinez@example.com"
+    }
+  }
+}
+"#
+    );
+
+    // team members only in ~/.git-coauthors come from a further layer as the sync is one way
+    ctx.git()
+        .args(["mob", "team-member", "--list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "em Emi Martinez <emi.martinez@example.com> (user)\nlm Leo Messi <leo.messi@example.com> (git-coauthors)\n",
+        ));
+
+    ctx.git()
+        .args(["mob", "team-member", "--delete", "lm"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::diff(
+            "Error: \"Team member with key lm comes from the git-coauthors roster and can only be hidden\"\n",
+        ));
+
+    ctx.git()
+        .args(["mob", "team-member", "--hide", "lm"])
+        .assert()
+        .success();
+
+    ctx.git()
+        .args(["mob", "team-member", "--list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "em Emi Martinez <emi.martinez@example.com> (user)\n",
+        ));
+
+    ctx.git()
+        .args(["mob", "team-member", "--export", "--roster-format", "git-coauthors"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "{\n  \"coauthors\": {\n    \"em\": {\n      \"name\": \"Emi Martinez\",\n      \"email\": \"emi.martinez@example.com\"\n    }\n  }\n}\n",
+        ));
+
+    // team members deleted are kept in sync with ~/.git-coauthors
+    ctx.git()
+        .args(["mob", "team-member", "--delete", "em"])
+        .assert()
+        .success();
+
+    assert_eq!(
+        fs::read_to_string(&coauthors_file)?,
+        r#"{
+  "coauthors": {
+    "lm": {
+      "name": "Leo Messi",
+      "email": "leo.messi@example.com"
+    }
+  }
+}
+"#
+    );
+
+    Ok(())
+}
+
+#[test_context(TestContextRepo, skip_teardown)]
+#[test]
+fn test_import_team_members_from_other_tools(ctx: TestContextRepo) -> Result<(), Box<dyn Error>> {
+    fs::write(
+        ctx.home_dir.path().join(".git-authors"),
+        "pairs:\n  lm: Leo Messi; leo.messi\n  ad: Angel Di Maria\nemail:\n  domain: example.com\n",
+    )?;
+
+    // importing from ~/.git-authors of git-duet
+    ctx.git()
+        .args(["mob", "team-member", "--import", "--from", "git-duet"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "! ad (cannot convert: no email address)\n+ lm Leo Messi <leo.messi@example.com>\n",
+        ));
+
+    for (key, value) in [
+        ("git-together.domain", "example.com"),
+        ("git-together.authors.em", "Emi Martinez; emi.martinez"),
+        ("git-together.authors.lm", "Leo Messi; leo.messi"),
+    ] {
+        ctx.git()
+            .args(["config", "--global", key, value])
+            .assert()
+            .success();
+    }
+
+    // importing from git config of git-together
+    ctx.git()
+        .args(["mob", "team-member", "--import", "--from", "git-together"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "+ em Emi Martinez <emi.martinez@example.com>\n",
+        ));
+
+    ctx.git()
+        .args(["mob", "team-member", "--list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "lm Leo Messi <leo.messi@example.com> (user)\nem Emi Martinez <emi.martinez@example.com> (user)\n",
+        ));
+
+    // a file is required unless importing from another tool
+    ctx.git()
+        .args(["mob", "team-member", "--import"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::diff(
+            "Error: \"A file to import is required unless --from is given\"\n",
+        ));
+
+    Ok(())
+}
+
+#[test_context(TestContextCli, skip_teardown)]
+#[test]
+fn test_add_member_when_key_exists(ctx: TestContextCli) -> Result<(), Box<dyn Error>> {
+    ctx.git()
+        .args([
+            "mob",
+            "team-member",
+            "--add",
+            "lm",
+            "Leo Messi",
+            "leo.messi@example.com",
+        ])
+        .assert()
+        .success();
+
+    ctx.git()
+        .args([
+            "mob",
+            "team-member",
+            "--add",
+            "lm",
+            "Lionel Messi",
+            "lionel.messi@example.com",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::diff(
+            "Error: \"Team member with key lm already exists. Use --force to overwrite it\"\n",
+        ));
+
+    ctx.git()
+        .args([
+            "mob",
+            "team-member",
+            "--add",
+            "lm",
+            "Lionel Messi",
+            "lionel.messi@example.com",
+            "--force",
+        ])
+        .assert()
+        .success();
+
+    ctx.git()
+        .args(["mob", "team-member", "--list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "lm Lionel Messi <lionel.messi@example.com> (user)\n",
+        ));
+
+    Ok(())
+}
+
+#[test_context(TestContextCli, skip_teardown)]
+#[test]
+fn test_edit_member(ctx: TestContextCli) -> Result<(), Box<dyn Error>> {
+    ctx.git()
+        .args([
+            "mob",
+            "team-member",
+            "--add",
+            "lm",
+            "Leo Messi",
+            "leo.messi@example.com",
+        ])
+        .assert()
+        .success();
+    ctx.git()
+        .args(["mob", "team", "--add", "frontend", "lm"])
+        .assert()
+        .success();
+    ctx.git().args(["mob", "--with", "lm"]).assert().success();
+    ctx.git()
+        .args(["mob", "preset", "save", "payments-mob"])
+        .assert()
+        .success();
+
+    // re-keying and changing email updates the team group and the preset
+    ctx.git()
+        .args([
+            "mob",
+            "team-member",
+            "--edit",
+            "lm",
+            "--key",
+            "leo",
+            "--email",
+            "leo@example.com",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "Leo Messi <leo@example.com>\nUpdated team group @frontend\nUpdated preset payments-mob\n",
+        ));
+
+    ctx.git()
+        .args(["mob", "team-member", "--list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "leo Leo Messi <leo@example.com> (user)\n",
+        ));
+
+    ctx.git()
+        .args(["mob", "team", "--list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("@frontend leo\n"));
+
+    ctx.git()
+        .args(["mob", "preset", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "payments-mob: Leo Messi <leo@example.com>\n",
+        ));
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test_context(TestContextCli, skip_teardown)]
+#[test]
+fn test_edit_member_interactively(ctx: TestContextCli) -> Result<(), Box<dyn Error>> {
+    ctx.git()
+        .args([
+            "mob",
+            "team-member",
+            "--add",
+            "lm",
+            "Leo",
+            "leo.messi@example.com",
+        ])
+        .assert()
+        .success();
+
+    // running command to display the prompts prefilled with the current values
+    let mut command = ctx.git();
+    command.args(["mob", "team-member", "--edit", "lm"]);
+
+    let mut session = spawn_command(command, Some(5000))?;
+    session.exp_string("Key:")?;
+    session.send_control('m')?;
+    // appending the surname to the prefilled name
+    session.exp_string("Name:")?;
+    session.send_line(" Messi")?;
+    session.exp_string("Email:")?;
+    session.send_control('m')?;
+    session.process.wait()?;
+
+    ctx.git()
+        .args(["mob", "team-member", "--list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "lm Leo Messi <leo.messi@example.com> (user)\n",
+        ));
+
+    Ok(())
+}
+
+#[test_context(TestContextCli, skip_teardown)]
+#[test]
+fn test_team_member_aliases(ctx: TestContextCli) -> Result<(), Box<dyn Error>> {
+    ctx.git()
+        .args([
+            "mob",
+            "team-member",
+            "--add",
+            "lm",
+            "Leo Messi",
+            "leo.messi@example.com",
+        ])
+        .assert()
+        .success();
+
+    ctx.git()
+        .args(["mob", "team-member", "--alias", "leo", "lm"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "leo Leo Messi <leo.messi@example.com>\n",
+        ));
+
+    // an alias can also be given instead of the key
+    ctx.git()
+        .args(["mob", "team-member", "--alias", "messi", "leo"])
+        .assert()
+        .success();
+
+    ctx.git()
+        .args(["mob", "team-member", "--list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "lm Leo Messi <leo.messi@example.com> (user)\n  aliases: leo, messi\n",
+        ));
+
+    // the key and its aliases resolve to the same co-author
+    ctx.git()
+        .args(["mob", "--with", "leo", "lm", "messi"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("Leo Messi <leo.messi@example.com>\n"));
+
+    ctx.git()
+        .args(["mob", "team-member", "--alias", "leo", "lm"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::diff(
+            "Error: \"leo is already the key or an alias of Leo Messi <leo.messi@example.com>\"\n",
+        ));
+
+    // deleting an alias keeps the team member
+    ctx.git()
+        .args(["mob", "team-member", "--delete", "messi"])
+        .assert()
+        .success();
+
+    ctx.git()
+        .args(["mob", "team-member", "--list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "lm Leo Messi <leo.messi@example.com> (user)\n  aliases: leo\n",
+        ));
+
+    Ok(())
+}